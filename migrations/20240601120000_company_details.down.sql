@@ -0,0 +1,10 @@
+-- Add down migration script here
+ALTER TABLE company DROP COLUMN note;
+ALTER TABLE company DROP COLUMN contract_number;
+ALTER TABLE company DROP COLUMN bic;
+ALTER TABLE company DROP COLUMN iban;
+ALTER TABLE company DROP COLUMN email;
+ALTER TABLE company DROP COLUMN phone;
+ALTER TABLE company DROP COLUMN contact_person;
+ALTER TABLE company DROP COLUMN legal_address;
+ALTER TABLE company DROP COLUMN tax_id;
@@ -0,0 +1,10 @@
+-- Add up migration script here
+ALTER TABLE company ADD COLUMN tax_id TEXT NOT NULL DEFAULT '';
+ALTER TABLE company ADD COLUMN legal_address TEXT NOT NULL DEFAULT '';
+ALTER TABLE company ADD COLUMN contact_person TEXT NOT NULL DEFAULT '';
+ALTER TABLE company ADD COLUMN phone TEXT NOT NULL DEFAULT '';
+ALTER TABLE company ADD COLUMN email TEXT NOT NULL DEFAULT '';
+ALTER TABLE company ADD COLUMN iban TEXT NOT NULL DEFAULT '';
+ALTER TABLE company ADD COLUMN bic TEXT NOT NULL DEFAULT '';
+ALTER TABLE company ADD COLUMN contract_number TEXT NOT NULL DEFAULT '';
+ALTER TABLE company ADD COLUMN note TEXT NOT NULL DEFAULT '';
@@ -0,0 +1,29 @@
+-- Add down migration script here
+-- a column used in a foreign key can't be dropped, so the table is rebuilt without it
+CREATE TABLE company_without_group (
+    id INTEGER NOT NULL CONSTRAINT PK_company PRIMARY KEY,
+    name TEXT NOT NULL,
+    remainder_begin_month REAL NOT NULL,
+    debit_turnover REAL NOT NULL,
+    credit_turnover REAL NOT NULL,
+    remainder_end_month REAL NOT NULL,
+    tax_id TEXT NOT NULL DEFAULT '',
+    legal_address TEXT NOT NULL DEFAULT '',
+    contact_person TEXT NOT NULL DEFAULT '',
+    phone TEXT NOT NULL DEFAULT '',
+    email TEXT NOT NULL DEFAULT '',
+    iban TEXT NOT NULL DEFAULT '',
+    bic TEXT NOT NULL DEFAULT '',
+    contract_number TEXT NOT NULL DEFAULT '',
+    note TEXT NOT NULL DEFAULT ''
+);
+
+INSERT INTO company_without_group
+SELECT id, name, remainder_begin_month, debit_turnover, credit_turnover, remainder_end_month,
+    tax_id, legal_address, contact_person, phone, email, iban, bic, contract_number, note
+FROM company;
+
+DROP TABLE company;
+ALTER TABLE company_without_group RENAME TO company;
+
+DROP TABLE company_group;
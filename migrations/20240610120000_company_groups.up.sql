@@ -0,0 +1,14 @@
+-- Add up migration script here
+CREATE TABLE company_group (
+    id INTEGER NOT NULL CONSTRAINT PK_company_group PRIMARY KEY,
+    name TEXT NOT NULL,
+    parent_id INTEGER NULL CONSTRAINT FK_company_group_parent REFERENCES company_group (id) ON DELETE CASCADE
+);
+
+INSERT INTO company_group (name) VALUES
+    ('Поставщики'),
+    ('Покупатели'),
+    ('Дочерние компании'),
+    ('Сотрудники');
+
+ALTER TABLE company ADD COLUMN group_id INTEGER NULL CONSTRAINT FK_company_company_group REFERENCES company_group (id) ON DELETE SET NULL;
@@ -0,0 +1,25 @@
+-- Add down migration script here
+ALTER TABLE company ADD COLUMN remainder_begin_month REAL NOT NULL DEFAULT 0;
+ALTER TABLE company ADD COLUMN debit_turnover REAL NOT NULL DEFAULT 0;
+ALTER TABLE company ADD COLUMN credit_turnover REAL NOT NULL DEFAULT 0;
+ALTER TABLE company ADD COLUMN remainder_end_month REAL NOT NULL DEFAULT 0;
+
+-- balances on different accounts can't be told apart on a single sheet, they are added up
+UPDATE company
+SET remainder_begin_month = totals.remainder_begin_month,
+    debit_turnover = totals.debit_turnover,
+    credit_turnover = totals.credit_turnover,
+    remainder_end_month = totals.remainder_end_month
+FROM (
+    SELECT company_id,
+        SUM(remainder_begin_month) AS remainder_begin_month,
+        SUM(debit_turnover) AS debit_turnover,
+        SUM(credit_turnover) AS credit_turnover,
+        SUM(remainder_end_month) AS remainder_end_month
+    FROM balance
+    GROUP BY company_id
+) AS totals
+WHERE company.id = totals.company_id;
+
+DROP TABLE balance;
+DROP TABLE account;
@@ -0,0 +1,33 @@
+-- Add up migration script here
+CREATE TABLE account (
+    id INTEGER NOT NULL CONSTRAINT PK_account PRIMARY KEY,
+    code TEXT NOT NULL CONSTRAINT UQ_account_code UNIQUE,
+    name TEXT NOT NULL,
+    kind TEXT NOT NULL CONSTRAINT CK_account_kind CHECK (kind IN ('active', 'passive', 'active_passive'))
+);
+
+INSERT INTO account (code, name, kind) VALUES
+    ('60', 'Расчёты с поставщиками и подрядчиками', 'active_passive'),
+    ('62', 'Расчёты с покупателями и заказчиками', 'active_passive'),
+    ('71', 'Расчёты с подотчётными лицами', 'active_passive');
+
+CREATE TABLE balance (
+    company_id INTEGER NOT NULL CONSTRAINT FK_balance_company REFERENCES company (id) ON DELETE CASCADE,
+    account_id INTEGER NOT NULL CONSTRAINT FK_balance_account REFERENCES account (id) ON DELETE CASCADE,
+    remainder_begin_month REAL NOT NULL,
+    debit_turnover REAL NOT NULL,
+    credit_turnover REAL NOT NULL,
+    remainder_end_month REAL NOT NULL,
+    CONSTRAINT PK_balance PRIMARY KEY (company_id, account_id)
+);
+
+-- everything entered so far was kept on a single sheet, it goes to suppliers
+INSERT INTO balance
+SELECT id, (SELECT id FROM account WHERE code = '60'),
+    remainder_begin_month, debit_turnover, credit_turnover, remainder_end_month
+FROM company;
+
+ALTER TABLE company DROP COLUMN remainder_begin_month;
+ALTER TABLE company DROP COLUMN debit_turnover;
+ALTER TABLE company DROP COLUMN credit_turnover;
+ALTER TABLE company DROP COLUMN remainder_end_month;
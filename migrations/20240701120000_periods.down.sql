@@ -0,0 +1,28 @@
+-- Add down migration script here
+DROP TRIGGER TR_period_delete_closed;
+DROP TRIGGER TR_balance_delete_closed;
+DROP TRIGGER TR_balance_update_closed;
+DROP TRIGGER TR_balance_insert_closed;
+
+CREATE TABLE balance_without_period (
+    company_id INTEGER NOT NULL CONSTRAINT FK_balance_company REFERENCES company (id) ON DELETE CASCADE,
+    account_id INTEGER NOT NULL CONSTRAINT FK_balance_account REFERENCES account (id) ON DELETE CASCADE,
+    remainder_begin_month REAL NOT NULL,
+    debit_turnover REAL NOT NULL,
+    credit_turnover REAL NOT NULL,
+    remainder_end_month REAL NOT NULL,
+    CONSTRAINT PK_balance PRIMARY KEY (company_id, account_id)
+);
+
+-- only the latest period fits on a sheet without periods
+INSERT INTO balance_without_period
+SELECT company_id, account_id,
+    remainder_begin_month, debit_turnover, credit_turnover, remainder_end_month
+FROM balance
+WHERE period_id = (SELECT id FROM period ORDER BY year DESC, month DESC LIMIT 1);
+
+DROP TABLE balance;
+ALTER TABLE balance_without_period RENAME TO balance;
+
+DROP TABLE period_log;
+DROP TABLE period;
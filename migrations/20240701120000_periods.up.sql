@@ -0,0 +1,64 @@
+-- Add up migration script here
+CREATE TABLE period (
+    id INTEGER NOT NULL CONSTRAINT PK_period PRIMARY KEY,
+    year INTEGER NOT NULL,
+    month INTEGER NOT NULL CONSTRAINT CK_period_month CHECK (month BETWEEN 1 AND 12),
+    closed BOOLEAN NOT NULL DEFAULT FALSE,
+    CONSTRAINT UQ_period UNIQUE (year, month)
+);
+
+-- every close and reopen is kept, a period can go back and forth several times
+CREATE TABLE period_log (
+    id INTEGER NOT NULL CONSTRAINT PK_period_log PRIMARY KEY,
+    period_id INTEGER NOT NULL CONSTRAINT FK_period_log_period REFERENCES period (id) ON DELETE CASCADE,
+    action TEXT NOT NULL CONSTRAINT CK_period_log_action CHECK (action IN ('close', 'reopen')),
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'localtime'))
+);
+
+INSERT INTO period (year, month)
+VALUES (CAST(strftime('%Y', 'now') AS INTEGER), CAST(strftime('%m', 'now') AS INTEGER));
+
+CREATE TABLE balance_with_period (
+    company_id INTEGER NOT NULL CONSTRAINT FK_balance_company REFERENCES company (id) ON DELETE CASCADE,
+    account_id INTEGER NOT NULL CONSTRAINT FK_balance_account REFERENCES account (id) ON DELETE CASCADE,
+    period_id INTEGER NOT NULL CONSTRAINT FK_balance_period REFERENCES period (id) ON DELETE CASCADE,
+    remainder_begin_month REAL NOT NULL,
+    debit_turnover REAL NOT NULL,
+    credit_turnover REAL NOT NULL,
+    remainder_end_month REAL NOT NULL,
+    CONSTRAINT PK_balance PRIMARY KEY (company_id, account_id, period_id)
+);
+
+INSERT INTO balance_with_period
+SELECT company_id, account_id, (SELECT id FROM period),
+    remainder_begin_month, debit_turnover, credit_turnover, remainder_end_month
+FROM balance;
+
+DROP TABLE balance;
+ALTER TABLE balance_with_period RENAME TO balance;
+
+-- balances of a closed period can't change whichever way the write comes
+CREATE TRIGGER TR_balance_insert_closed BEFORE INSERT ON balance
+WHEN (SELECT closed FROM period WHERE id = NEW.period_id)
+BEGIN
+    SELECT RAISE(ABORT, 'period is closed');
+END;
+
+CREATE TRIGGER TR_balance_update_closed BEFORE UPDATE ON balance
+WHEN (SELECT closed FROM period WHERE id = OLD.period_id)
+    OR (SELECT closed FROM period WHERE id = NEW.period_id)
+BEGIN
+    SELECT RAISE(ABORT, 'period is closed');
+END;
+
+CREATE TRIGGER TR_balance_delete_closed BEFORE DELETE ON balance
+WHEN (SELECT closed FROM period WHERE id = OLD.period_id)
+BEGIN
+    SELECT RAISE(ABORT, 'period is closed');
+END;
+
+CREATE TRIGGER TR_period_delete_closed BEFORE DELETE ON period
+WHEN OLD.closed
+BEGIN
+    SELECT RAISE(ABORT, 'period is closed');
+END;
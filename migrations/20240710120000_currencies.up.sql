@@ -0,0 +1,12 @@
+-- Add up migration script here
+-- an empty currency is the reporting one
+ALTER TABLE company ADD COLUMN currency TEXT NOT NULL DEFAULT '';
+
+-- units of the reporting currency per unit of the currency at the end of the month
+CREATE TABLE exchange_rate (
+    currency TEXT NOT NULL,
+    year INTEGER NOT NULL,
+    month INTEGER NOT NULL CONSTRAINT CK_exchange_rate_month CHECK (month BETWEEN 1 AND 12),
+    rate REAL NOT NULL CONSTRAINT CK_exchange_rate_rate CHECK (rate > 0),
+    CONSTRAINT PK_exchange_rate PRIMARY KEY (currency, year, month)
+);
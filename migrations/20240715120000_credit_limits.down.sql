@@ -0,0 +1,3 @@
+-- Add down migration script here
+ALTER TABLE company DROP COLUMN credit_limit;
+ALTER TABLE company DROP COLUMN debit_limit;
@@ -0,0 +1,4 @@
+-- Add up migration script here
+-- in the company's currency, no limit when empty
+ALTER TABLE company ADD COLUMN debit_limit REAL CONSTRAINT CK_company_debit_limit CHECK (debit_limit >= 0);
+ALTER TABLE company ADD COLUMN credit_limit REAL CONSTRAINT CK_company_credit_limit CHECK (credit_limit >= 0);
@@ -0,0 +1,19 @@
+-- Add up migration script here
+-- the first rule a company matches, in the order they were added, colors its row
+CREATE TABLE highlight_rule (
+    id INTEGER NOT NULL CONSTRAINT PK_highlight_rule PRIMARY KEY,
+    field TEXT NOT NULL CONSTRAINT CK_highlight_rule_field CHECK (field IN (
+        'opening_debit', 'opening_credit', 'debit_turnover', 'credit_turnover', 'turnover',
+        'closing_debit', 'closing_credit'
+    )),
+    comparison TEXT NOT NULL CONSTRAINT CK_highlight_rule_comparison CHECK (comparison IN (
+        'greater', 'greater_or_equal', 'less', 'less_or_equal', 'equal', 'not_equal'
+    )),
+    value REAL NOT NULL,
+    -- RRGGBB
+    color TEXT NOT NULL CONSTRAINT CK_highlight_rule_color CHECK (length(color) = 6)
+);
+
+INSERT INTO highlight_rule (field, comparison, value, color) VALUES
+    ('closing_credit', 'greater', 100000, 'E06666'),
+    ('turnover', 'equal', 0, 'B7B7B7');
@@ -0,0 +1,14 @@
+-- Add up migration script here
+-- every merge of a duplicate into the company that is kept, the duplicate itself is gone
+CREATE TABLE company_merge_log (
+    id INTEGER NOT NULL CONSTRAINT PK_company_merge_log PRIMARY KEY,
+    company_id INTEGER NOT NULL CONSTRAINT FK_company_merge_log_company REFERENCES company (id) ON DELETE CASCADE,
+    merged_id INTEGER NOT NULL,
+    merged_name TEXT NOT NULL,
+    merged_tax_id TEXT NOT NULL,
+    -- balances of the duplicate moved over as they were
+    balances_moved INTEGER NOT NULL,
+    -- balances added up with the ones the company had on the same account and period
+    balances_combined INTEGER NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'localtime'))
+);
@@ -0,0 +1,5 @@
+-- Add down migration script here
+DROP TRIGGER TR_company_search_update;
+DROP TRIGGER TR_company_search_delete;
+DROP TRIGGER TR_company_search_insert;
+DROP TABLE company_search;
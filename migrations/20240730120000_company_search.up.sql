@@ -0,0 +1,38 @@
+-- Add up migration script here
+-- the text of every company for full-text search, kept in step with the table by the triggers
+CREATE VIRTUAL TABLE company_search USING fts5 (
+    name,
+    tax_id,
+    legal_address,
+    contact_person,
+    phone,
+    email,
+    iban,
+    contract_number,
+    note,
+    content = 'company',
+    content_rowid = 'id',
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+INSERT INTO company_search (company_search) VALUES ('rebuild');
+
+CREATE TRIGGER TR_company_search_insert AFTER INSERT ON company
+BEGIN
+    INSERT INTO company_search (rowid, name, tax_id, legal_address, contact_person, phone, email, iban, contract_number, note)
+    VALUES (NEW.id, NEW.name, NEW.tax_id, NEW.legal_address, NEW.contact_person, NEW.phone, NEW.email, NEW.iban, NEW.contract_number, NEW.note);
+END;
+
+CREATE TRIGGER TR_company_search_delete AFTER DELETE ON company
+BEGIN
+    INSERT INTO company_search (company_search, rowid, name, tax_id, legal_address, contact_person, phone, email, iban, contract_number, note)
+    VALUES ('delete', OLD.id, OLD.name, OLD.tax_id, OLD.legal_address, OLD.contact_person, OLD.phone, OLD.email, OLD.iban, OLD.contract_number, OLD.note);
+END;
+
+CREATE TRIGGER TR_company_search_update AFTER UPDATE ON company
+BEGIN
+    INSERT INTO company_search (company_search, rowid, name, tax_id, legal_address, contact_person, phone, email, iban, contract_number, note)
+    VALUES ('delete', OLD.id, OLD.name, OLD.tax_id, OLD.legal_address, OLD.contact_person, OLD.phone, OLD.email, OLD.iban, OLD.contract_number, OLD.note);
+    INSERT INTO company_search (rowid, name, tax_id, legal_address, contact_person, phone, email, iban, contract_number, note)
+    VALUES (NEW.id, NEW.name, NEW.tax_id, NEW.legal_address, NEW.contact_person, NEW.phone, NEW.email, NEW.iban, NEW.contract_number, NEW.note);
+END;
@@ -0,0 +1,11 @@
+-- Add up migration script here
+CREATE TABLE tag (
+    id INTEGER NOT NULL CONSTRAINT PK_tag PRIMARY KEY,
+    name TEXT NOT NULL COLLATE NOCASE CONSTRAINT UQ_tag_name UNIQUE
+);
+
+CREATE TABLE company_tag (
+    company_id INTEGER NOT NULL CONSTRAINT FK_company_tag_company REFERENCES company (id) ON DELETE CASCADE,
+    tag_id INTEGER NOT NULL CONSTRAINT FK_company_tag_tag REFERENCES tag (id) ON DELETE CASCADE,
+    CONSTRAINT PK_company_tag PRIMARY KEY (company_id, tag_id)
+);
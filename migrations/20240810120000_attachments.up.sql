@@ -0,0 +1,11 @@
+-- Add up migration script here
+-- scanned contracts, reconciliation acts and the like, kept with the counterparty
+CREATE TABLE attachment (
+    id INTEGER NOT NULL CONSTRAINT PK_attachment PRIMARY KEY,
+    company_id INTEGER NOT NULL CONSTRAINT FK_attachment_company REFERENCES company (id) ON DELETE CASCADE,
+    file_name TEXT NOT NULL,
+    data BLOB NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now', 'localtime'))
+);
+
+CREATE INDEX IX_attachment_company ON attachment (company_id);
@@ -1,20 +1,86 @@
-use std::sync::mpsc::{self, Receiver, Sender};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::mpsc::{self, Receiver, Sender},
+};
 
-use egui::global_dark_light_mode_buttons;
 use sqlx::SqlitePool;
+use strum::IntoEnumIterator;
 
+mod accounts;
+mod aging;
+mod attachments;
+mod bulk;
+pub mod cli;
+mod clipboard;
+mod currency;
+mod dashboard;
+mod dataset;
+mod details;
+mod duplicates;
 mod exports;
+mod expression;
+mod format;
+mod groups;
+mod highlight;
+mod i18n;
+mod import;
+mod limits;
 mod map;
 mod model;
 mod operations;
+mod organization;
+mod periods;
+mod reconciliation;
+mod search;
 mod table;
+mod tags;
+mod validation;
 
 use self::{
-    exports::{export_to_excel, map_to_excel},
+    accounts::{account_selector, view_label, AccountAction, AccountEditor},
+    aging::{aging_rows, aging_ui, AgingAction, AgingRow},
+    attachments::AttachmentAction,
+    bulk::BulkEditor,
+    clipboard::{companies_to_tsv, parse_tsv},
+    currency::{
+        parse_rates_csv, revaluate, revaluation_window, Currencies, RatesAction, RatesEditor,
+    },
+    dashboard::Dashboard,
+    dataset::{Dataset, DatasetCounts, DatasetError, DatasetImportDialog, DatasetMode},
+    details::{DetailsAction, DetailsForm, DetailsTab},
+    duplicates::{find_duplicates, DuplicatesWindow, MergeRequest},
+    exports::{
+        aging_to_excel, build_sheet, export_to_excel, export_to_html, export_to_ods, export_to_pdf,
+        periods_to_excel, reconciliation_to_excel, reconciliation_to_pdf, PeriodSheet,
+        Reconciliation, SheetTitle, WorkbookCover,
+    },
+    format::{separator_label, NumberFormat},
+    groups::{GroupAction, GroupEditor, GroupError},
+    highlight::{RuleAction, RulesEditor},
+    i18n::{Language, Message},
+    import::{import_file, ImportError, Imported},
+    limits::alerts_ui,
     map::{map_to_edited, map_to_new},
-    model::Company,
-    operations::{add_company, delete_company, edit_company, get_all_companies, Operation},
+    model::{
+        Account, AccountView, BulkChange, Company, ExchangeRate, Group, HighlightRule,
+        Organization, Period, PeriodBalance, SearchHit, Sheet, Tag,
+    },
+    operations::{
+        add_account, add_attachments, add_company, add_group, add_next_period, add_rule, bulk_edit,
+        delete_account, delete_attachment, delete_company, delete_group, delete_rate, delete_rule,
+        delete_tag, edit_account, edit_company, edit_company_details, edit_group, edit_rule,
+        export_dataset, get_all_accounts, get_all_companies, get_all_groups, get_all_periods,
+        get_all_rates, get_all_rules, get_all_tags, get_attachment_data, get_attachments,
+        get_balance_history, get_company_history, get_company_tags, get_period_totals,
+        import_dataset, merge_companies, save_rates, search_companies, set_company_tags,
+        set_period_closed, Operation,
+    },
+    organization::organization_window,
+    periods::{period_selector, PeriodRangeDialog, ReopenDialog},
+    reconciliation::{ReconciliationDialog, ReconciliationFormat, ReconciliationRequest},
+    search::search_popup,
     table::CompanyTable,
+    tags::{company_tags, tag_filter_menu, tag_totals, tags_of, CompanyTags, TagsWindow},
 };
 
 pub struct MyApp {
@@ -24,13 +90,29 @@ pub struct MyApp {
     state: State,
 }
 
-#[derive(strum::Display)]
 enum Mode {
     Normal,
     Add,
     Edit,
 }
 
+#[derive(PartialEq)]
+enum Tab {
+    Table,
+    Dashboard,
+    Aging,
+}
+
+impl Mode {
+    fn title(&self) -> Message {
+        match self {
+            Mode::Normal => Message::NormalMode,
+            Mode::Add => Message::AddMode,
+            Mode::Edit => Message::EditMode,
+        }
+    }
+}
+
 #[derive(Default, Debug)]
 pub struct EditedCompanyRow {
     pub id: i64,
@@ -50,7 +132,7 @@ pub struct NewCompanyRow {
     pub credit_turnover: String,
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 pub struct TotalRow {
     pub remainder_begin_month_pos: f64,
     pub remainder_begin_month_neg: f64,
@@ -60,6 +142,45 @@ pub struct TotalRow {
     pub remainder_end_month_neg: f64,
 }
 
+impl<'a> FromIterator<&'a Company> for TotalRow {
+    fn from_iter<T: IntoIterator<Item = &'a Company>>(iter: T) -> Self {
+        iter.into_iter().fold(TotalRow::default(), |acc, constant| {
+            let positive_begin = constant.remainder_begin_month >= 0.;
+            let remainder_begin = constant.remainder_begin_month.abs();
+
+            let positive_end = constant.remainder_end_month >= 0.;
+            let remainder_end = constant.remainder_end_month.abs();
+
+            TotalRow {
+                remainder_begin_month_pos: if positive_begin {
+                    acc.remainder_begin_month_pos + remainder_begin
+                } else {
+                    acc.remainder_begin_month_pos
+                },
+                remainder_begin_month_neg: if !positive_begin {
+                    acc.remainder_begin_month_neg + remainder_begin
+                } else {
+                    acc.remainder_begin_month_neg
+                },
+                debit_turnover: acc.debit_turnover + constant.debit_turnover,
+                credit_turnover: acc.credit_turnover + constant.credit_turnover,
+                remainder_end_month_pos: if positive_end {
+                    acc.remainder_end_month_pos + remainder_end
+                } else {
+                    acc.remainder_end_month_pos
+                },
+                remainder_end_month_neg: if !positive_end {
+                    acc.remainder_end_month_neg + remainder_end
+                } else {
+                    acc.remainder_end_month_neg
+                },
+            }
+        })
+    }
+}
+
+// nearly every row is a company, boxing them would only add indirection
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug)]
 pub enum Row {
     Constant(Company),
@@ -78,6 +199,20 @@ impl Row {
     }
 }
 
+#[derive(serde::Deserialize, serde::Serialize, Default)]
+#[serde(default)]
+pub struct Settings {
+    number_format: NumberFormat,
+    language: Language,
+    show_groups: bool,
+    account: AccountView,
+    /// `None` follows the latest period.
+    period_id: Option<i64>,
+    organization: Organization,
+    /// Balances in other currencies are converted to this one, empty when nothing is converted.
+    reporting_currency: String,
+}
+
 // #[derive(serde::Deserialize, serde::Serialize)]
 pub struct State {
     // #[serde(skip)]
@@ -89,7 +224,76 @@ pub struct State {
 
     need_to_calculate_total: bool,
 
+    need_to_calculate_aging: bool,
+
     selected_rows: std::collections::HashSet<usize>,
+
+    settings: Settings,
+
+    messages: Vec<String>,
+
+    tab: Tab,
+
+    scroll_to_row: Option<usize>,
+
+    filter: String,
+
+    details: Option<DetailsForm>,
+
+    groups: Vec<Group>,
+
+    collapsed_groups: std::collections::HashSet<Option<i64>>,
+
+    group_editor: GroupEditor,
+
+    accounts: Vec<Account>,
+
+    account_editor: AccountEditor,
+
+    periods: Vec<Period>,
+
+    period_totals: Vec<(i64, TotalRow)>,
+
+    balance_history: Vec<(i64, PeriodBalance)>,
+
+    /// The aging report of the selected period, made again when what it's built from arrives.
+    aging: Vec<AgingRow>,
+
+    reopen_dialog: ReopenDialog,
+    period_range_dialog: PeriodRangeDialog,
+    dataset_dialog: DatasetImportDialog,
+
+    organization_open: bool,
+
+    reconciliation_dialog: ReconciliationDialog,
+
+    rates: Vec<ExchangeRate>,
+
+    rates_editor: RatesEditor,
+
+    revaluation_open: bool,
+
+    over_limit_only: bool,
+
+    rules: Vec<HighlightRule>,
+
+    rules_editor: RulesEditor,
+
+    bulk_editor: BulkEditor,
+
+    duplicates_window: DuplicatesWindow,
+
+    /// Full-text search results for the current filter.
+    search_hits: Vec<SearchHit>,
+
+    tags: Vec<Tag>,
+
+    company_tags: CompanyTags,
+
+    /// Only the companies with every one of these tags are shown.
+    tag_filter: HashSet<i64>,
+
+    tags_window: TagsWindow,
 }
 
 impl Default for State {
@@ -100,42 +304,157 @@ impl Default for State {
             need_to_fetch: true,
             selected_rows: Default::default(),
             need_to_calculate_total: true,
+            need_to_calculate_aging: true,
+            settings: Default::default(),
+            messages: Default::default(),
+            tab: Tab::Table,
+            scroll_to_row: None,
+            filter: Default::default(),
+            details: None,
+            groups: Default::default(),
+            collapsed_groups: Default::default(),
+            group_editor: Default::default(),
+            accounts: Default::default(),
+            account_editor: Default::default(),
+            periods: Default::default(),
+            period_totals: Default::default(),
+            balance_history: Default::default(),
+            aging: Default::default(),
+            reopen_dialog: Default::default(),
+            period_range_dialog: Default::default(),
+            dataset_dialog: Default::default(),
+            organization_open: false,
+            reconciliation_dialog: Default::default(),
+            rates: Default::default(),
+            rates_editor: Default::default(),
+            revaluation_open: false,
+            over_limit_only: false,
+            rules: Default::default(),
+            rules_editor: Default::default(),
+            bulk_editor: Default::default(),
+            duplicates_window: Default::default(),
+            search_hits: Default::default(),
+            tags: Default::default(),
+            company_tags: Default::default(),
+            tag_filter: Default::default(),
+            tags_window: Default::default(),
+        }
+    }
+}
+
+impl State {
+    /// Selected counterparties in the order of the table.
+    fn selected_companies(&self) -> Vec<&Company> {
+        self.rows
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| self.selected_rows.contains(index))
+            .filter_map(|(_, row)| match row {
+                Row::Constant(company) => Some(company),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn period(&self) -> Option<&Period> {
+        match self.settings.period_id {
+            Some(id) => self.periods.iter().find(|period| period.id == id),
+            None => self.periods.last(),
         }
     }
+
+    fn companies(&self) -> Vec<&Company> {
+        self.rows
+            .iter()
+            .filter_map(|row| match row {
+                Row::Constant(company) => Some(company),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Rates of the selected period for the currencies of the shown companies.
+    fn currencies(&self) -> Currencies {
+        Currencies::new(
+            &self.settings.reporting_currency,
+            &self.rates,
+            self.period(),
+            &self.companies(),
+        )
+    }
+
+    /// The sheet rows are written to, there is none for the consolidated view and closed periods.
+    fn sheet(&self) -> Option<Sheet> {
+        let AccountView::Account(account_id) = self.settings.account else {
+            return None;
+        };
+
+        self.period()
+            .filter(|period| !period.closed)
+            .map(|period| Sheet {
+                account_id,
+                period_id: period.id,
+            })
+    }
 }
 
 impl MyApp {
     pub fn new(cc: &eframe::CreationContext<'_>, db: SqlitePool) -> Self {
         let (tx, rx) = mpsc::channel();
-        // if let Some(storage) = cc.storage {
-        //     let state = eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default();
-        //     return Self { tx, rx, db, state };
-        // }
+        let mut state = State::default();
 
-        Self {
-            tx,
-            rx,
-            db,
-            state: State::default(),
+        if let Some(storage) = cc.storage {
+            state.settings = eframe::get_value(storage, eframe::APP_KEY).unwrap_or_default();
         }
+
+        Self { tx, rx, db, state }
     }
 }
 
 impl eframe::App for MyApp {
-    // fn save(&mut self, storage: &mut dyn eframe::Storage) {
-    //     eframe::set_value(storage, eframe::APP_KEY, &self.state);
-    // }
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, eframe::APP_KEY, &self.state.settings);
+    }
 
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         if self.state.need_to_fetch {
-            fetch_all(self.db.clone(), self.tx.clone());
+            fetch_all(
+                self.db.clone(),
+                self.state.settings.account,
+                self.state.settings.period_id,
+                self.tx.clone(),
+            );
+            fetch_groups(self.db.clone(), self.tx.clone());
+            fetch_accounts(self.db.clone(), self.tx.clone());
+            fetch_rates(self.db.clone(), self.tx.clone());
+            fetch_rules(self.db.clone(), self.tx.clone());
+            fetch_tags(self.db.clone(), self.tx.clone());
+            fetch_periods(
+                self.db.clone(),
+                self.state.settings.account,
+                self.state.settings.reporting_currency.clone(),
+                self.tx.clone(),
+            );
             self.state.need_to_fetch = false;
         }
 
         if self.state.need_to_calculate_total {
-            calculate_total(&self.state.rows, self.tx.clone());
+            calculate_total(&self.state.rows, self.state.currencies(), self.tx.clone());
             self.state.need_to_calculate_total = false;
         }
+
+        if self.state.need_to_calculate_aging {
+            self.state.aging = aging_report(&self.state);
+            self.state.need_to_calculate_aging = false;
+        }
+
+        if let Some(form) = &mut self.state.details {
+            if form.need_attachments {
+                fetch_attachments(self.db.clone(), form.id, self.tx.clone());
+                form.need_attachments = false;
+            }
+        }
+
         if let Ok(op) = self.rx.try_recv() {
             match op {
                 Operation::Add { new_companies } => {
@@ -146,17 +465,20 @@ impl eframe::App for MyApp {
 
                     self.state.rows.extend(mapped_into_constant);
                     self.state.need_to_calculate_total = true;
+                    self.state.need_to_calculate_aging = true;
                 }
                 Operation::FetchAll { all_companies } => {
                     if let Ok(companies) = all_companies {
                         self.state.rows = companies.into_iter().map(Row::Constant).collect();
                     }
                     self.state.need_to_calculate_total = true;
+                    self.state.need_to_calculate_aging = true;
                 }
                 Operation::Delete { deleted_companies } => {
                     remove_deleted(&mut self.state.rows, &deleted_companies);
                     self.state.selected_rows.clear();
                     self.state.need_to_calculate_total = true;
+                    self.state.need_to_calculate_aging = true;
                 }
                 Operation::Edit => {
                     self.state.need_to_fetch = true;
@@ -165,50 +487,580 @@ impl eframe::App for MyApp {
                     remove_non_constant(&mut self.state.rows, true);
                     self.state.rows.push(Row::Total(total));
                 }
+                Operation::FetchGroups { all_groups } => {
+                    if let Ok(groups) = all_groups {
+                        self.state.groups = groups;
+                    }
+                }
+                Operation::GroupsChanged => {
+                    self.state.need_to_fetch = true;
+                }
+                Operation::FetchAccounts { all_accounts } => {
+                    if let Ok(accounts) = all_accounts {
+                        self.state.accounts = accounts;
+                    }
+
+                    // the account could have been deleted since it was picked
+                    if let AccountView::Account(id) = self.state.settings.account {
+                        if !self.state.accounts.iter().any(|account| account.id == id) {
+                            self.state.settings.account = self
+                                .state
+                                .accounts
+                                .first()
+                                .map_or(AccountView::Consolidated, |account| {
+                                    AccountView::Account(account.id)
+                                });
+                            self.state.need_to_fetch = true;
+                        }
+                    }
+                }
+                Operation::AccountsChanged => {
+                    self.state.need_to_fetch = true;
+                }
+                Operation::FetchPeriods { all_periods } => {
+                    if let Ok(periods) = all_periods {
+                        self.state.periods = periods;
+                    }
+                    self.state.need_to_calculate_aging = true;
+
+                    if let Some(id) = self.state.settings.period_id {
+                        if !self.state.periods.iter().any(|period| period.id == id) {
+                            self.state.settings.period_id = None;
+                            self.state.need_to_fetch = true;
+                        }
+                    }
+                }
+                Operation::PeriodsChanged => {
+                    self.state.need_to_fetch = true;
+                }
+                Operation::PeriodTotals { totals } => {
+                    if let Ok(totals) = totals {
+                        self.state.period_totals = totals;
+                    }
+                }
+                Operation::BalanceHistory { history } => {
+                    if let Ok(history) = history {
+                        self.state.balance_history = history;
+                    }
+                    self.state.need_to_calculate_aging = true;
+                }
+                Operation::FetchRates { all_rates } => {
+                    if let Ok(rates) = all_rates {
+                        self.state.rates = rates;
+                    }
+                    self.state.need_to_calculate_total = true;
+                    self.state.need_to_calculate_aging = true;
+                }
+                Operation::RatesChanged => {
+                    self.state.need_to_fetch = true;
+                }
+                Operation::FetchRules { all_rules } => {
+                    if let Ok(rules) = all_rules {
+                        self.state.rules = rules;
+                    }
+                }
+                Operation::FetchTags {
+                    all_tags,
+                    company_tags: pairs,
+                } => {
+                    if let (Ok(tags), Ok(pairs)) = (all_tags, pairs) {
+                        let known: HashSet<_> = tags.iter().map(|tag| tag.id).collect();
+                        self.state.tag_filter.retain(|id| known.contains(id));
+                        self.state.tags = tags;
+                        self.state.company_tags = company_tags(pairs);
+                    }
+                }
+                Operation::RulesChanged => {
+                    self.state.need_to_fetch = true;
+                }
+                Operation::FetchAttachments {
+                    company_id,
+                    attachments,
+                } => {
+                    if let (Some(form), Ok(attachments)) = (&mut self.state.details, attachments) {
+                        if form.id == company_id {
+                            form.attachments = attachments;
+                        }
+                    }
+                }
+                Operation::AttachmentsChanged { company_id } => {
+                    if let Some(form) = &mut self.state.details {
+                        form.need_attachments |= form.id == company_id;
+                    }
+                }
+                Operation::Imported {
+                    file_name,
+                    imported,
+                } => {
+                    receive_import(
+                        self.db.clone(),
+                        &mut self.state,
+                        file_name,
+                        imported,
+                        self.tx.clone(),
+                    );
+                }
+                Operation::DatasetImported { file_name, counts } => {
+                    receive_dataset(&mut self.state, file_name, counts);
+                }
+                Operation::Search { query, hits } => {
+                    // an answer to an older query comes too late
+                    if query == self.state.filter {
+                        self.state.search_hits = hits.unwrap_or_default();
+                    }
+                }
+                Operation::Merged { merge } => {
+                    let language = self.state.settings.language;
+                    self.state.messages.push(language.trf(
+                        Message::CompaniesMerged,
+                        &[
+                            ("merged", &merge.merged_name),
+                            ("kept", &merge.kept_name),
+                            ("moved", &merge.balances_moved),
+                            ("combined", &merge.balances_combined),
+                        ],
+                    ));
+                    self.state.selected_rows.clear();
+                    self.state.need_to_fetch = true;
+                }
+                Operation::Failed { error } => {
+                    let language = self.state.settings.language;
+                    self.state
+                        .messages
+                        .push(language.trf(Message::SaveFailed, &[("error", &error)]));
+                    self.state.need_to_fetch = true;
+                }
             }
         }
 
+        let language = self.state.settings.language;
+
+        handle_clipboard(ctx, &mut self.state);
+        handle_dropped_files(ctx, &mut self.state, self.db.clone(), self.tx.clone());
+
         let top_panel = egui::TopBottomPanel::top("top_panel").show_separator_line(false);
 
         top_panel.show(ctx, |ui| {
             ui.horizontal(|ui| {
                 egui::menu::bar(ui, |ui| {
-                    ui.menu_button("File", |ui| {
-                        ui.menu_button("Export as..", |ui| {
-                            if ui.button("Excel").clicked() {
-                                save_to_excel(&mut self.state);
+                    ui.menu_button(language.tr(Message::File), |ui| {
+                        ui.menu_button(language.tr(Message::ExportAs), |ui| {
+                            if ui.button(language.tr(Message::Excel)).clicked() {
+                                save_to_excel(&mut self.state, self.tx.clone());
+                            }
+                            if ui.button(language.tr(Message::Ods)).clicked() {
+                                save_to_ods(&mut self.state, self.tx.clone());
+                            }
+                            if ui.button(language.tr(Message::Pdf)).clicked() {
+                                save_to_pdf(&mut self.state, self.tx.clone());
+                            }
+                            if ui.button(language.tr(Message::Html)).clicked() {
+                                save_to_html(&mut self.state, self.tx.clone());
+                            }
+                            if ui.button(language.tr(Message::Json)).clicked() {
+                                save_dataset(self.db.clone(), self.tx.clone());
+                            }
+                            if ui.button(language.tr(Message::PeriodsWorkbook)).clicked() {
+                                let state = &mut self.state;
+                                let current = state.period().cloned();
+                                state
+                                    .period_range_dialog
+                                    .open(&state.periods, current.as_ref());
+                                ui.close_menu();
                             }
                         });
 
-                        if ui.button("Exit").clicked() {
+                        if ui.button(language.tr(Message::ImportDataset)).clicked() {
+                            self.state.dataset_dialog.open = true;
+                            ui.close_menu();
+                        }
+
+                        if ui.button(language.tr(Message::Groups)).clicked() {
+                            self.state.group_editor.open = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(language.tr(Message::Accounts)).clicked() {
+                            self.state.account_editor.open = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(language.tr(Message::OurOrganization)).clicked() {
+                            self.state.organization_open = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(language.tr(Message::HighlightRules)).clicked() {
+                            self.state.rules_editor.open = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(language.tr(Message::ExchangeRates)).clicked() {
+                            self.state.rates_editor.open = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(language.tr(Message::Revaluation)).clicked() {
+                            self.state.revaluation_open = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(language.tr(Message::TagSubtotals)).clicked() {
+                            self.state.tags_window.open = true;
+                            ui.close_menu();
+                        }
+                        if ui.button(language.tr(Message::Duplicates)).clicked() {
+                            self.state.duplicates_window.open = true;
+                            ui.close_menu();
+                        }
+
+                        if ui.button(language.tr(Message::Exit)).clicked() {
                             ctx.send_viewport_cmd(egui::ViewportCommand::Close);
                         }
                     });
-                    ui.menu_button("View", |ui| {
-                        global_dark_light_mode_buttons(ui);
+                    ui.menu_button(language.tr(Message::View), |ui| {
+                        theme_buttons(ui, language);
+                        ui.menu_button(language.tr(Message::Language), |ui| {
+                            for option in Language::iter() {
+                                ui.radio_value(
+                                    &mut self.state.settings.language,
+                                    option,
+                                    option.native_name(),
+                                );
+                            }
+                        });
+                        ui.checkbox(
+                            &mut self.state.settings.show_groups,
+                            language.tr(Message::ShowGroups),
+                        );
+                        ui.menu_button(language.tr(Message::NumberFormat), |ui| {
+                            number_format_menu(
+                                ui,
+                                &mut self.state.settings.number_format,
+                                language,
+                            );
+                        });
                     });
                 });
             });
             ui.vertical(|ui| {
                 ui.horizontal(|ui| {
                     ui.columns(2, |columns| {
-                        columns[0].vertical(|ui| ui.heading("Application"));
+                        columns[0].vertical(|ui| ui.heading(language.tr(Message::Application)));
                         columns[1].vertical_centered_justified(|ui| {
                             ui.with_layout(egui::Layout::right_to_left(egui::Align::Max), |ui| {
-                                ui.heading(format!("{} mode", &self.state.mode))
+                                ui.heading(language.tr(self.state.mode.title()))
                             });
                         });
                     });
                 });
+                ui.horizontal(|ui| {
+                    ui.selectable_value(
+                        &mut self.state.tab,
+                        Tab::Table,
+                        language.tr(Message::TableTab),
+                    );
+                    ui.selectable_value(
+                        &mut self.state.tab,
+                        Tab::Dashboard,
+                        language.tr(Message::DashboardTab),
+                    );
+                    ui.selectable_value(
+                        &mut self.state.tab,
+                        Tab::Aging,
+                        language.tr(Message::AgingTab),
+                    );
+                    ui.separator();
+
+                    // rows being added or edited belong to the account they were started on
+                    let account = self.state.settings.account;
+                    ui.add_enabled_ui(matches!(self.state.mode, Mode::Normal), |ui| {
+                        account_selector(
+                            ui,
+                            &mut self.state.settings.account,
+                            &self.state.accounts,
+                            language,
+                        );
+                    });
+                    ui.separator();
+
+                    let period_id = self.state.settings.period_id;
+                    ui.add_enabled_ui(matches!(self.state.mode, Mode::Normal), |ui| {
+                        period_selector(
+                            ui,
+                            &mut self.state.settings.period_id,
+                            &self.state.periods,
+                            language,
+                        );
+                        period_buttons(ui, self.db.clone(), &mut self.state, self.tx.clone());
+                    });
+
+                    if self.state.settings.account != account
+                        || self.state.settings.period_id != period_id
+                    {
+                        self.state.selected_rows.clear();
+                        self.state.need_to_fetch = true;
+                    }
+                });
             });
         });
 
+        if !self.state.messages.is_empty() {
+            egui::TopBottomPanel::bottom("messages_panel").show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.vertical(|ui| {
+                        for message in &self.state.messages {
+                            ui.label(message);
+                        }
+                    });
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Min), |ui| {
+                        if ui.button(language.tr(Message::Dismiss)).clicked() {
+                            self.state.messages.clear();
+                        }
+                    });
+                });
+            });
+        }
+
+        if let Some(action) = self
+            .state
+            .group_editor
+            .editor_ui(ctx, &self.state.groups, language)
+        {
+            save_group(self.db.clone(), action, language, self.tx.clone());
+        }
+
+        organization_window(
+            ctx,
+            &mut self.state.organization_open,
+            &mut self.state.settings.organization,
+            language,
+        );
+
+        if let Some(request) =
+            self.state
+                .reconciliation_dialog
+                .dialog_ui(ctx, &self.state.periods, language)
+        {
+            save_reconciliation(self.db.clone(), &self.state, request, self.tx.clone());
+        }
+
+        if let Some(action) = self.state.rules_editor.editor_ui(
+            ctx,
+            &self.state.rules,
+            &self.state.settings.number_format,
+            language,
+        ) {
+            save_rule(self.db.clone(), action, self.tx.clone());
+        }
+
+        if self.state.bulk_editor.open {
+            let selected: Vec<Company> = self
+                .state
+                .selected_companies()
+                .into_iter()
+                .cloned()
+                .collect();
+            let selected: Vec<&Company> = selected.iter().collect();
+            if let Some(change) = self.state.bulk_editor.editor_ui(
+                ctx,
+                &selected,
+                &self.state.groups,
+                &self.state.settings.number_format,
+                language,
+            ) {
+                save_bulk_edit(self.db.clone(), &self.state, change, self.tx.clone());
+            }
+        }
+
+        if self.state.tags_window.open {
+            let currencies = self.state.currencies();
+            let converted: Vec<_> = self
+                .state
+                .companies()
+                .into_iter()
+                .map(|company| currencies.convert(company))
+                .collect();
+            let totals = tag_totals(
+                &converted,
+                &self.state.tags,
+                &self.state.company_tags,
+                language,
+            );
+            if let Some(id) = self.state.tags_window.window_ui(
+                ctx,
+                &totals,
+                &self.state.settings.number_format,
+                language,
+            ) {
+                remove_tag(self.db.clone(), id, self.tx.clone());
+            }
+        }
+
+        if self.state.duplicates_window.open {
+            let pairs = find_duplicates(&self.state.companies());
+            if let Some(request) = self
+                .state
+                .duplicates_window
+                .window_ui(ctx, &pairs, language)
+            {
+                merge_duplicate(self.db.clone(), request, language, self.tx.clone());
+            }
+        }
+
+        let reporting_currency = self.state.settings.reporting_currency.clone();
+        if let Some(action) = self.state.rates_editor.editor_ui(
+            ctx,
+            &self.state.rates,
+            &mut self.state.settings.reporting_currency,
+            language,
+        ) {
+            save_rate(self.db.clone(), action, language, self.tx.clone());
+        }
+        if self.state.settings.reporting_currency != reporting_currency {
+            self.state.need_to_calculate_total = true;
+            self.state.need_to_calculate_aging = true;
+            // the totals of the periods are converted in the database
+            self.state.need_to_fetch = true;
+        }
+
+        if self.state.revaluation_open {
+            let revaluation = match self.state.period() {
+                Some(period) => revaluate(
+                    &self.state.companies(),
+                    &self.state.rates,
+                    period,
+                    &self.state.settings.reporting_currency,
+                ),
+                None => Vec::new(),
+            };
+            let period = self.state.period().cloned();
+            revaluation_window(
+                ctx,
+                &mut self.state.revaluation_open,
+                period.as_ref(),
+                &revaluation,
+                &self.state.settings.number_format,
+                language,
+            );
+        }
+
+        if let Some(periods) =
+            self.state
+                .period_range_dialog
+                .dialog_ui(ctx, &self.state.periods, language)
+        {
+            save_periods_to_excel(self.db.clone(), &self.state, periods, self.tx.clone());
+        }
+
+        if let Some(mode) = self.state.dataset_dialog.dialog_ui(ctx, language) {
+            import_dataset_file(self.db.clone(), mode, self.tx.clone());
+        }
+
+        if let Some(id) = self.state.reopen_dialog.dialog_ui(ctx, language) {
+            close_period(self.db.clone(), id, false, self.tx.clone());
+        }
+
+        if let Some(action) =
+            self.state
+                .account_editor
+                .editor_ui(ctx, &self.state.accounts, language)
+        {
+            save_account(self.db.clone(), action, self.tx.clone());
+        }
+
+        if let Some(form) = &mut self.state.details {
+            let action = egui::SidePanel::right("company_details")
+                .resizable(true)
+                .default_width(320.0)
+                .show(ctx, |ui| {
+                    egui::ScrollArea::vertical()
+                        .show(ui, |ui| {
+                            form.details_ui(ui, &self.state.groups, &self.state.tags, language)
+                        })
+                        .inner
+                })
+                .inner;
+
+            match action {
+                Some(DetailsAction::Save) => {
+                    save_details(self.db.clone(), &mut self.state, self.tx.clone());
+                }
+                Some(DetailsAction::Close) => self.state.details = None,
+                Some(DetailsAction::Attachment(action)) => {
+                    save_attachment(self.db.clone(), form, action, self.tx.clone());
+                }
+                None => (),
+            }
+        }
+
+        let breaches: Vec<_> = self
+            .state
+            .companies()
+            .into_iter()
+            .filter_map(Company::breach)
+            .collect();
+        if self.state.tab == Tab::Table && !breaches.is_empty() {
+            let clicked = egui::TopBottomPanel::bottom("alerts_panel")
+                .show(ctx, |ui| {
+                    alerts_ui(ui, &breaches, &self.state.settings.number_format, language)
+                })
+                .inner;
+
+            if let Some(id) = clicked {
+                jump_to_company(&mut self.state, id);
+            }
+        }
+
         egui::CentralPanel::default().show(ctx, |ui| {
+            if self.state.tab == Tab::Dashboard {
+                let history: Vec<_> = self
+                    .state
+                    .period_totals
+                    .iter()
+                    .filter_map(|(id, total)| {
+                        let period = self.state.periods.iter().find(|period| period.id == *id)?;
+                        Some((period.label(), total.clone()))
+                    })
+                    .collect();
+
+                let currencies = self.state.currencies();
+                let converted: Vec<_> = self
+                    .state
+                    .companies()
+                    .into_iter()
+                    .map(|company| currencies.convert(company))
+                    .collect();
+                let dashboard = Dashboard::new(
+                    converted.iter().collect(),
+                    &self.state.settings.number_format,
+                    language,
+                )
+                .history(&history);
+
+                if let Some(id) = dashboard.dashboard_ui(ui) {
+                    jump_to_company(&mut self.state, id);
+                }
+                return;
+            }
+
+            if self.state.tab == Tab::Aging {
+                match aging_ui(
+                    ui,
+                    &self.state.aging,
+                    self.state.period(),
+                    &self.state.settings.number_format,
+                    language,
+                ) {
+                    Some(AgingAction::Open(id)) => jump_to_company(&mut self.state, id),
+                    Some(AgingAction::Export) => save_aging_to_excel(&self.state, self.tx.clone()),
+                    None => (),
+                }
+                return;
+            }
+
+            let editable = self.state.sheet().is_some();
+            let currencies = self.state.currencies();
+
             ui.vertical(|ui| {
                 ui.horizontal(|ui| {
                     let add_button = ui.add_enabled(
-                        matches!(self.state.mode, Mode::Normal),
-                        egui::Button::new("Add row"),
+                        editable && matches!(self.state.mode, Mode::Normal),
+                        egui::Button::new(language.tr(Message::AddRow)),
                     );
 
                     if add_button.clicked() {
@@ -216,9 +1068,10 @@ impl eframe::App for MyApp {
                     }
 
                     let edit_button = ui.add_enabled(
-                        !self.state.selected_rows.is_empty()
+                        editable
+                            && !self.state.selected_rows.is_empty()
                             && matches!(self.state.mode, Mode::Normal),
-                        egui::Button::new("Edit"),
+                        egui::Button::new(language.tr(Message::Edit)),
                     );
 
                     if edit_button.clicked() {
@@ -226,14 +1079,106 @@ impl eframe::App for MyApp {
                     }
 
                     let delete_button = ui.add_enabled(
-                        !self.state.selected_rows.is_empty()
+                        editable
+                            && !self.state.selected_rows.is_empty()
                             && matches!(self.state.mode, Mode::Normal),
-                        egui::Button::new("Delete"),
+                        egui::Button::new(language.tr(Message::Delete)),
                     );
 
                     if delete_button.clicked() {
                         delete_selected(self.db.clone(), &mut self.state, self.tx.clone());
                     }
+
+                    let bulk_button = ui.add_enabled(
+                        editable
+                            && !self.state.selected_rows.is_empty()
+                            && matches!(self.state.mode, Mode::Normal),
+                        egui::Button::new(language.tr(Message::BulkEdit)),
+                    );
+
+                    if bulk_button.clicked() {
+                        self.state.bulk_editor.open = true;
+                    }
+
+                    let selected_company =
+                        match self.state.selected_rows.iter().collect::<Vec<_>>()[..] {
+                            [&index] => match &self.state.rows[index] {
+                                Row::Constant(company) => Some(company),
+                                _ => None,
+                            },
+                            _ => None,
+                        };
+                    let reconciliation_button = ui.add_enabled(
+                        selected_company.is_some() && matches!(self.state.mode, Mode::Normal),
+                        egui::Button::new(language.tr(Message::ReconciliationAct)),
+                    );
+
+                    if let (true, Some(company)) =
+                        (reconciliation_button.clicked(), selected_company.cloned())
+                    {
+                        let period = self.state.period().cloned();
+                        self.state
+                            .reconciliation_dialog
+                            .open(&company, period.as_ref());
+                    }
+
+                    if self.state.settings.account == AccountView::Consolidated {
+                        ui.weak(language.tr(Message::ConsolidatedReadOnly));
+                    } else if !editable {
+                        ui.weak(language.tr(Message::PeriodClosedReadOnly));
+                    }
+
+                    let missing = currencies.missing();
+                    if !missing.is_empty() {
+                        ui.colored_label(
+                            ui.visuals().warn_fg_color,
+                            language.trf(
+                                Message::MissingRates,
+                                &[("currencies", &missing.join(", "))],
+                            ),
+                        );
+                    }
+
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        let filter = ui.add(
+                            egui::TextEdit::singleline(&mut self.state.filter)
+                                .hint_text(language.tr(Message::Filter)),
+                        );
+                        if filter.changed() {
+                            search(self.db.clone(), &self.state.filter, self.tx.clone());
+                        }
+
+                        // only what can be jumped to in the table
+                        let hits: Vec<_> = self
+                            .state
+                            .search_hits
+                            .iter()
+                            .filter(|hit| {
+                                self.state.rows.iter().any(|row| {
+                                    matches!(row, Row::Constant(company) if company.id == hit.id)
+                                })
+                            })
+                            .take(10)
+                            .cloned()
+                            .collect();
+                        if let Some(id) = search_popup(ui, &filter, &hits) {
+                            self.state.filter.clear();
+                            self.state.search_hits.clear();
+                            jump_to_company(&mut self.state, id);
+                        }
+                        ui.checkbox(
+                            &mut self.state.over_limit_only,
+                            language.tr(Message::OverLimitOnly),
+                        );
+                        if !self.state.tags.is_empty() {
+                            tag_filter_menu(
+                                ui,
+                                &self.state.tags,
+                                &mut self.state.tag_filter,
+                                language,
+                            );
+                        }
+                    });
                 });
                 use egui_extras::{Size, StripBuilder};
                 StripBuilder::new(ui)
@@ -243,36 +1188,54 @@ impl eframe::App for MyApp {
                             let mut table = CompanyTable::new(
                                 &mut self.state.rows,
                                 &mut self.state.selected_rows,
+                                &self.state.settings.number_format,
+                                language,
+                            )
+                            .scroll_to_row(self.state.scroll_to_row.take())
+                            .filter(&self.state.filter, &self.state.search_hits)
+                            .currencies(&currencies)
+                            .over_limit_only(self.state.over_limit_only)
+                            .rules(&self.state.rules)
+                            .tags(
+                                &self.state.tags,
+                                &self.state.company_tags,
+                                &self.state.tag_filter,
                             );
-                            egui::ScrollArea::horizontal().show(ui, |ui| {
-                                table.table_ui(ui);
-                            });
+                            if self.state.settings.show_groups {
+                                table = table
+                                    .groups(&self.state.groups, &mut self.state.collapsed_groups);
+                            }
+                            let double_clicked = egui::ScrollArea::horizontal()
+                                .show(ui, |ui| table.table_ui(ui))
+                                .inner;
+
+                            if let Some(id) = double_clicked {
+                                open_details(&mut self.state, id);
+                            }
                             ui.horizontal(|ui| {
                                 if !matches!(self.state.mode, Mode::Normal)
-                                    && ui.button("Save").clicked()
+                                    && ui.button(language.tr(Message::Save)).clicked()
                                 {
-                                    match &self.state.mode {
-                                        Mode::Add => {
-                                            save_new_rows(
-                                                self.db.clone(),
-                                                &mut self.state,
-                                                self.tx.clone(),
-                                            );
-                                        }
-                                        Mode::Edit => {
-                                            save_edited_rows(
-                                                self.db.clone(),
-                                                &mut self.state,
-                                                self.tx.clone(),
-                                            );
-                                        }
-                                        _ => (),
+                                    let saved = match &self.state.mode {
+                                        Mode::Add => save_new_rows(
+                                            self.db.clone(),
+                                            &mut self.state,
+                                            self.tx.clone(),
+                                        ),
+                                        Mode::Edit => save_edited_rows(
+                                            self.db.clone(),
+                                            &mut self.state,
+                                            self.tx.clone(),
+                                        ),
+                                        _ => true,
+                                    };
+                                    if saved {
+                                        self.state.mode = Mode::Normal;
                                     }
-                                    self.state.mode = Mode::Normal;
                                 }
 
                                 if !matches!(self.state.mode, Mode::Normal)
-                                    && ui.button("Cancel").clicked()
+                                    && ui.button(language.tr(Message::Cancel)).clicked()
                                 {
                                     match self.state.mode {
                                         Mode::Add => {
@@ -293,15 +1256,356 @@ impl eframe::App for MyApp {
     }
 }
 
-fn add_row(state: &mut State) {
-    state.mode = Mode::Add;
-    state
+fn jump_to_company(state: &mut State, id: i64) {
+    let index = state
         .rows
-        .insert(state.rows.len() - 1, Row::New(NewCompanyRow::default()))
+        .iter()
+        .position(|row| matches!(row, Row::Constant(company) if company.id == id));
+
+    if let Some(index) = index {
+        state.tab = Tab::Table;
+        state.selected_rows = std::collections::HashSet::from([index]);
+        state.scroll_to_row = Some(index);
+    }
 }
 
-fn edit_selected_rows(state: &mut State) {
-    state.mode = Mode::Edit;
+fn open_details(state: &mut State, id: i64) {
+    state.details = state.rows.iter().find_map(|row| match row {
+        Row::Constant(company) if company.id == id => {
+            let tags = tags_of(&state.tags, &state.company_tags, id)
+                .into_iter()
+                .map(|tag| tag.name.clone())
+                .collect();
+            Some(DetailsForm::new(
+                company,
+                tags,
+                &state.settings.number_format,
+            ))
+        }
+        _ => None,
+    });
+}
+
+fn save_details(db: SqlitePool, state: &mut State, tx: Sender<Operation>) {
+    let Some(form) = state.details.take() else {
+        return;
+    };
+    let (id, details, tags) = (form.id, form.details(), form.tags);
+
+    tokio::spawn(async move {
+        let result = match edit_company_details(db.clone(), id, details).await {
+            Ok(()) => set_company_tags(db, id, tags).await,
+            error => error,
+        };
+        if let Err(error) = result {
+            _ = tx.send(Operation::Failed {
+                error: error.to_string(),
+            });
+        }
+
+        tx.send(Operation::Edit)
+    });
+}
+
+fn fetch_attachments(db: SqlitePool, company_id: i64, tx: Sender<Operation>) {
+    tokio::spawn(async move {
+        let attachments = get_attachments(db, company_id).await;
+        tx.send(Operation::FetchAttachments {
+            company_id,
+            attachments,
+        })
+    });
+}
+
+fn save_attachment(
+    db: SqlitePool,
+    form: &DetailsForm,
+    action: AttachmentAction,
+    tx: Sender<Operation>,
+) {
+    let company_id = form.id;
+    let open_task = match action {
+        AttachmentAction::Attach => Some(rfd::AsyncFileDialog::new().pick_files()),
+        _ => None,
+    };
+    let save_task = match action {
+        AttachmentAction::Save(id) => form
+            .attachments
+            .iter()
+            .find(|attachment| attachment.id == id)
+            .map(|attachment| {
+                rfd::AsyncFileDialog::new()
+                    .set_file_name(&attachment.file_name)
+                    .save_file()
+            }),
+        _ => None,
+    };
+
+    tokio::spawn(async move {
+        let result = match action {
+            AttachmentAction::Attach => {
+                let Some(picked) = open_task.unwrap().await else {
+                    return Ok(());
+                };
+                let mut files = Vec::new();
+                for file in picked {
+                    files.push((file.file_name(), file.read().await));
+                }
+                add_attachments(db, company_id, files)
+                    .await
+                    .map_err(|e| e.to_string())
+            }
+            AttachmentAction::Save(id) => {
+                let Some(file) = (match save_task {
+                    Some(task) => task.await,
+                    None => None,
+                }) else {
+                    return Ok(());
+                };
+                match get_attachment_data(db, id).await {
+                    Ok(data) => file.write(&data).await.map_err(|e| e.to_string()),
+                    Err(error) => Err(error.to_string()),
+                }
+            }
+            AttachmentAction::Delete(id) => {
+                delete_attachment(db, id).await.map_err(|e| e.to_string())
+            }
+        };
+
+        if let Err(error) = result {
+            _ = tx.send(Operation::Failed { error });
+        }
+        tx.send(Operation::AttachmentsChanged { company_id })
+    });
+}
+
+/// Files dropped onto the window are attached to the company open on the attachments tab,
+/// anywhere else they are imported.
+fn handle_dropped_files(
+    ctx: &egui::Context,
+    state: &mut State,
+    db: SqlitePool,
+    tx: Sender<Operation>,
+) {
+    let language = state.settings.language;
+    let target = state
+        .details
+        .as_ref()
+        .filter(|form| form.tab == DetailsTab::Attachments);
+
+    if ctx.input(|i| !i.raw.hovered_files.is_empty()) {
+        let text = match target {
+            Some(form) => language.trf(Message::DropFilesHere, &[("name", &form.name)]),
+            None => language.tr(Message::DropToImport).to_string(),
+        };
+        let painter = ctx.layer_painter(egui::LayerId::new(
+            egui::Order::Foreground,
+            egui::Id::new("drop_overlay"),
+        ));
+        let screen = ctx.screen_rect();
+        painter.rect_filled(screen, 0.0, egui::Color32::from_black_alpha(160));
+        painter.text(
+            screen.center(),
+            egui::Align2::CENTER_CENTER,
+            text,
+            egui::FontId::proportional(20.0),
+            egui::Color32::WHITE,
+        );
+    }
+
+    let dropped = ctx.input(|i| i.raw.dropped_files.clone());
+    if dropped.is_empty() {
+        return;
+    }
+    let Some(form) = target else {
+        import_files(dropped, &state.settings.number_format, tx);
+        return;
+    };
+    let company_id = form.id;
+
+    tokio::spawn(async move {
+        let mut files = Vec::new();
+        for (file_name, contents) in dropped.into_iter().filter_map(read_dropped_file) {
+            match contents {
+                Ok(contents) => files.push((file_name, contents)),
+                Err(error) => {
+                    _ = tx.send(Operation::Failed {
+                        error: format!("{file_name}: {error}"),
+                    });
+                }
+            }
+        }
+
+        if let Err(error) = add_attachments(db, company_id, files).await {
+            _ = tx.send(Operation::Failed {
+                error: error.to_string(),
+            });
+        }
+        tx.send(Operation::AttachmentsChanged { company_id })
+    });
+}
+
+fn add_row(state: &mut State) {
+    state.mode = Mode::Add;
+    state
+        .rows
+        .insert(state.rows.len() - 1, Row::New(NewCompanyRow::default()))
+}
+
+fn handle_clipboard(ctx: &egui::Context, state: &mut State) {
+    // text fields handle copy and paste on their own
+    let text_field_focused = ctx.memory(|memory| memory.focused().is_some());
+
+    let copy_requested = ctx.input(|i| i.events.iter().any(|e| matches!(e, egui::Event::Copy)));
+
+    if copy_requested && !text_field_focused && !state.selected_rows.is_empty() {
+        let selected = state
+            .rows
+            .iter()
+            .enumerate()
+            .filter(|(i, row)| state.selected_rows.contains(i) && matches!(row, Row::Constant(_)))
+            .map(|(_, row)| row.constant());
+
+        let text = companies_to_tsv(selected, &state.settings.number_format);
+        ctx.output_mut(|output| output.copied_text = text);
+    }
+
+    if !matches!(state.mode, Mode::Add) {
+        return;
+    }
+
+    // a block of cells is ours even when a field is focused, a single value goes to the field
+    let pasted = ctx.input_mut(|i| {
+        let position = i.events.iter().position(|e| match e {
+            egui::Event::Paste(text) => !text_field_focused || text.contains(['\t', '\n']),
+            _ => false,
+        });
+
+        match position.map(|position| i.events.remove(position)) {
+            Some(egui::Event::Paste(text)) => Some(text),
+            _ => None,
+        }
+    });
+
+    let Some(text) = pasted else {
+        return;
+    };
+
+    let language = state.settings.language;
+    let (new_rows, errors) = parse_tsv(&text, &state.settings.number_format);
+
+    state
+        .messages
+        .push(language.trf(Message::PastedRows, &[("count", &new_rows.len())]));
+    state
+        .messages
+        .extend(errors.iter().map(|error| error.describe(language)));
+
+    insert_new_rows(state, new_rows);
+}
+
+/// The name and contents of a dropped file. Native windows only give the path, the contents
+/// are read here.
+fn read_dropped_file(file: egui::DroppedFile) -> Option<(String, std::io::Result<Vec<u8>>)> {
+    let file_name = file
+        .path
+        .as_ref()
+        .and_then(|path| path.file_name())
+        .map_or(file.name, |name| name.to_string_lossy().into_owned());
+
+    match (file.bytes, file.path) {
+        (Some(bytes), _) => Some((file_name, Ok(bytes.to_vec()))),
+        (None, Some(path)) => Some((file_name, std::fs::read(path))),
+        (None, None) => None,
+    }
+}
+
+/// New rows go above the total.
+fn insert_new_rows(state: &mut State, new_rows: Vec<NewCompanyRow>) {
+    let position = state.rows.len() - 1;
+    state
+        .rows
+        .splice(position..position, new_rows.into_iter().map(Row::New));
+}
+
+/// Files are read one after another, each one's result arrives in the order they were dropped.
+fn import_files(
+    files: Vec<egui::DroppedFile>,
+    number_format: &NumberFormat,
+    tx: Sender<Operation>,
+) {
+    let number_format = number_format.clone();
+
+    tokio::spawn(async move {
+        for (file_name, contents) in files.into_iter().filter_map(read_dropped_file) {
+            let imported = contents
+                .map_err(|error| ImportError::Unreadable(error.to_string()))
+                .and_then(|contents| import_file(&file_name, contents, &number_format));
+            tx.send(Operation::Imported {
+                file_name,
+                imported,
+            })?;
+        }
+        Ok::<_, mpsc::SendError<Operation>>(())
+    });
+}
+
+/// Companies go to the table as new rows to look over and save like pasted ones, rates are
+/// saved right away and shown in the rates window.
+fn receive_import(
+    db: SqlitePool,
+    state: &mut State,
+    file_name: String,
+    imported: Result<Imported, ImportError>,
+    tx: Sender<Operation>,
+) {
+    let language = state.settings.language;
+
+    match imported {
+        Ok(Imported::Companies { .. }) if matches!(state.mode, Mode::Edit) => {
+            state
+                .messages
+                .push(language.trf(Message::ImportWhileEditing, &[("file", &file_name)]));
+        }
+        Ok(Imported::Companies { rows, errors }) => {
+            state.mode = Mode::Add;
+            state.messages.push(language.trf(
+                Message::ImportedRows,
+                &[("file", &file_name), ("count", &rows.len())],
+            ));
+            state
+                .messages
+                .extend(errors.iter().map(|error| error.describe(language)));
+            insert_new_rows(state, rows);
+        }
+        Ok(Imported::Rates(rates)) => {
+            state.messages.push(language.trf(
+                Message::ImportedRates,
+                &[("file", &file_name), ("count", &rates.len())],
+            ));
+            state.rates_editor.open = true;
+
+            tokio::spawn(async move {
+                if let Err(error) = save_rates(db, rates).await {
+                    _ = tx.send(Operation::Failed {
+                        error: error.to_string(),
+                    });
+                }
+                tx.send(Operation::RatesChanged)
+            });
+        }
+        Err(error) => {
+            state.messages.push(language.trf(
+                Message::ImportFailed,
+                &[("file", &file_name), ("error", &error.describe(language))],
+            ));
+        }
+    }
+}
+
+fn edit_selected_rows(state: &mut State) {
+    state.mode = Mode::Edit;
+    let number_format = &state.settings.number_format;
 
     let rows_to_be_edited: Vec<_> = state
         .rows
@@ -317,10 +1621,11 @@ fn edit_selected_rows(state: &mut State) {
             let mut remainder_begin_month_pos = String::new();
 
             if company.remainder_begin_month >= 0. {
-                remainder_begin_month_pos.push_str(&company.remainder_begin_month.to_string());
+                remainder_begin_month_pos
+                    .push_str(&number_format.format_exact(company.remainder_begin_month));
             } else {
-                let remainder = company.remainder_begin_month * -1.;
-                remainder_begin_month_neg.push_str(&remainder.to_string());
+                remainder_begin_month_neg
+                    .push_str(&number_format.format_exact(-company.remainder_begin_month));
             }
 
             *x = Row::BeingEdited(EditedCompanyRow {
@@ -328,8 +1633,8 @@ fn edit_selected_rows(state: &mut State) {
                 name: company.name.to_owned(),
                 remainder_begin_month_pos,
                 remainder_begin_month_neg,
-                debit_turnover: company.debit_turnover.to_string(),
-                credit_turnover: company.credit_turnover.to_string(),
+                debit_turnover: number_format.format_exact(company.debit_turnover),
+                credit_turnover: number_format.format_exact(company.credit_turnover),
             })
         }
     })
@@ -348,6 +1653,9 @@ fn remove_deleted(vec: &mut Vec<Row>, deleted_companies: &std::collections::Hash
 }
 
 fn delete_selected(db: SqlitePool, state: &mut State, tx: Sender<Operation>) {
+    let Some(sheet) = state.sheet() else {
+        return;
+    };
     let row_ids: Vec<_> = state
         .rows
         .iter()
@@ -359,7 +1667,12 @@ fn delete_selected(db: SqlitePool, state: &mut State, tx: Sender<Operation>) {
     tokio::spawn(async move {
         let mut ids_deleted = std::collections::HashSet::new();
         for id in row_ids {
-            delete_company(db.clone(), id).await.unwrap();
+            if let Err(error) = delete_company(db.clone(), sheet, id).await {
+                _ = tx.send(Operation::Failed {
+                    error: error.to_string(),
+                });
+                break;
+            }
 
             ids_deleted.insert(id);
         }
@@ -370,15 +1683,267 @@ fn delete_selected(db: SqlitePool, state: &mut State, tx: Sender<Operation>) {
     });
 }
 
-fn fetch_all(db: SqlitePool, tx: Sender<Operation>) {
+fn fetch_all(db: SqlitePool, view: AccountView, period_id: Option<i64>, tx: Sender<Operation>) {
     tokio::spawn(async move {
-        let all_companies = get_all_companies(db.clone()).await;
+        let all_companies = get_all_companies(db.clone(), view, period_id).await;
 
         tx.send(Operation::FetchAll { all_companies })
     });
 }
 
-fn save_edited_rows(db: SqlitePool, state: &mut State, tx: Sender<Operation>) {
+fn fetch_groups(db: SqlitePool, tx: Sender<Operation>) {
+    tokio::spawn(async move {
+        let all_groups = get_all_groups(db).await;
+
+        tx.send(Operation::FetchGroups { all_groups })
+    });
+}
+
+fn save_group(db: SqlitePool, action: GroupAction, language: Language, tx: Sender<Operation>) {
+    tokio::spawn(async move {
+        let result = match action {
+            GroupAction::Add { name, parent_id } => add_group(db, name, parent_id)
+                .await
+                .map_err(GroupError::from),
+            GroupAction::Edit(group) => edit_group(db, group).await,
+            GroupAction::Delete(id) => delete_group(db, id).await.map_err(GroupError::from),
+        };
+
+        if let Err(error) = result {
+            _ = tx.send(Operation::Failed {
+                error: error.describe(language),
+            });
+        }
+        tx.send(Operation::GroupsChanged)
+    });
+}
+
+fn fetch_accounts(db: SqlitePool, tx: Sender<Operation>) {
+    tokio::spawn(async move {
+        let all_accounts = get_all_accounts(db).await;
+
+        tx.send(Operation::FetchAccounts { all_accounts })
+    });
+}
+
+fn save_account(db: SqlitePool, action: AccountAction, tx: Sender<Operation>) {
+    tokio::spawn(async move {
+        let result = match action {
+            AccountAction::Add { code, name, kind } => add_account(db, code, name, kind).await,
+            AccountAction::Edit(account) => edit_account(db, account).await,
+            AccountAction::Delete(id) => delete_account(db, id).await,
+        };
+
+        if let Err(error) = result {
+            _ = tx.send(Operation::Failed {
+                error: error.to_string(),
+            });
+        }
+        tx.send(Operation::AccountsChanged)
+    });
+}
+
+fn fetch_rules(db: SqlitePool, tx: Sender<Operation>) {
+    tokio::spawn(async move {
+        let all_rules = get_all_rules(db).await;
+        tx.send(Operation::FetchRules { all_rules })
+    });
+}
+
+fn fetch_tags(db: SqlitePool, tx: Sender<Operation>) {
+    tokio::spawn(async move {
+        let all_tags = get_all_tags(db.clone()).await;
+        let company_tags = get_company_tags(db).await;
+        tx.send(Operation::FetchTags {
+            all_tags,
+            company_tags,
+        })
+    });
+}
+
+fn remove_tag(db: SqlitePool, id: i64, tx: Sender<Operation>) {
+    tokio::spawn(async move {
+        if let Err(error) = delete_tag(db, id).await {
+            _ = tx.send(Operation::Failed {
+                error: error.to_string(),
+            });
+        }
+        tx.send(Operation::Edit)
+    });
+}
+
+fn save_rule(db: SqlitePool, action: RuleAction, tx: Sender<Operation>) {
+    tokio::spawn(async move {
+        let result = match action {
+            RuleAction::Add(rule) => {
+                add_rule(db, rule.field, rule.comparison, rule.value, rule.color).await
+            }
+            RuleAction::Edit(rule) => edit_rule(db, rule).await,
+            RuleAction::Delete(id) => delete_rule(db, id).await,
+        };
+
+        if let Err(error) = result {
+            _ = tx.send(Operation::Failed {
+                error: error.to_string(),
+            });
+        }
+        tx.send(Operation::RulesChanged)
+    });
+}
+
+fn save_bulk_edit(db: SqlitePool, state: &State, change: BulkChange, tx: Sender<Operation>) {
+    let Some(sheet) = state.sheet() else {
+        return;
+    };
+    let ids: Vec<_> = state
+        .selected_companies()
+        .iter()
+        .map(|company| company.id)
+        .collect();
+
+    tokio::spawn(async move {
+        if let Err(error) = bulk_edit(db, sheet, ids, change).await {
+            _ = tx.send(Operation::Failed {
+                error: error.to_string(),
+            });
+        }
+        tx.send(Operation::Edit)
+    });
+}
+
+fn merge_duplicate(
+    db: SqlitePool,
+    request: MergeRequest,
+    language: Language,
+    tx: Sender<Operation>,
+) {
+    tokio::spawn(async move {
+        match merge_companies(db, request.keep_id, request.merged_id).await {
+            Ok(merge) => tx.send(Operation::Merged { merge }),
+            Err(error) => tx.send(Operation::Failed {
+                error: error.describe(language),
+            }),
+        }
+    });
+}
+
+fn search(db: SqlitePool, query: &str, tx: Sender<Operation>) {
+    let query = query.to_string();
+
+    tokio::spawn(async move {
+        let hits = search_companies(db, &query).await;
+        tx.send(Operation::Search { query, hits })
+    });
+}
+
+fn fetch_periods(
+    db: SqlitePool,
+    view: AccountView,
+    reporting_currency: String,
+    tx: Sender<Operation>,
+) {
+    tokio::spawn(async move {
+        let all_periods = get_all_periods(db.clone()).await;
+        _ = tx.send(Operation::FetchPeriods { all_periods });
+
+        let totals = get_period_totals(db.clone(), view, &reporting_currency).await;
+        _ = tx.send(Operation::PeriodTotals { totals });
+
+        let history = get_balance_history(db, view).await;
+        tx.send(Operation::BalanceHistory { history })
+    });
+}
+
+/// New period, close and reopen buttons next to the period selector.
+fn period_buttons(ui: &mut egui::Ui, db: SqlitePool, state: &mut State, tx: Sender<Operation>) {
+    let language = state.settings.language;
+
+    if ui.button(language.tr(Message::NewPeriod)).clicked() {
+        tokio::spawn(async move {
+            if let Err(error) = add_next_period(db).await {
+                _ = tx.send(Operation::Failed {
+                    error: error.to_string(),
+                });
+            }
+            tx.send(Operation::PeriodsChanged)
+        });
+        return;
+    }
+
+    let Some(period) = state.period().cloned() else {
+        return;
+    };
+
+    if period.closed {
+        if ui.button(language.tr(Message::ReopenPeriod)).clicked() {
+            state.reopen_dialog.open(period);
+        }
+    } else if ui.button(language.tr(Message::ClosePeriod)).clicked() {
+        close_period(db, period.id, true, tx);
+    }
+}
+
+fn fetch_rates(db: SqlitePool, tx: Sender<Operation>) {
+    tokio::spawn(async move {
+        let all_rates = get_all_rates(db).await;
+        tx.send(Operation::FetchRates { all_rates })
+    });
+}
+
+fn save_rate(db: SqlitePool, action: RatesAction, language: Language, tx: Sender<Operation>) {
+    let open_task = match action {
+        RatesAction::Import => Some(
+            rfd::AsyncFileDialog::new()
+                .add_filter("CSV", &["csv", "txt"])
+                .pick_file(),
+        ),
+        _ => None,
+    };
+
+    tokio::spawn(async move {
+        let result = match action {
+            RatesAction::Save(rate) => save_rates(db, vec![rate]).await.map_err(|e| e.to_string()),
+            RatesAction::Delete(rate) => delete_rate(db, rate.currency, rate.year, rate.month)
+                .await
+                .map_err(|e| e.to_string()),
+            RatesAction::Import => {
+                let Some(file) = open_task.unwrap().await else {
+                    return Ok(());
+                };
+                let text = String::from_utf8_lossy(&file.read().await).into_owned();
+                match parse_rates_csv(&text) {
+                    Ok(rates) => save_rates(db, rates).await.map_err(|e| e.to_string()),
+                    Err(line) => Err(language.trf(Message::RatesImportFailed, &[("line", &line)])),
+                }
+            }
+        };
+
+        if let Err(error) = result {
+            _ = tx.send(Operation::Failed { error });
+        }
+        tx.send(Operation::RatesChanged)
+    });
+}
+
+fn close_period(db: SqlitePool, id: i64, closed: bool, tx: Sender<Operation>) {
+    tokio::spawn(async move {
+        if let Err(error) = set_period_closed(db, id, closed).await {
+            _ = tx.send(Operation::Failed {
+                error: error.to_string(),
+            });
+        }
+        tx.send(Operation::PeriodsChanged)
+    });
+}
+
+/// Nothing is saved while any row is invalid, the errors go to the messages. Returns whether
+/// the rows were saved.
+fn save_edited_rows(db: SqlitePool, state: &mut State, tx: Sender<Operation>) -> bool {
+    let Some(sheet) = state.sheet() else {
+        return true;
+    };
+    let language = state.settings.language;
+    let mut errors = Vec::new();
     let edited_rows: Vec<_> = state
         .rows
         .iter()
@@ -386,19 +1951,46 @@ fn save_edited_rows(db: SqlitePool, state: &mut State, tx: Sender<Operation>) {
             Row::BeingEdited(row) => Some(row),
             _ => None,
         })
-        .flat_map(map_to_edited)
+        .filter_map(
+            |row| match map_to_edited(row, &state.settings.number_format, language) {
+                Ok(company) => Some(company),
+                Err(error) => {
+                    errors.push(language.trf(
+                        Message::InvalidRow,
+                        &[("name", &row.name), ("error", &error)],
+                    ));
+                    None
+                }
+            },
+        )
         .collect();
+    if !errors.is_empty() {
+        state.messages.extend(errors);
+        return false;
+    }
 
     tokio::spawn(async move {
         for row in edited_rows {
-            let _value = edit_company(db.clone(), row).await;
+            if let Err(error) = edit_company(db.clone(), sheet, row).await {
+                _ = tx.send(Operation::Failed {
+                    error: error.to_string(),
+                });
+                break;
+            }
         }
 
         tx.send(Operation::Edit)
     });
+    true
 }
 
-fn save_new_rows(db: SqlitePool, state: &mut State, tx: Sender<Operation>) {
+/// Like [`save_edited_rows`], nothing is saved while any row is invalid.
+fn save_new_rows(db: SqlitePool, state: &mut State, tx: Sender<Operation>) -> bool {
+    let Some(sheet) = state.sheet() else {
+        return true;
+    };
+    let language = state.settings.language;
+    let mut errors = Vec::new();
     let new_rows: Vec<_> = state
         .rows
         .iter()
@@ -406,47 +1998,526 @@ fn save_new_rows(db: SqlitePool, state: &mut State, tx: Sender<Operation>) {
             Row::New(row) => Some(row),
             _ => None,
         })
-        .flat_map(map_to_new)
+        .filter_map(
+            |row| match map_to_new(row, &state.settings.number_format, language) {
+                Ok(company) => Some(company),
+                Err(error) => {
+                    errors.push(language.trf(
+                        Message::InvalidRow,
+                        &[("name", &row.name), ("error", &error)],
+                    ));
+                    None
+                }
+            },
+        )
         .collect();
+    if !errors.is_empty() {
+        state.messages.extend(errors);
+        return false;
+    }
 
     tokio::spawn(async move {
         let mut vec = Vec::new();
 
         // TODO: needs to be rewritten to use JoinSet
         for row in new_rows {
-            let item = add_company(db.clone(), row).await;
-            vec.push(item.unwrap());
+            match add_company(db.clone(), sheet, row).await {
+                Ok(company) => vec.push(company),
+                Err(error) => {
+                    _ = tx.send(Operation::Failed {
+                        error: error.to_string(),
+                    });
+                    break;
+                }
+            }
         }
 
         tx.send(Operation::Add { new_companies: vec })
     });
+    true
+}
+
+/// Constant rows laid out the way they are exported, grouped when groups are shown in the table.
+/// Exported amounts are all in the reporting currency.
+fn export_sheet(state: &State) -> Vec<exports::SheetRow> {
+    let currencies = state.currencies();
+    let converted: Vec<_> = state
+        .companies()
+        .into_iter()
+        .map(|company| currencies.convert(company))
+        .collect();
+    let companies: Vec<_> = converted.iter().collect();
+    let groups: &[Group] = if state.settings.show_groups {
+        &state.groups
+    } else {
+        &[]
+    };
+
+    build_sheet(&companies, groups, &state.rules, state.settings.language)
+}
+
+/// The organization and the period the exported sheet is for.
+fn sheet_title(state: &State) -> SheetTitle {
+    let organization = &state.settings.organization.name;
+    let organization = if organization.trim().is_empty() {
+        state
+            .settings
+            .language
+            .tr(Message::OurOrganization)
+            .to_string()
+    } else {
+        organization.clone()
+    };
+
+    SheetTitle {
+        organization,
+        period: state.period().cloned(),
+    }
 }
 
-fn save_to_excel(state: &mut State) {
+fn save_to_excel(state: &mut State, tx: Sender<Operation>) {
     let dialog = rfd::AsyncFileDialog::new().set_file_name("company_list.xlsx");
     let save_task = dialog.save_file();
-    let mapped_to_excel: Vec<_> = state
-        .rows
-        .iter()
-        .flat_map(|r| match r {
-            Row::Constant(row) => Some(map_to_excel(row)),
-            _ => None,
+    let sheet = export_sheet(state);
+    let title = sheet_title(state);
+    let number_format = state.settings.number_format.clone();
+    let language = state.settings.language;
+    tokio::spawn(async move {
+        let Some(file) = save_task.await else {
+            return;
+        };
+
+        let result = async {
+            let excel = export_to_excel(&sheet, &title, &number_format, language)?;
+            file.write(&excel).await?;
+
+            Ok::<_, Box<dyn std::error::Error>>(())
+        };
+
+        if let Err(error) = result.await {
+            _ = tx.send(Operation::Failed {
+                error: error.to_string(),
+            });
+        }
+    });
+}
+
+/// A workbook with a sheet for every period, each converted at the rates of its month.
+fn save_periods_to_excel(
+    db: SqlitePool,
+    state: &State,
+    periods: Vec<Period>,
+    tx: Sender<Operation>,
+) {
+    let dialog = rfd::AsyncFileDialog::new().set_file_name("company_periods.xlsx");
+    let save_task = dialog.save_file();
+
+    let settings = &state.settings;
+    let cover = WorkbookCover {
+        organization: sheet_title(state).organization,
+        account: view_label(settings.account, &state.accounts, settings.language),
+        reporting_currency: settings.reporting_currency.clone(),
+    };
+    let groups = if settings.show_groups {
+        state.groups.clone()
+    } else {
+        Vec::new()
+    };
+    let rules = state.rules.clone();
+    let rates = state.rates.clone();
+    let view = settings.account;
+    let number_format = settings.number_format.clone();
+    let language = settings.language;
+
+    tokio::spawn(async move {
+        let Some(file) = save_task.await else {
+            return;
+        };
+
+        let result = async {
+            let mut sheets = Vec::new();
+            for period in periods {
+                let companies = get_all_companies(db.clone(), view, Some(period.id)).await?;
+                let companies: Vec<_> = companies.iter().collect();
+                let currencies =
+                    Currencies::new(&cover.reporting_currency, &rates, Some(&period), &companies);
+                let converted: Vec<_> = companies
+                    .into_iter()
+                    .map(|company| currencies.convert(company))
+                    .collect();
+                let converted: Vec<_> = converted.iter().collect();
+
+                let rows = build_sheet(&converted, &groups, &rules, language);
+                sheets.push(PeriodSheet { period, rows });
+            }
+
+            let bytes = periods_to_excel(&sheets, &cover, &number_format, language)?;
+            file.write(&bytes).await?;
+
+            Ok::<_, Box<dyn std::error::Error>>(())
+        };
+
+        if let Err(error) = result.await {
+            _ = tx.send(Operation::Failed {
+                error: error.to_string(),
+            });
+        }
+    });
+}
+
+/// The same sheet as `save_to_excel` for those on LibreOffice.
+fn save_to_ods(state: &mut State, tx: Sender<Operation>) {
+    let dialog = rfd::AsyncFileDialog::new().set_file_name("company_list.ods");
+    let save_task = dialog.save_file();
+    let sheet = export_sheet(state);
+    let title = sheet_title(state);
+    let number_format = state.settings.number_format.clone();
+    let language = state.settings.language;
+    tokio::spawn(async move {
+        let Some(file) = save_task.await else {
+            return;
+        };
+
+        let result = async {
+            let ods = export_to_ods(&sheet, &title, &number_format, language)?;
+            file.write(&ods).await?;
+
+            Ok::<_, Box<dyn std::error::Error>>(())
+        };
+
+        if let Err(error) = result.await {
+            _ = tx.send(Operation::Failed {
+                error: error.to_string(),
+            });
+        }
+    });
+}
+
+/// Everything in the database as one JSON file, see `Dataset`.
+fn save_dataset(db: SqlitePool, tx: Sender<Operation>) {
+    let dialog = rfd::AsyncFileDialog::new()
+        .add_filter("JSON", &["json"])
+        .set_file_name("company_data.json");
+    let save_task = dialog.save_file();
+
+    tokio::spawn(async move {
+        let Some(file) = save_task.await else {
+            return;
+        };
+
+        let result = async {
+            let dataset = export_dataset(db).await?;
+            file.write(&dataset.to_json()).await?;
+
+            Ok::<_, Box<dyn std::error::Error>>(())
+        };
+
+        if let Err(error) = result.await {
+            _ = tx.send(Operation::Failed {
+                error: error.to_string(),
+            });
+        }
+    });
+}
+
+fn import_dataset_file(db: SqlitePool, mode: DatasetMode, tx: Sender<Operation>) {
+    let open_task = rfd::AsyncFileDialog::new()
+        .add_filter("JSON", &["json"])
+        .pick_file();
+
+    tokio::spawn(async move {
+        let Some(file) = open_task.await else {
+            return Ok(());
+        };
+
+        let counts = match Dataset::from_json(&file.read().await) {
+            Ok(dataset) => import_dataset(db, dataset, mode).await,
+            Err(error) => Err(error),
+        };
+        tx.send(Operation::DatasetImported {
+            file_name: file.file_name(),
+            counts,
         })
+    });
+}
+
+fn receive_dataset(
+    state: &mut State,
+    file_name: String,
+    counts: Result<DatasetCounts, DatasetError>,
+) {
+    let language = state.settings.language;
+
+    match counts {
+        Ok(counts) => {
+            state.messages.push(language.trf(
+                Message::DatasetImported,
+                &[
+                    ("file", &file_name),
+                    ("companies", &counts.companies),
+                    ("balances", &counts.balances),
+                    ("other", &counts.other),
+                ],
+            ));
+            state.need_to_fetch = true;
+        }
+        Err(error) => {
+            let mut lines = error.describe(language).into_iter();
+            let first = lines.next().unwrap_or_default();
+            state.messages.push(language.trf(
+                Message::ImportFailed,
+                &[("file", &file_name), ("error", &first)],
+            ));
+            state.messages.extend(lines);
+        }
+    }
+}
+
+fn save_to_pdf(state: &mut State, tx: Sender<Operation>) {
+    let dialog = rfd::AsyncFileDialog::new().set_file_name("company_list.pdf");
+    let save_task = dialog.save_file();
+    let sheet = export_sheet(state);
+    let number_format = state.settings.number_format.clone();
+    let language = state.settings.language;
+    tokio::spawn(async move {
+        let Some(file) = save_task.await else {
+            return;
+        };
+
+        let result = async {
+            let pdf = export_to_pdf(&sheet, &number_format, language)?;
+            file.write(&pdf).await?;
+
+            Ok::<_, Box<dyn std::error::Error>>(())
+        };
+
+        if let Err(error) = result.await {
+            _ = tx.send(Operation::Failed {
+                error: error.to_string(),
+            });
+        }
+    });
+}
+
+fn save_to_html(state: &mut State, tx: Sender<Operation>) {
+    let dialog = rfd::AsyncFileDialog::new().set_file_name("company_list.html");
+    let save_task = dialog.save_file();
+    let sheet = export_sheet(state);
+    let title = sheet_title(state);
+    let number_format = state.settings.number_format.clone();
+    let language = state.settings.language;
+    tokio::spawn(async move {
+        let Some(file) = save_task.await else {
+            return;
+        };
+
+        let html = export_to_html(&sheet, &title, &number_format, language);
+        if let Err(error) = file.write(html.as_bytes()).await {
+            _ = tx.send(Operation::Failed {
+                error: error.to_string(),
+            });
+        }
+    });
+}
+
+fn aging_report(state: &State) -> Vec<AgingRow> {
+    let Some(report) = state.period() else {
+        return Vec::new();
+    };
+
+    let currencies = state.currencies();
+    let companies = state.companies();
+
+    // ages come from the history in the company's own currency, amounts are converted after
+    let mut rows = aging_rows(&companies, &state.balance_history, &state.periods, report);
+    let currency_of: HashMap<i64, &str> = companies
+        .iter()
+        .map(|company| (company.id, company.currency.as_str()))
         .collect();
+    for row in &mut rows {
+        let Some(currency) = currency_of.get(&row.id) else {
+            continue;
+        };
+        let rate = currencies.rate(currency);
+        for value in row.receivables.iter_mut().chain(row.payables.iter_mut()) {
+            *value *= rate;
+        }
+    }
+    rows
+}
+
+fn save_aging_to_excel(state: &State, tx: Sender<Operation>) {
+    let Some(date) = state.period().map(Period::last_day) else {
+        return;
+    };
+
+    let rows = state.aging.clone();
+    let dialog = rfd::AsyncFileDialog::new().set_file_name("aging.xlsx");
+    let save_task = dialog.save_file();
+    let number_format = state.settings.number_format.clone();
+    let language = state.settings.language;
     tokio::spawn(async move {
-        let file = save_task.await;
-        if let Some(file) = file {
-            let excel = export_to_excel(&mapped_to_excel).unwrap();
-            _ = file.write(&excel).await;
+        let Some(file) = save_task.await else {
+            return;
+        };
+
+        let result = async {
+            let excel = aging_to_excel(&rows, &date, &number_format, language)?;
+            file.write(&excel).await?;
+
+            Ok::<_, Box<dyn std::error::Error>>(())
+        };
+
+        if let Err(error) = result.await {
+            _ = tx.send(Operation::Failed {
+                error: error.to_string(),
+            });
+        }
+    });
+}
+
+fn save_reconciliation(
+    db: SqlitePool,
+    state: &State,
+    request: ReconciliationRequest,
+    tx: Sender<Operation>,
+) {
+    let Some(company) = state.rows.iter().find_map(|row| match row {
+        Row::Constant(company) if company.id == request.company_id => Some(company.clone()),
+        _ => None,
+    }) else {
+        return;
+    };
+
+    let extension = match request.format {
+        ReconciliationFormat::Pdf => "pdf",
+        ReconciliationFormat::Excel => "xlsx",
+    };
+    let dialog = rfd::AsyncFileDialog::new()
+        .set_file_name(format!("reconciliation_{}.{extension}", company.name));
+    let save_task = dialog.save_file();
+
+    let position = |id: i64| state.periods.iter().position(|period| period.id == id);
+    let (Some(from), Some(to)) = (position(request.from), position(request.to)) else {
+        return;
+    };
+    let periods = state.periods[from..=to].to_vec();
+
+    let view = state.settings.account;
+    let organization = state.settings.organization.clone();
+    let number_format = state.settings.number_format.clone();
+    let language = state.settings.language;
+
+    tokio::spawn(async move {
+        let Some(file) = save_task.await else {
+            return;
+        };
+
+        let result = async {
+            let history = get_company_history(db, company.id, view).await?;
+            let balances = periods
+                .iter()
+                .filter_map(|period| {
+                    let balance = history.iter().find(|b| b.period_id == period.id)?;
+                    Some((period.clone(), balance.clone()))
+                })
+                .collect();
+
+            let reconciliation = Reconciliation {
+                organization,
+                company,
+                from: periods[0].clone(),
+                to: periods[periods.len() - 1].clone(),
+                balances,
+            };
+
+            let bytes = match request.format {
+                ReconciliationFormat::Pdf => {
+                    reconciliation_to_pdf(&reconciliation, &number_format, language)?
+                }
+                ReconciliationFormat::Excel => {
+                    reconciliation_to_excel(&reconciliation, &number_format, language)?
+                }
+            };
+            file.write(&bytes).await?;
+
+            Ok::<_, Box<dyn std::error::Error>>(())
+        };
+
+        if let Err(error) = result.await {
+            _ = tx.send(Operation::Failed {
+                error: error.to_string(),
+            });
+        }
+    });
+}
+
+fn theme_buttons(ui: &mut egui::Ui, language: Language) {
+    let mut dark_mode = ui.ctx().style().visuals.dark_mode;
+
+    let dark = ui.radio_value(&mut dark_mode, true, language.tr(Message::DarkTheme));
+    let light = ui.radio_value(&mut dark_mode, false, language.tr(Message::LightTheme));
+
+    if dark.changed() || light.changed() {
+        ui.ctx().set_visuals(if dark_mode {
+            egui::Visuals::dark()
+        } else {
+            egui::Visuals::light()
+        });
+    }
+}
+
+fn number_format_menu(ui: &mut egui::Ui, number_format: &mut NumberFormat, language: Language) {
+    ui.label(language.tr(Message::DecimalSeparator));
+    for separator in NumberFormat::DECIMAL_SEPARATORS {
+        let changed = ui
+            .radio_value(
+                &mut number_format.decimal_separator,
+                separator,
+                separator_label(Some(separator), language),
+            )
+            .changed();
+
+        // the same character can't be both, parsing would become ambiguous
+        if changed && number_format.group_separator == Some(separator) {
+            number_format.group_separator = NumberFormat::default().group_separator;
         }
+    }
+
+    ui.separator();
+    ui.label(language.tr(Message::ThousandsSeparator));
+    for separator in NumberFormat::GROUP_SEPARATORS {
+        ui.add_enabled_ui(separator != Some(number_format.decimal_separator), |ui| {
+            ui.radio_value(
+                &mut number_format.group_separator,
+                separator,
+                separator_label(separator, language),
+            );
+        });
+    }
+
+    ui.separator();
+    ui.horizontal(|ui| {
+        ui.label(language.tr(Message::DecimalPlaces));
+        ui.add(
+            egui::DragValue::new(&mut number_format.decimal_places)
+                .clamp_range(0..=NumberFormat::MAX_DECIMAL_PLACES),
+        );
     });
+
+    ui.separator();
+    ui.label(format!(
+        "{}: {}",
+        language.tr(Message::Preview),
+        number_format.format(-1234567.891)
+    ));
 }
 
-fn calculate_total(rows: &[Row], tx: Sender<Operation>) {
+fn calculate_total(rows: &[Row], currencies: Currencies, tx: Sender<Operation>) {
     let constant_rows: Vec<_> = rows
         .iter()
         .filter_map(|x| match x {
-            Row::Constant(row) => Some(row.to_owned()),
+            Row::Constant(row) => Some(currencies.convert(row)),
             _ => None,
         })
         .collect();
@@ -454,46 +2525,7 @@ fn calculate_total(rows: &[Row], tx: Sender<Operation>) {
     // this is overkill to use tokio spawn for sync stuff but i don't care(i want my code to look
     // pretty)
     tokio::spawn(async move {
-        let total = constant_rows
-            .iter()
-            .fold(TotalRow::default(), |acc, constant| {
-                let positive_begin = constant.remainder_begin_month >= 0.;
-                let remainder_begin = match positive_begin {
-                    true => constant.remainder_begin_month,
-                    false => constant.remainder_begin_month * -1.,
-                };
-
-                let positive_end = constant.remainder_end_month >= 0.;
-                let remainder_end = match positive_end {
-                    true => constant.remainder_end_month,
-                    false => constant.remainder_end_month * -1.,
-                };
-
-                TotalRow {
-                    remainder_begin_month_pos: if positive_begin {
-                        acc.remainder_begin_month_pos + remainder_begin
-                    } else {
-                        acc.remainder_begin_month_pos
-                    },
-                    remainder_begin_month_neg: if !positive_begin {
-                        acc.remainder_begin_month_neg + remainder_begin
-                    } else {
-                        acc.remainder_begin_month_neg
-                    },
-                    debit_turnover: acc.debit_turnover + constant.debit_turnover,
-                    credit_turnover: acc.credit_turnover + constant.debit_turnover,
-                    remainder_end_month_pos: if positive_end {
-                        acc.remainder_begin_month_pos + remainder_end
-                    } else {
-                        acc.remainder_end_month_pos
-                    },
-                    remainder_end_month_neg: if !positive_end {
-                        acc.remainder_begin_month_neg + remainder_end
-                    } else {
-                        acc.remainder_end_month_neg
-                    },
-                }
-            });
+        let total: TotalRow = constant_rows.iter().collect();
         tx.send(Operation::Total { total })
     });
 }
@@ -0,0 +1,220 @@
+use strum::IntoEnumIterator;
+
+use super::{
+    i18n::{Language, Message},
+    model::{Account, AccountKind, AccountView},
+};
+
+impl AccountKind {
+    pub fn title(self) -> Message {
+        match self {
+            AccountKind::Active => Message::ActiveAccount,
+            AccountKind::Passive => Message::PassiveAccount,
+            AccountKind::ActivePassive => Message::ActivePassiveAccount,
+        }
+    }
+}
+
+fn account_label(account: &Account) -> String {
+    format!("{} {}", account.code, account.name)
+}
+
+pub fn view_label(view: AccountView, accounts: &[Account], language: Language) -> String {
+    match view {
+        AccountView::Account(id) => accounts
+            .iter()
+            .find(|account| account.id == id)
+            .map(account_label)
+            .unwrap_or_default(),
+        AccountView::Consolidated => language.tr(Message::AllAccounts).to_string(),
+    }
+}
+
+/// Combo box for the top panel: every account and the consolidated view.
+pub fn account_selector(
+    ui: &mut egui::Ui,
+    view: &mut AccountView,
+    accounts: &[Account],
+    language: Language,
+) {
+    let selected_text = view_label(*view, accounts, language);
+
+    ui.label(language.tr(Message::Account));
+    egui::ComboBox::from_id_source("account_selector")
+        .selected_text(selected_text)
+        .width(280.0)
+        .show_ui(ui, |ui| {
+            for account in accounts {
+                ui.selectable_value(
+                    view,
+                    AccountView::Account(account.id),
+                    account_label(account),
+                );
+            }
+            ui.separator();
+            ui.selectable_value(
+                view,
+                AccountView::Consolidated,
+                language.tr(Message::AllAccounts),
+            );
+        });
+}
+
+fn kind_combo(ui: &mut egui::Ui, id_source: &str, kind: &mut AccountKind, language: Language) {
+    egui::ComboBox::from_id_source(id_source)
+        .selected_text(language.tr(kind.title()))
+        .show_ui(ui, |ui| {
+            for option in AccountKind::iter() {
+                ui.selectable_value(kind, option, language.tr(option.title()));
+            }
+        });
+}
+
+pub enum AccountAction {
+    Add {
+        code: String,
+        name: String,
+        kind: AccountKind,
+    },
+    Edit(Account),
+    Delete(i64),
+}
+
+pub struct AccountEditor {
+    pub open: bool,
+    new_code: String,
+    new_name: String,
+    new_kind: AccountKind,
+    editing: Option<Account>,
+    /// The account waiting for its deletion to be confirmed.
+    confirm_delete: Option<Account>,
+}
+
+impl Default for AccountEditor {
+    fn default() -> Self {
+        Self {
+            open: false,
+            new_code: Default::default(),
+            new_name: Default::default(),
+            new_kind: AccountKind::ActivePassive,
+            editing: None,
+            confirm_delete: None,
+        }
+    }
+}
+
+impl AccountEditor {
+    pub fn editor_ui(
+        &mut self,
+        ctx: &egui::Context,
+        accounts: &[Account],
+        language: Language,
+    ) -> Option<AccountAction> {
+        let mut action = None;
+        let mut open = self.open;
+
+        egui::Window::new(language.tr(Message::Accounts))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                if let Some(account) = &self.confirm_delete {
+                    ui.label(language.trf(
+                        Message::DeleteAccountConfirm,
+                        &[("account", &account_label(account))],
+                    ));
+                    ui.horizontal(|ui| {
+                        if ui.button(language.tr(Message::Delete)).clicked() {
+                            action = self
+                                .confirm_delete
+                                .take()
+                                .map(|account| AccountAction::Delete(account.id));
+                        }
+                        if ui.button(language.tr(Message::Cancel)).clicked() {
+                            self.confirm_delete = None;
+                        }
+                    });
+                    return;
+                }
+
+                egui::Grid::new("accounts_grid")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for account in accounts {
+                            match &mut self.editing {
+                                Some(editing) if editing.id == account.id => {
+                                    ui.add(
+                                        egui::TextEdit::singleline(&mut editing.code)
+                                            .desired_width(50.0),
+                                    );
+                                    ui.text_edit_singleline(&mut editing.name);
+                                    kind_combo(
+                                        ui,
+                                        "editing_account_kind",
+                                        &mut editing.kind,
+                                        language,
+                                    );
+                                    if ui
+                                        .add_enabled(
+                                            is_filled(&editing.code, &editing.name),
+                                            egui::Button::new(language.tr(Message::Save)),
+                                        )
+                                        .clicked()
+                                    {
+                                        action = self.editing.take().map(AccountAction::Edit);
+                                    }
+                                    if ui.button(language.tr(Message::Cancel)).clicked() {
+                                        self.editing = None;
+                                    }
+                                }
+                                _ => {
+                                    ui.label(&account.code);
+                                    ui.label(&account.name);
+                                    ui.label(language.tr(account.kind.title()));
+                                    if ui.button(language.tr(Message::Edit)).clicked() {
+                                        self.editing = Some(account.clone());
+                                    }
+                                    if ui.button(language.tr(Message::Delete)).clicked() {
+                                        self.confirm_delete = Some(account.clone());
+                                    }
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.new_code)
+                            .hint_text(language.tr(Message::AccountCode))
+                            .desired_width(50.0),
+                    );
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.new_name)
+                            .hint_text(language.tr(Message::Name)),
+                    );
+                    kind_combo(ui, "new_account_kind", &mut self.new_kind, language);
+                    if ui
+                        .add_enabled(
+                            is_filled(&self.new_code, &self.new_name),
+                            egui::Button::new(language.tr(Message::AddAccount)),
+                        )
+                        .clicked()
+                    {
+                        action = Some(AccountAction::Add {
+                            code: std::mem::take(&mut self.new_code).trim().to_string(),
+                            name: std::mem::take(&mut self.new_name).trim().to_string(),
+                            kind: self.new_kind,
+                        });
+                    }
+                });
+            });
+
+        self.open = open;
+        action
+    }
+}
+
+fn is_filled(code: &str, name: &str) -> bool {
+    !code.trim().is_empty() && !name.trim().is_empty()
+}
@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+
+use egui_extras::{Column, TableBuilder, TableRow};
+
+use super::{
+    format::NumberFormat,
+    i18n::{Language, Message},
+    model::{Company, Period, PeriodBalance},
+};
+
+pub const BUCKETS: [Message; 4] = [
+    Message::Days0To30,
+    Message::Days31To60,
+    Message::Days61To90,
+    Message::DaysOver90,
+];
+
+fn bucket(days: i64) -> usize {
+    match days {
+        ..=30 => 0,
+        31..=60 => 1,
+        61..=90 => 2,
+        _ => 3,
+    }
+}
+
+/// Debit and credit balance of a company split by how many days ago it arose.
+#[derive(Default, Clone)]
+pub struct AgingRow {
+    pub id: i64,
+    pub name: String,
+    pub receivables: [f64; 4],
+    pub payables: [f64; 4],
+}
+
+impl AgingRow {
+    /// The four buckets of a side followed by their sum.
+    pub fn values(side: &[f64; 4]) -> [f64; 5] {
+        let [a, b, c, d] = *side;
+        [a, b, c, d, a + b + c + d]
+    }
+
+    fn add(&mut self, other: &AgingRow) {
+        for i in 0..4 {
+            self.receivables[i] += other.receivables[i];
+            self.payables[i] += other.payables[i];
+        }
+    }
+}
+
+/// Splits the closing balance of every company by age as of the end of `report`.
+///
+/// Balances only know their month, so the balance is taken to be made of the latest turnovers
+/// on its side (first in, first out), each dated by the last day of its period. Whatever the
+/// history doesn't cover is dated by the opening balance of the earliest period.
+pub fn aging_rows(
+    companies: &[&Company],
+    history: &[(i64, PeriodBalance)],
+    periods: &[Period],
+    report: &Period,
+) -> Vec<AgingRow> {
+    let report_day = report.last_day_number();
+    let periods: HashMap<i64, &Period> = periods
+        .iter()
+        .filter(|period| period.last_day_number() <= report_day)
+        .map(|period| (period.id, period))
+        .collect();
+
+    // the history comes oldest first, so each company's balances stay in that order
+    let mut by_company: HashMap<i64, Vec<(&Period, &PeriodBalance)>> = HashMap::new();
+    for (company_id, balance) in history {
+        if let Some(period) = periods.get(&balance.period_id) {
+            by_company
+                .entry(*company_id)
+                .or_default()
+                .push((period, balance));
+        }
+    }
+
+    companies
+        .iter()
+        .filter(|company| company.remainder_end_month != 0.)
+        .map(|company| {
+            // newest first
+            let balances: Vec<_> = by_company
+                .get(&company.id)
+                .map_or(&[][..], Vec::as_slice)
+                .iter()
+                .rev()
+                .collect();
+
+            let debit = company.remainder_end_month > 0.;
+            let mut remaining = company.remainder_end_month.abs();
+            let mut buckets = [0.; 4];
+
+            for (period, balance) in &balances {
+                let turnover = if debit {
+                    balance.debit_turnover
+                } else {
+                    balance.credit_turnover
+                };
+                let amount = turnover.clamp(0., remaining);
+                buckets[bucket(report_day - period.last_day_number())] += amount;
+                remaining -= amount;
+            }
+
+            if remaining > 0. {
+                let opened = balances
+                    .last()
+                    .map_or(report_day, |(period, _)| period.first_day_number() - 1);
+                buckets[bucket(report_day - opened)] += remaining;
+            }
+
+            let mut row = AgingRow {
+                id: company.id,
+                name: company.name.clone(),
+                ..Default::default()
+            };
+            if debit {
+                row.receivables = buckets;
+            } else {
+                row.payables = buckets;
+            }
+            row
+        })
+        .collect()
+}
+
+pub fn aging_total(rows: &[AgingRow]) -> AgingRow {
+    rows.iter().fold(AgingRow::default(), |mut total, row| {
+        total.add(row);
+        total
+    })
+}
+
+pub enum AgingAction {
+    Open(i64),
+    Export,
+}
+
+pub fn aging_ui(
+    ui: &mut egui::Ui,
+    rows: &[AgingRow],
+    report: Option<&Period>,
+    number_format: &NumberFormat,
+    language: Language,
+) -> Option<AgingAction> {
+    let mut action = None;
+
+    let Some(report) = report else {
+        ui.label(language.tr(Message::NoPeriods));
+        return None;
+    };
+
+    ui.horizontal(|ui| {
+        ui.heading(language.trf(Message::AgingAsOf, &[("date", &report.last_day())]));
+        if ui.button(language.tr(Message::Excel)).clicked() {
+            action = Some(AgingAction::Export);
+        }
+    });
+    ui.separator();
+
+    let total = aging_total(rows);
+    let available_height = ui.available_height();
+
+    TableBuilder::new(ui)
+        .striped(true)
+        .resizable(true)
+        .sense(egui::Sense::click())
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(Column::initial(25.0).at_least(25.0).at_most(30.0))
+        .column(Column::auto().at_least(150.0))
+        .columns(Column::initial(400.0).at_least(300.0), 2)
+        .min_scrolled_height(0.0)
+        .max_scroll_height(available_height)
+        .header(40.0, |mut header| {
+            header.col(|ui| {
+                ui.vertical_centered(|ui| ui.strong(language.tr(Message::Id)));
+            });
+            header.col(|ui| {
+                ui.vertical_centered(|ui| ui.strong(language.tr(Message::Name)));
+            });
+            for side in [Message::Receivables, Message::Payables] {
+                header.col(|ui| side_header(ui, language.tr(side), language));
+            }
+        })
+        .body(|body| {
+            body.rows(18.0, rows.len() + 1, |mut row| {
+                match rows.get(row.index()) {
+                    Some(aging) => {
+                        row.col(|ui| {
+                            ui.label(aging.id.to_string());
+                        });
+                        row.col(|ui| {
+                            ui.label(&aging.name);
+                        });
+                        aging_values(&mut row, aging, number_format);
+
+                        if row.response().double_clicked() {
+                            action = Some(AgingAction::Open(aging.id));
+                        }
+                    }
+                    None => {
+                        row.col(|ui| {
+                            ui.label("");
+                        });
+                        row.col(|ui| {
+                            ui.strong(language.tr(Message::Total));
+                        });
+                        aging_values(&mut row, &total, number_format);
+                    }
+                }
+            });
+        });
+
+    action
+}
+
+fn side_header(ui: &mut egui::Ui, title: &str, language: Language) {
+    ui.vertical_centered(|ui| {
+        ui.strong(title);
+        ui.separator();
+        ui.columns(5, |columns| {
+            for (column, title) in columns.iter_mut().zip(BUCKETS) {
+                column.vertical_centered(|ui| ui.strong(language.tr(title)));
+            }
+            columns[4].vertical_centered(|ui| ui.strong(language.tr(Message::Total)));
+        });
+    });
+}
+
+fn aging_values(row: &mut TableRow, aging: &AgingRow, number_format: &NumberFormat) {
+    for side in [&aging.receivables, &aging.payables] {
+        row.col(|ui| {
+            ui.columns(5, |columns| {
+                for (column, value) in columns.iter_mut().zip(AgingRow::values(side)) {
+                    column.vertical_centered(|ui| ui.label(number_format.format(value)));
+                }
+            });
+        });
+    }
+}
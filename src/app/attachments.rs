@@ -0,0 +1,73 @@
+use super::{
+    i18n::{Language, Message},
+    model::Attachment,
+};
+
+pub enum AttachmentAction {
+    /// Pick files to attach with the file dialog.
+    Attach,
+    Save(i64),
+    Delete(i64),
+}
+
+fn size_text(size: i64, language: Language) -> String {
+    let kilobytes = (size as f64 / 1024.).ceil();
+    language.trf(Message::Kilobytes, &[("size", &kilobytes)])
+}
+
+/// The files of the company with a preview of the images, the loaders installed in `run`
+/// decode them.
+pub fn attachments_ui(
+    ui: &mut egui::Ui,
+    attachments: &[Attachment],
+    language: Language,
+) -> Option<AttachmentAction> {
+    let mut action = None;
+
+    ui.horizontal(|ui| {
+        if ui.button(language.tr(Message::AttachFile)).clicked() {
+            action = Some(AttachmentAction::Attach);
+        }
+        ui.weak(language.tr(Message::DropToAttach));
+    });
+    ui.separator();
+
+    if attachments.is_empty() {
+        ui.label(language.tr(Message::NoAttachments));
+        return action;
+    }
+
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        for attachment in attachments {
+            ui.group(|ui| {
+                ui.set_width(ui.available_width());
+                ui.horizontal(|ui| {
+                    ui.strong(&attachment.file_name);
+                    ui.weak(size_text(attachment.size, language));
+                    ui.weak(&attachment.created_at);
+                });
+                if let Some(bytes) = &attachment.preview {
+                    let uri = format!(
+                        "bytes://attachment/{}/{}",
+                        attachment.id, attachment.file_name
+                    );
+                    ui.add(
+                        egui::Image::from_bytes(uri, bytes.clone())
+                            .max_height(200.0)
+                            .max_width(ui.available_width()),
+                    );
+                }
+                ui.horizontal(|ui| {
+                    if ui.button(language.tr(Message::SaveAs)).clicked() {
+                        action = Some(AttachmentAction::Save(attachment.id));
+                    }
+                    if ui.button(language.tr(Message::Delete)).clicked() {
+                        action = Some(AttachmentAction::Delete(attachment.id));
+                    }
+                });
+            });
+        }
+    });
+
+    action
+}
@@ -0,0 +1,192 @@
+use strum::IntoEnumIterator;
+
+use super::{
+    format::NumberFormat,
+    groups::{group_combo, group_path},
+    i18n::{Language, Message},
+    model::{BulkChange, Company, Group},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::EnumIter)]
+enum BulkField {
+    ZeroTurnovers,
+    Group,
+    Note,
+}
+
+impl BulkField {
+    fn title(self) -> Message {
+        match self {
+            BulkField::ZeroTurnovers => Message::ZeroTurnovers,
+            BulkField::Group => Message::Group,
+            BulkField::Note => Message::Note,
+        }
+    }
+}
+
+impl BulkChange {
+    /// The value the change sets, as it reads for the company before or after it.
+    fn shown(
+        &self,
+        company: &Company,
+        groups: &[Group],
+        number_format: &NumberFormat,
+        language: Language,
+    ) -> String {
+        let group = |id: Option<i64>| match id {
+            Some(id) => group_path(groups, id),
+            None => language.tr(Message::NoGroup).to_string(),
+        };
+
+        match self {
+            BulkChange::ZeroTurnovers => format!(
+                "{} / {}",
+                number_format.format(company.debit_turnover),
+                number_format.format(company.credit_turnover)
+            ),
+            BulkChange::Group(_) => group(company.group_id),
+            BulkChange::Note(_) => company.note.clone(),
+        }
+    }
+
+    fn apply(&self, company: &Company) -> Company {
+        match self {
+            BulkChange::ZeroTurnovers => Company {
+                debit_turnover: 0.,
+                credit_turnover: 0.,
+                remainder_end_month: company.remainder_begin_month,
+                ..company.clone()
+            },
+            BulkChange::Group(group_id) => Company {
+                group_id: *group_id,
+                ..company.clone()
+            },
+            BulkChange::Note(note) => Company {
+                note: note.clone(),
+                ..company.clone()
+            },
+        }
+    }
+}
+
+pub struct BulkEditor {
+    pub open: bool,
+    field: BulkField,
+    group_id: Option<i64>,
+    note: String,
+}
+
+impl Default for BulkEditor {
+    fn default() -> Self {
+        Self {
+            open: false,
+            field: BulkField::ZeroTurnovers,
+            group_id: None,
+            note: String::new(),
+        }
+    }
+}
+
+impl BulkEditor {
+    fn change(&self) -> BulkChange {
+        match self.field {
+            BulkField::ZeroTurnovers => BulkChange::ZeroTurnovers,
+            BulkField::Group => BulkChange::Group(self.group_id),
+            BulkField::Note => BulkChange::Note(self.note.trim().to_string()),
+        }
+    }
+
+    /// Returns the change once it is applied, the window closes then.
+    pub fn editor_ui(
+        &mut self,
+        ctx: &egui::Context,
+        companies: &[&Company],
+        groups: &[Group],
+        number_format: &NumberFormat,
+        language: Language,
+    ) -> Option<BulkChange> {
+        let mut applied = None;
+        let mut open = self.open;
+
+        egui::Window::new(language.tr(Message::BulkEdit))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(language.tr(Message::BulkField));
+                    egui::ComboBox::from_id_source("bulk_field")
+                        .selected_text(language.tr(self.field.title()))
+                        .show_ui(ui, |ui| {
+                            for field in BulkField::iter() {
+                                ui.selectable_value(
+                                    &mut self.field,
+                                    field,
+                                    language.tr(field.title()),
+                                );
+                            }
+                        });
+
+                    match self.field {
+                        BulkField::ZeroTurnovers => {}
+                        BulkField::Group => group_combo(
+                            ui,
+                            "bulk_group",
+                            &mut self.group_id,
+                            groups,
+                            None,
+                            language,
+                        ),
+                        BulkField::Note => {
+                            ui.text_edit_singleline(&mut self.note);
+                        }
+                    }
+                });
+                ui.separator();
+
+                let change = self.change();
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("bulk_preview")
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for title in
+                                    [Message::Name, Message::BulkBefore, Message::BulkAfter]
+                                {
+                                    ui.strong(language.tr(title));
+                                }
+                                ui.end_row();
+
+                                for company in companies {
+                                    let after = change.apply(company);
+                                    ui.label(&company.name);
+                                    ui.label(change.shown(
+                                        company,
+                                        groups,
+                                        number_format,
+                                        language,
+                                    ));
+                                    ui.label(change.shown(&after, groups, number_format, language));
+                                    ui.end_row();
+                                }
+                            });
+                    });
+
+                ui.separator();
+                if ui
+                    .add_enabled(
+                        !companies.is_empty(),
+                        egui::Button::new(
+                            language.trf(Message::BulkApply, &[("count", &companies.len())]),
+                        ),
+                    )
+                    .clicked()
+                {
+                    applied = Some(change);
+                }
+            });
+
+        self.open = open && applied.is_none();
+        applied
+    }
+}
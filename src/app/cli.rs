@@ -0,0 +1,140 @@
+use std::path::Path;
+
+use sqlx::SqlitePool;
+
+use super::{
+    currency::Currencies,
+    dataset::{Dataset, DatasetMode},
+    exports::{
+        build_sheet, export_to_excel, export_to_html, export_to_ods, export_to_pdf, SheetTitle,
+    },
+    i18n::Message,
+    model::AccountView,
+    operations::{
+        export_dataset, get_all_companies, get_all_periods, get_all_rates, get_all_rules,
+        import_dataset,
+    },
+    Settings,
+};
+
+/// Runs `export <file> [MM.YYYY]` or `import <file.json> merge|replace` given on the command
+/// line instead of opening the window.
+pub async fn run(db: SqlitePool, args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    match args {
+        [command, path] if command == "export" => export(db, Path::new(path), None).await,
+        [command, path, period] if command == "export" => {
+            export(db, Path::new(path), Some(period)).await
+        }
+        [command, path, mode] if command == "import" && mode == "merge" => {
+            import(db, Path::new(path), DatasetMode::Merge).await
+        }
+        [command, path, mode] if command == "import" && mode == "replace" => {
+            import(db, Path::new(path), DatasetMode::Replace).await
+        }
+        _ => Err(Settings::default().language.tr(Message::CliUsage).into()),
+    }
+}
+
+/// The consolidated sheet of the latest period, or the `MM.YYYY` one, written to `path` in the
+/// format its extension names. The settings of the window are not read, the defaults are used.
+async fn export(
+    db: SqlitePool,
+    path: &Path,
+    period: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let settings = Settings::default();
+    let language = settings.language;
+
+    let extension = path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase());
+    if !matches!(
+        extension.as_deref(),
+        Some("xlsx" | "ods" | "pdf" | "html" | "json")
+    ) {
+        return Err(language.tr(Message::CliUnknownFormat).into());
+    }
+    if extension.as_deref() == Some("json") {
+        std::fs::write(path, export_dataset(db).await?.to_json())?;
+        return Ok(());
+    }
+
+    let periods = get_all_periods(db.clone()).await?;
+    let period = match period {
+        Some(label) => Some(
+            periods
+                .into_iter()
+                .find(|period| period.label() == label)
+                .ok_or_else(|| language.trf(Message::CliUnknownPeriod, &[("period", &label)]))?,
+        ),
+        None => periods.into_iter().last(),
+    };
+
+    let companies = get_all_companies(
+        db.clone(),
+        AccountView::Consolidated,
+        period.as_ref().map(|period| period.id),
+    )
+    .await?;
+    let rates = get_all_rates(db.clone()).await?;
+    let rules = get_all_rules(db).await?;
+
+    let companies: Vec<_> = companies.iter().collect();
+    let currencies = Currencies::new(
+        &settings.reporting_currency,
+        &rates,
+        period.as_ref(),
+        &companies,
+    );
+    let converted: Vec<_> = companies
+        .into_iter()
+        .map(|company| currencies.convert(company))
+        .collect();
+    let converted: Vec<_> = converted.iter().collect();
+    let sheet = build_sheet(&converted, &[], &rules, language);
+
+    let title = SheetTitle {
+        organization: language.tr(Message::OurOrganization).to_string(),
+        period,
+    };
+    let bytes = match extension.as_deref() {
+        Some("ods") => export_to_ods(&sheet, &title, &settings.number_format, language)?,
+        Some("pdf") => export_to_pdf(&sheet, &settings.number_format, language)?,
+        Some("html") => export_to_html(&sheet, &title, &settings.number_format, language).into(),
+        _ => export_to_excel(&sheet, &title, &settings.number_format, language)?,
+    };
+    std::fs::write(path, bytes)?;
+
+    Ok(())
+}
+
+/// Prints what was added, or every conflict when nothing was.
+async fn import(
+    db: SqlitePool,
+    path: &Path,
+    mode: DatasetMode,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let language = Settings::default().language;
+    let contents = std::fs::read(path)?;
+
+    let counts = match Dataset::from_json(&contents) {
+        Ok(dataset) => import_dataset(db, dataset, mode).await,
+        Err(error) => Err(error),
+    }
+    .map_err(|error| error.describe(language).join("\n"))?;
+
+    println!(
+        "{}",
+        language.trf(
+            Message::DatasetImported,
+            &[
+                ("file", &path.display()),
+                ("companies", &counts.companies),
+                ("balances", &counts.balances),
+                ("other", &counts.other),
+            ],
+        )
+    );
+
+    Ok(())
+}
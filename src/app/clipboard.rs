@@ -0,0 +1,185 @@
+use super::{
+    format::NumberFormat,
+    i18n::{Language, Message},
+    model::Company,
+    NewCompanyRow,
+};
+
+pub enum PasteError {
+    ColumnCount {
+        line: usize,
+        found: usize,
+    },
+    EmptyName {
+        line: usize,
+    },
+    InvalidNumber {
+        line: usize,
+        column: usize,
+        value: String,
+    },
+    BothRemainders {
+        line: usize,
+    },
+}
+
+impl PasteError {
+    pub fn describe(&self, language: Language) -> String {
+        match self {
+            PasteError::ColumnCount { line, found } => language.trf(
+                Message::PasteColumnCount,
+                &[("line", line), ("found", found)],
+            ),
+            PasteError::EmptyName { line } => {
+                language.trf(Message::PasteEmptyName, &[("line", line)])
+            }
+            PasteError::InvalidNumber {
+                line,
+                column,
+                value,
+            } => language.trf(
+                Message::PasteInvalidNumber,
+                &[("line", line), ("column", column), ("value", value)],
+            ),
+            PasteError::BothRemainders { line } => {
+                language.trf(Message::PasteBothRemainders, &[("line", line)])
+            }
+        }
+    }
+}
+
+/// Rows copied as tab separated values, in the same column order as `CompanyTable`. Grouping is
+/// left out so spreadsheets recognize the cells as numbers.
+pub fn companies_to_tsv<'a>(
+    companies: impl IntoIterator<Item = &'a Company>,
+    number_format: &NumberFormat,
+) -> String {
+    let number_format = NumberFormat {
+        group_separator: None,
+        ..number_format.clone()
+    };
+
+    let split = |value: f64| {
+        if value >= 0. {
+            (number_format.format(value), String::new())
+        } else {
+            (String::new(), number_format.format(-value))
+        }
+    };
+
+    companies
+        .into_iter()
+        .map(|company| {
+            let (begin_debit, begin_credit) = split(company.remainder_begin_month);
+            let (end_debit, end_credit) = split(company.remainder_end_month);
+
+            [
+                company.id.to_string(),
+                company.name.clone(),
+                begin_debit,
+                begin_credit,
+                number_format.format(company.debit_turnover),
+                number_format.format(company.credit_turnover),
+                end_debit,
+                end_credit,
+            ]
+            .join("\t")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Parses a block of cells copied from a spreadsheet. Accepted layouts are
+/// `name, opening debit, opening credit, debit, credit` with optional closing debit and credit
+/// after them (ignored, they are always recalculated) and an optional code in front, which is
+/// what [`companies_to_tsv`] produces.
+///
+/// Lines with a wrong number of columns are skipped, lines with bad values are still returned
+/// so they can be fixed in the table. Both are reported in the error list.
+pub fn parse_tsv(
+    text: &str,
+    number_format: &NumberFormat,
+) -> (Vec<NewCompanyRow>, Vec<PasteError>) {
+    let lines = text
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.split('\t').collect()));
+
+    parse_cells(lines, number_format)
+}
+
+/// The rows of [`parse_tsv`] from cells already split, numbered by their line in the source.
+/// Blank lines are skipped.
+pub fn parse_cells<'a>(
+    lines: impl IntoIterator<Item = (usize, Vec<&'a str>)>,
+    number_format: &NumberFormat,
+) -> (Vec<NewCompanyRow>, Vec<PasteError>) {
+    let mut rows = Vec::new();
+    let mut errors = Vec::new();
+
+    for (line, cells) in lines {
+        let cells: Vec<_> = cells.into_iter().map(str::trim).collect();
+        if cells.iter().all(|cell| cell.is_empty()) {
+            continue;
+        }
+
+        let (offset, cells) = match cells.len() {
+            5 | 7 => (1, &cells[..5]),
+            6 | 8 => (2, &cells[1..6]),
+            found => {
+                errors.push(PasteError::ColumnCount { line, found });
+                continue;
+            }
+        };
+
+        let [name, begin_debit, begin_credit, debit, credit] = cells else {
+            unreachable!()
+        };
+
+        let invalid: Vec<_> = [begin_debit, begin_credit, debit, credit]
+            .into_iter()
+            .enumerate()
+            .filter(|(_, value)| !value.is_empty() && number_format.parse(value).is_err())
+            .map(|(i, value)| (offset + i + 1, value.to_string()))
+            .collect();
+
+        // a header row copied together with the data, nothing in it looks like a number
+        if line == 1 && invalid.len() == 4 {
+            continue;
+        }
+
+        if name.is_empty() {
+            errors.push(PasteError::EmptyName { line });
+        }
+
+        for (column, value) in invalid {
+            errors.push(PasteError::InvalidNumber {
+                line,
+                column,
+                value,
+            });
+        }
+
+        if !begin_debit.is_empty() && !begin_credit.is_empty() {
+            errors.push(PasteError::BothRemainders { line });
+        }
+
+        let turnover = |value: &str| {
+            if value.is_empty() {
+                number_format.format(0.)
+            } else {
+                value.to_string()
+            }
+        };
+
+        rows.push(NewCompanyRow {
+            name: name.to_string(),
+            remainder_begin_month_pos: begin_debit.to_string(),
+            remainder_begin_month_neg: begin_credit.to_string(),
+            debit_turnover: turnover(debit),
+            credit_turnover: turnover(credit),
+        });
+    }
+
+    (rows, errors)
+}
@@ -0,0 +1,360 @@
+use std::collections::HashMap;
+
+use egui::Color32;
+
+use super::{
+    format::NumberFormat,
+    i18n::{Language, Message},
+    model::{Company, ExchangeRate, Period},
+    validation::validate_currency,
+};
+
+/// The latest rate of the currency set at or before the month.
+pub fn rate_at(rates: &[ExchangeRate], currency: &str, year: i64, month: i64) -> Option<f64> {
+    rates
+        .iter()
+        .filter(|rate| rate.currency == currency && (rate.year, rate.month) <= (year, month))
+        .max_by_key(|rate| (rate.year, rate.month))
+        .map(|rate| rate.rate)
+}
+
+/// Rates of every currency in use at the end of one period.
+#[derive(Default, Clone)]
+pub struct Currencies {
+    reporting: String,
+    rates: HashMap<String, f64>,
+    missing: Vec<String>,
+}
+
+impl Currencies {
+    pub fn new(
+        reporting: &str,
+        rates: &[ExchangeRate],
+        period: Option<&Period>,
+        companies: &[&Company],
+    ) -> Self {
+        let mut currencies = Self {
+            reporting: reporting.to_string(),
+            ..Default::default()
+        };
+
+        for company in companies {
+            let currency = &company.currency;
+            if !currencies.is_foreign(currency)
+                || currencies.rates.contains_key(currency)
+                || currencies.missing.contains(currency)
+            {
+                continue;
+            }
+
+            match period.and_then(|period| rate_at(rates, currency, period.year, period.month)) {
+                Some(rate) => {
+                    currencies.rates.insert(currency.clone(), rate);
+                }
+                None => currencies.missing.push(currency.clone()),
+            }
+        }
+
+        currencies
+    }
+
+    pub fn is_foreign(&self, currency: &str) -> bool {
+        !currency.is_empty() && currency != self.reporting
+    }
+
+    /// A currency without a rate is taken one to one, see [`Currencies::missing`].
+    pub fn rate(&self, currency: &str) -> f64 {
+        self.rates.get(currency).copied().unwrap_or(1.)
+    }
+
+    /// Currencies in use that have no rate for the period yet.
+    pub fn missing(&self) -> &[String] {
+        &self.missing
+    }
+
+    /// The company with its balance in the reporting currency.
+    pub fn convert(&self, company: &Company) -> Company {
+        let rate = self.rate(&company.currency);
+
+        Company {
+            remainder_begin_month: company.remainder_begin_month * rate,
+            debit_turnover: company.debit_turnover * rate,
+            credit_turnover: company.credit_turnover * rate,
+            remainder_end_month: company.remainder_end_month * rate,
+            ..company.clone()
+        }
+    }
+}
+
+/// Exchange difference of one counterparty for a period.
+pub struct Revaluation {
+    pub name: String,
+    pub currency: String,
+    pub opening: f64,
+    pub opening_rate: f64,
+    pub closing_rate: f64,
+}
+
+impl Revaluation {
+    /// Turnovers are taken at the closing rate, so only the opening balance changes its value
+    /// in the reporting currency. A gain is positive.
+    pub fn difference(&self) -> f64 {
+        self.opening * (self.closing_rate - self.opening_rate)
+    }
+}
+
+/// Revaluation of the foreign currency balances from the end of the previous month to the end
+/// of the period. Counterparties without both rates are left out.
+pub fn revaluate(
+    companies: &[&Company],
+    rates: &[ExchangeRate],
+    period: &Period,
+    reporting: &str,
+) -> Vec<Revaluation> {
+    let (year, month) = match period.month {
+        1 => (period.year - 1, 12),
+        month => (period.year, month - 1),
+    };
+
+    companies
+        .iter()
+        .filter(|company| !company.currency.is_empty() && company.currency != reporting)
+        .filter(|company| company.remainder_begin_month != 0.)
+        .filter_map(|company| {
+            Some(Revaluation {
+                name: company.name.clone(),
+                currency: company.currency.clone(),
+                opening: company.remainder_begin_month,
+                opening_rate: rate_at(rates, &company.currency, year, month)?,
+                closing_rate: rate_at(rates, &company.currency, period.year, period.month)?,
+            })
+        })
+        .collect()
+}
+
+/// `MM.YYYY` as the periods are labelled, or `YYYY-MM`.
+fn parse_month(text: &str) -> Option<(i64, i64)> {
+    let (year, month) = match text.split_once('.') {
+        Some((month, year)) => (year, month),
+        None => text.split_once('-')?,
+    };
+    let (year, month) = (year.trim().parse().ok()?, month.trim().parse().ok()?);
+
+    (1..=12).contains(&month).then_some((year, month))
+}
+
+fn parse_rate(currency: &str, month: &str, rate: &str) -> Option<ExchangeRate> {
+    let currency = currency.trim().to_uppercase();
+    validate_currency(&currency).ok()?;
+    let (year, month) = parse_month(month)?;
+    let rate: f64 = rate.trim().replace(',', ".").parse().ok()?;
+
+    (rate > 0.).then_some(ExchangeRate {
+        currency,
+        year,
+        month,
+        rate,
+    })
+}
+
+/// Reads `currency;month;rate` lines, separated by semicolons, commas or tabs. A header line is
+/// skipped, any other line that can't be read fails the import with its number.
+pub fn parse_rates_csv(text: &str) -> Result<Vec<ExchangeRate>, usize> {
+    let mut rates = Vec::new();
+
+    for (i, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let separator = [';', '\t', ',']
+            .into_iter()
+            .find(|separator| line.contains(*separator))
+            .unwrap_or(';');
+        let fields: Vec<_> = line.split(separator).collect();
+
+        match fields.as_slice() {
+            [currency, month, rate] => match parse_rate(currency, month, rate) {
+                Some(rate) => rates.push(rate),
+                None if i == 0 => continue,
+                None => return Err(i + 1),
+            },
+            _ => return Err(i + 1),
+        }
+    }
+
+    Ok(rates)
+}
+
+pub enum RatesAction {
+    Save(ExchangeRate),
+    Delete(ExchangeRate),
+    Import,
+}
+
+#[derive(Default)]
+pub struct RatesEditor {
+    pub open: bool,
+    currency: String,
+    month: String,
+    rate: String,
+}
+
+impl RatesEditor {
+    pub fn editor_ui(
+        &mut self,
+        ctx: &egui::Context,
+        rates: &[ExchangeRate],
+        reporting: &mut String,
+        language: Language,
+    ) -> Option<RatesAction> {
+        let mut action = None;
+        let mut open = self.open;
+
+        egui::Window::new(language.tr(Message::ExchangeRates))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(language.tr(Message::ReportingCurrency));
+                    ui.add(egui::TextEdit::singleline(reporting).desired_width(50.0));
+                    if !reporting.is_empty() {
+                        if let Err(error) = validate_currency(reporting) {
+                            ui.colored_label(Color32::RED, language.tr(error));
+                        }
+                    }
+                });
+                ui.separator();
+
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("rates_grid").striped(true).show(ui, |ui| {
+                            for rate in rates {
+                                ui.label(&rate.currency);
+                                ui.label(format!("{:02}.{}", rate.month, rate.year));
+                                ui.label(rate.rate.to_string());
+                                if ui.button(language.tr(Message::Delete)).clicked() {
+                                    action = Some(RatesAction::Delete(rate.clone()));
+                                }
+                                ui.end_row();
+                            }
+                        });
+                    });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.currency)
+                            .hint_text(language.tr(Message::Currency))
+                            .desired_width(50.0),
+                    );
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.month)
+                            .hint_text(language.tr(Message::Period))
+                            .desired_width(70.0),
+                    );
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.rate)
+                            .hint_text(language.tr(Message::Rate))
+                            .desired_width(70.0),
+                    );
+
+                    let rate = parse_rate(&self.currency, &self.month, &self.rate);
+                    if ui
+                        .add_enabled(
+                            rate.is_some(),
+                            egui::Button::new(language.tr(Message::AddRate)),
+                        )
+                        .clicked()
+                    {
+                        action = rate.map(RatesAction::Save);
+                        self.rate.clear();
+                    }
+                });
+
+                if ui.button(language.tr(Message::ImportCsv)).clicked() {
+                    action = Some(RatesAction::Import);
+                }
+                ui.weak(language.tr(Message::RatesCsvHint));
+            });
+
+        self.open = open;
+        action
+    }
+}
+
+/// Exchange differences of the period with the gains and losses added up.
+pub fn revaluation_window(
+    ctx: &egui::Context,
+    open: &mut bool,
+    period: Option<&Period>,
+    revaluation: &[Revaluation],
+    number_format: &NumberFormat,
+    language: Language,
+) {
+    egui::Window::new(language.tr(Message::Revaluation))
+        .open(open)
+        .resizable(true)
+        .show(ctx, |ui| {
+            let Some(period) = period else {
+                ui.label(language.tr(Message::NoPeriods));
+                return;
+            };
+            ui.strong(language.trf(
+                Message::RevaluationFor,
+                &[("from", &period.first_day()), ("to", &period.last_day())],
+            ));
+            ui.separator();
+
+            egui::Grid::new("revaluation_grid")
+                .striped(true)
+                .show(ui, |ui| {
+                    for title in [
+                        Message::Name,
+                        Message::Currency,
+                        Message::OpeningBalance,
+                        Message::OpeningRate,
+                        Message::ClosingRate,
+                        Message::ExchangeDifference,
+                    ] {
+                        ui.strong(language.tr(title));
+                    }
+                    ui.end_row();
+
+                    for row in revaluation {
+                        ui.label(&row.name);
+                        ui.label(&row.currency);
+                        ui.label(number_format.format(row.opening));
+                        ui.label(row.opening_rate.to_string());
+                        ui.label(row.closing_rate.to_string());
+                        ui.label(number_format.format(row.difference()));
+                        ui.end_row();
+                    }
+                });
+
+            let gains: f64 = revaluation
+                .iter()
+                .map(Revaluation::difference)
+                .filter(|difference| *difference > 0.)
+                .sum();
+            let losses: f64 = revaluation
+                .iter()
+                .map(Revaluation::difference)
+                .filter(|difference| *difference < 0.)
+                .sum();
+
+            ui.separator();
+            egui::Grid::new("revaluation_totals").show(ui, |ui| {
+                for (title, value) in [
+                    (Message::ExchangeGains, gains),
+                    (Message::ExchangeLosses, -losses),
+                    (Message::Total, gains + losses),
+                ] {
+                    ui.label(language.tr(title));
+                    ui.strong(number_format.format(value));
+                    ui.end_row();
+                }
+            });
+        });
+}
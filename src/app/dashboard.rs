@@ -0,0 +1,297 @@
+use egui::Color32;
+use egui_plot::{Bar, BarChart, GridMark, Legend, Line, Plot, PlotPoints};
+
+use super::{
+    format::NumberFormat,
+    i18n::{Language, Message},
+    model::Company,
+    TotalRow,
+};
+
+const TOP_COUNT: usize = 10;
+const DEBIT_COLOR: Color32 = Color32::from_rgb(86, 156, 214);
+const CREDIT_COLOR: Color32 = Color32::from_rgb(214, 120, 86);
+
+type BarFormatter = Box<dyn Fn(&Bar, &BarChart) -> String>;
+type Series = (Message, fn(&TotalRow) -> f64, Color32);
+
+pub struct Dashboard<'a> {
+    companies: Vec<&'a Company>,
+    number_format: &'a NumberFormat,
+    language: Language,
+    history: &'a [(String, TotalRow)],
+}
+
+impl<'a> Dashboard<'a> {
+    /// `companies` are ranked and plotted as they are, so they should all be in the reporting
+    /// currency.
+    pub fn new(
+        companies: Vec<&'a Company>,
+        number_format: &'a NumberFormat,
+        language: Language,
+    ) -> Self {
+        Self {
+            companies,
+            number_format,
+            language,
+            history: &[],
+        }
+    }
+
+    /// Totals of every period, labelled, oldest first.
+    pub fn history(mut self, history: &'a [(String, TotalRow)]) -> Self {
+        self.history = history;
+        self
+    }
+
+    /// Returns the id of the company whose bar was clicked.
+    pub fn dashboard_ui(&self, ui: &mut egui::Ui) -> Option<i64> {
+        let mut clicked = None;
+
+        let mut debtors: Vec<_> = self
+            .companies
+            .iter()
+            .filter(|company| company.remainder_end_month > 0.)
+            .copied()
+            .collect();
+        debtors.sort_by(|a, b| b.remainder_end_month.total_cmp(&a.remainder_end_month));
+        debtors.truncate(TOP_COUNT);
+
+        let mut creditors: Vec<_> = self
+            .companies
+            .iter()
+            .filter(|company| company.remainder_end_month < 0.)
+            .copied()
+            .collect();
+        creditors.sort_by(|a, b| a.remainder_end_month.total_cmp(&b.remainder_end_month));
+        creditors.truncate(TOP_COUNT);
+
+        let mut by_turnover = self.companies.clone();
+        by_turnover.sort_by(|a, b| {
+            let turnover = |company: &Company| company.debit_turnover + company.credit_turnover;
+            turnover(b).total_cmp(&turnover(a))
+        });
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            let chart_height = (ui.available_height() / 2.).max(200.);
+
+            ui.columns(2, |columns| {
+                columns[0].strong(self.language.tr(Message::TopDebtors));
+                clicked = clicked.or(self.balance_chart(
+                    &mut columns[0],
+                    "top_debtors",
+                    &debtors,
+                    DEBIT_COLOR,
+                    chart_height,
+                ));
+
+                columns[1].strong(self.language.tr(Message::TopCreditors));
+                clicked = clicked.or(self.balance_chart(
+                    &mut columns[1],
+                    "top_creditors",
+                    &creditors,
+                    CREDIT_COLOR,
+                    chart_height,
+                ));
+            });
+
+            ui.separator();
+            ui.strong(self.language.tr(Message::TurnoverComparison));
+            clicked = clicked.or(self.turnover_chart(ui, &by_turnover, chart_height));
+
+            // a single period is already on the charts above
+            if self.history.len() > 1 {
+                ui.separator();
+                ui.strong(self.language.tr(Message::TotalsOverTime));
+                self.history_chart(ui, chart_height);
+            }
+        });
+
+        clicked
+    }
+
+    fn balance_chart(
+        &self,
+        ui: &mut egui::Ui,
+        id: &str,
+        companies: &[&Company],
+        color: Color32,
+        height: f32,
+    ) -> Option<i64> {
+        let bars = companies
+            .iter()
+            .enumerate()
+            .map(|(i, company)| {
+                Bar::new(i as f64, company.remainder_end_month.abs())
+                    .name(&company.name)
+                    .width(0.6)
+            })
+            .collect();
+
+        let chart = BarChart::new(bars)
+            .horizontal()
+            .color(color)
+            .element_formatter(self.bar_formatter());
+
+        let names = company_names(companies);
+
+        let response = Plot::new(id)
+            .height(height)
+            .allow_zoom(false)
+            .allow_scroll(false)
+            .allow_drag(false)
+            .y_axis_width(12)
+            .y_axis_formatter(move |mark, _, _| axis_label(&names, mark))
+            .x_axis_formatter(self.value_formatter())
+            .show(ui, |plot_ui| {
+                plot_ui.bar_chart(chart);
+                clicked_index(plot_ui, |point| point.y)
+            });
+
+        response
+            .inner
+            .and_then(|index| companies.get(index))
+            .map(|company| company.id)
+    }
+
+    fn turnover_chart(
+        &self,
+        ui: &mut egui::Ui,
+        companies: &[&Company],
+        height: f32,
+    ) -> Option<i64> {
+        let bars = |offset: f64, value: fn(&Company) -> f64| {
+            companies
+                .iter()
+                .enumerate()
+                .map(|(i, company)| {
+                    Bar::new(i as f64 + offset, value(company))
+                        .name(&company.name)
+                        .width(0.4)
+                })
+                .collect()
+        };
+
+        let debit = BarChart::new(bars(-0.2, |company| company.debit_turnover))
+            .name(self.language.tr(Message::Debit))
+            .color(DEBIT_COLOR)
+            .element_formatter(self.bar_formatter());
+
+        let credit = BarChart::new(bars(0.2, |company| company.credit_turnover))
+            .name(self.language.tr(Message::Credit))
+            .color(CREDIT_COLOR)
+            .element_formatter(self.bar_formatter());
+
+        let names = company_names(companies);
+
+        let response = Plot::new("turnover_comparison")
+            .height(height)
+            .legend(Legend::default())
+            .allow_scroll(false)
+            .x_axis_formatter(move |mark, _, _| axis_label(&names, mark))
+            .y_axis_formatter(self.value_formatter())
+            .show(ui, |plot_ui| {
+                plot_ui.bar_chart(debit);
+                plot_ui.bar_chart(credit);
+                clicked_index(plot_ui, |point| point.x)
+            });
+
+        response
+            .inner
+            .and_then(|index| companies.get(index))
+            .map(|company| company.id)
+    }
+
+    fn history_chart(&self, ui: &mut egui::Ui, height: f32) {
+        let series: [Series; 4] = [
+            (
+                Message::DebitBalance,
+                |total| total.remainder_end_month_pos,
+                DEBIT_COLOR,
+            ),
+            (
+                Message::CreditBalance,
+                |total| total.remainder_end_month_neg,
+                CREDIT_COLOR,
+            ),
+            (
+                Message::Debit,
+                |total| total.debit_turnover,
+                DEBIT_COLOR.gamma_multiply(0.5),
+            ),
+            (
+                Message::Credit,
+                |total| total.credit_turnover,
+                CREDIT_COLOR.gamma_multiply(0.5),
+            ),
+        ];
+
+        let labels: Vec<_> = self
+            .history
+            .iter()
+            .map(|(label, _)| label.clone())
+            .collect();
+
+        Plot::new("totals_over_time")
+            .height(height)
+            .legend(Legend::default())
+            .allow_scroll(false)
+            .x_axis_formatter(move |mark, _, _| axis_label(&labels, mark))
+            .y_axis_formatter(self.value_formatter())
+            .show(ui, |plot_ui| {
+                for (name, value, color) in series {
+                    let points: PlotPoints = self
+                        .history
+                        .iter()
+                        .enumerate()
+                        .map(|(i, (_, total))| [i as f64, value(total)])
+                        .collect();
+
+                    plot_ui.line(Line::new(points).name(self.language.tr(name)).color(color));
+                }
+            });
+    }
+
+    fn bar_formatter(&self) -> BarFormatter {
+        let number_format = self.number_format.clone();
+        Box::new(move |bar, _| format!("{}\n{}", bar.name, number_format.format(bar.value)))
+    }
+
+    fn value_formatter(
+        &self,
+    ) -> impl Fn(GridMark, usize, &std::ops::RangeInclusive<f64>) -> String {
+        let number_format = NumberFormat {
+            decimal_places: 0,
+            ..self.number_format.clone()
+        };
+        move |mark, _, _| number_format.format(mark.value)
+    }
+}
+
+fn company_names(companies: &[&Company]) -> Vec<String> {
+    companies
+        .iter()
+        .map(|company| company.name.clone())
+        .collect()
+}
+
+/// Bars sit on whole numbers, so only those marks get a company name.
+fn axis_label(names: &[String], mark: GridMark) -> String {
+    if mark.value.fract() != 0. || mark.value < 0. {
+        return String::new();
+    }
+
+    names.get(mark.value as usize).cloned().unwrap_or_default()
+}
+
+fn clicked_index(
+    plot_ui: &egui_plot::PlotUi,
+    argument: fn(egui_plot::PlotPoint) -> f64,
+) -> Option<usize> {
+    if !plot_ui.response().clicked() {
+        return None;
+    }
+
+    let position = argument(plot_ui.pointer_coordinate()?).round();
+    (position >= 0.).then_some(position as usize)
+}
@@ -0,0 +1,560 @@
+use std::{collections::HashMap, hash::Hash};
+
+use serde::{Deserialize, Serialize};
+
+use super::i18n::{Language, Message};
+
+/// What the `format` field of a dataset file says, to tell it from any other JSON.
+pub const DATASET_FORMAT: &str = "company_calc.dataset";
+/// Raised whenever a field is added, renamed or changes its meaning. The layout of every version
+/// is described in `schemas/dataset.v{version}.schema.json`.
+pub const DATASET_VERSION: u32 = 1;
+
+/// The whole database as plain records, ids included, so it moves between machines as it is.
+/// Values are kept as they are stored, balances in the currency of the company.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Dataset {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: String,
+    #[serde(default)]
+    pub groups: Vec<GroupRecord>,
+    #[serde(default)]
+    pub accounts: Vec<AccountRecord>,
+    #[serde(default)]
+    pub periods: Vec<PeriodRecord>,
+    #[serde(default)]
+    pub companies: Vec<CompanyRecord>,
+    #[serde(default)]
+    pub balances: Vec<BalanceRecord>,
+    #[serde(default)]
+    pub exchange_rates: Vec<ExchangeRateRecord>,
+    #[serde(default)]
+    pub highlight_rules: Vec<HighlightRuleRecord>,
+    #[serde(default)]
+    pub tags: Vec<TagRecord>,
+    #[serde(default)]
+    pub company_tags: Vec<CompanyTagRecord>,
+    #[serde(default)]
+    pub attachments: Vec<AttachmentRecord>,
+    #[serde(default)]
+    pub period_log: Vec<PeriodLogRecord>,
+    #[serde(default)]
+    pub merge_log: Vec<MergeLogRecord>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GroupRecord {
+    pub id: i64,
+    pub name: String,
+    pub parent_id: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccountRecord {
+    pub id: i64,
+    pub code: String,
+    pub name: String,
+    /// `active`, `passive` or `active_passive`.
+    pub kind: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeriodRecord {
+    pub id: i64,
+    pub year: i64,
+    pub month: i64,
+    pub closed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompanyRecord {
+    pub id: i64,
+    pub name: String,
+    pub tax_id: String,
+    pub legal_address: String,
+    pub contact_person: String,
+    pub phone: String,
+    pub email: String,
+    pub iban: String,
+    pub bic: String,
+    pub contract_number: String,
+    pub note: String,
+    pub group_id: Option<i64>,
+    pub currency: String,
+    pub debit_limit: Option<f64>,
+    pub credit_limit: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BalanceRecord {
+    pub company_id: i64,
+    pub account_id: i64,
+    pub period_id: i64,
+    pub remainder_begin_month: f64,
+    pub debit_turnover: f64,
+    pub credit_turnover: f64,
+    pub remainder_end_month: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExchangeRateRecord {
+    pub currency: String,
+    pub year: i64,
+    pub month: i64,
+    pub rate: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HighlightRuleRecord {
+    pub id: i64,
+    pub field: String,
+    pub comparison: String,
+    pub value: f64,
+    /// `RRGGBB`.
+    pub color: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TagRecord {
+    pub id: i64,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompanyTagRecord {
+    pub company_id: i64,
+    pub tag_id: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttachmentRecord {
+    pub id: i64,
+    pub company_id: i64,
+    pub file_name: String,
+    pub created_at: String,
+    /// Contents of the file in standard base64.
+    #[serde(with = "base64_data")]
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeriodLogRecord {
+    pub id: i64,
+    pub period_id: i64,
+    /// `close` or `reopen`.
+    pub action: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MergeLogRecord {
+    pub id: i64,
+    pub company_id: i64,
+    pub merged_id: i64,
+    pub merged_name: String,
+    pub merged_tax_id: String,
+    pub balances_moved: i64,
+    pub balances_combined: i64,
+    pub created_at: String,
+}
+
+mod base64_data {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(data: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&STANDARD.encode(data))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        STANDARD.decode(text).map_err(D::Error::custom)
+    }
+}
+
+/// How an imported dataset meets what the database already has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DatasetMode {
+    /// Everything in the database is deleted first.
+    Replace,
+    /// Records are added next to the existing ones, the same record found on both sides is
+    /// skipped and a different one under the same id stops the import.
+    #[default]
+    Merge,
+}
+
+/// A record of the file that can't be merged, named by its section and key as in the file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conflict {
+    pub record: String,
+    pub reason: ConflictReason,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConflictReason {
+    /// The database has a different record under the same id.
+    Differs,
+    /// Another record of the database already has this unique value.
+    Duplicate(&'static str),
+    /// The balance would go into a period closed in the database.
+    ClosedPeriod,
+}
+
+impl Conflict {
+    pub fn describe(&self, language: Language) -> String {
+        let record = &self.record;
+        match self.reason {
+            ConflictReason::Differs => {
+                language.trf(Message::ConflictDiffers, &[("record", record)])
+            }
+            ConflictReason::Duplicate(field) => language.trf(
+                Message::ConflictDuplicate,
+                &[("record", record), ("field", &field)],
+            ),
+            ConflictReason::ClosedPeriod => {
+                language.trf(Message::ConflictClosedPeriod, &[("record", record)])
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum DatasetError {
+    Json(serde_json::Error),
+    NotADataset,
+    /// Written by a newer version of the application.
+    UnsupportedVersion(u32),
+    Conflicts(Vec<Conflict>),
+    /// Labels of the closed periods a replace would delete.
+    ClosedPeriods(Vec<String>),
+    /// Names of the groups nested in themselves.
+    GroupCycles(Vec<String>),
+    Database(sqlx::Error),
+}
+
+impl From<sqlx::Error> for DatasetError {
+    fn from(error: sqlx::Error) -> Self {
+        DatasetError::Database(error)
+    }
+}
+
+impl DatasetError {
+    /// One line per problem, every conflict on its own line.
+    pub fn describe(&self, language: Language) -> Vec<String> {
+        match self {
+            DatasetError::Json(error) => vec![error.to_string()],
+            DatasetError::NotADataset => vec![language.tr(Message::NotADataset).to_string()],
+            DatasetError::UnsupportedVersion(version) => vec![language.trf(
+                Message::UnsupportedDatasetVersion,
+                &[("version", version), ("supported", &DATASET_VERSION)],
+            )],
+            DatasetError::Conflicts(conflicts) => {
+                let mut lines =
+                    vec![language.trf(Message::DatasetConflicts, &[("count", &conflicts.len())])];
+                lines.extend(conflicts.iter().map(|conflict| conflict.describe(language)));
+                lines
+            }
+            DatasetError::ClosedPeriods(periods) => vec![language.trf(
+                Message::DatasetClosedPeriods,
+                &[("periods", &periods.join(", "))],
+            )],
+            DatasetError::GroupCycles(groups) => vec![language.trf(
+                Message::DatasetGroupCycles,
+                &[("groups", &groups.join(", "))],
+            )],
+            DatasetError::Database(error) => vec![error.to_string()],
+        }
+    }
+}
+
+/// What an import added.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DatasetCounts {
+    pub companies: usize,
+    pub balances: usize,
+    /// Every other record besides the companies and balances.
+    pub other: usize,
+}
+
+impl Dataset {
+    pub fn to_json(&self) -> Vec<u8> {
+        // the records are plain data, there is nothing serde could fail on
+        serde_json::to_vec_pretty(self).expect("dataset is serializable")
+    }
+
+    /// Checks the format and the version before the records, so a wrong or newer file is told
+    /// as such rather than by the first field that doesn't match.
+    pub fn from_json(contents: &[u8]) -> Result<Self, DatasetError> {
+        #[derive(Deserialize)]
+        struct Header {
+            format: Option<String>,
+            version: Option<u32>,
+        }
+
+        let header: Header = serde_json::from_slice(contents).map_err(DatasetError::Json)?;
+        if header.format.as_deref() != Some(DATASET_FORMAT) {
+            return Err(DatasetError::NotADataset);
+        }
+        match header.version {
+            Some(version) if version > DATASET_VERSION => {
+                return Err(DatasetError::UnsupportedVersion(version))
+            }
+            None => return Err(DatasetError::NotADataset),
+            Some(_) => {}
+        }
+
+        serde_json::from_slice(contents).map_err(DatasetError::Json)
+    }
+
+    pub fn counts(&self) -> DatasetCounts {
+        DatasetCounts {
+            companies: self.companies.len(),
+            balances: self.balances.len(),
+            other: self.groups.len()
+                + self.accounts.len()
+                + self.periods.len()
+                + self.exchange_rates.len()
+                + self.highlight_rules.len()
+                + self.tags.len()
+                + self.company_tags.len()
+                + self.attachments.len()
+                + self.period_log.len()
+                + self.merge_log.len(),
+        }
+    }
+
+    /// The records of `incoming` the database in `self` doesn't have yet, or every conflict
+    /// between the two. Records found on both sides as they are count as already there.
+    pub fn merge_new(&self, incoming: &Dataset) -> Result<Dataset, Vec<Conflict>> {
+        let mut conflicts = Conflicts::default();
+        let id = |id: i64| format!("id={id}");
+        let balance_key = |balance: &BalanceRecord| {
+            format!(
+                "company_id={}, account_id={}, period_id={}",
+                balance.company_id, balance.account_id, balance.period_id
+            )
+        };
+
+        let groups = conflicts.records("groups", &self.groups, &incoming.groups, |group| {
+            id(group.id)
+        });
+        let accounts =
+            conflicts.records("accounts", &self.accounts, &incoming.accounts, |a| id(a.id));
+        let periods = conflicts.records("periods", &self.periods, &incoming.periods, |period| {
+            id(period.id)
+        });
+        let companies = conflicts.records("companies", &self.companies, &incoming.companies, |c| {
+            id(c.id)
+        });
+        let balances =
+            conflicts.records("balances", &self.balances, &incoming.balances, balance_key);
+        let exchange_rates = conflicts.records(
+            "exchange_rates",
+            &self.exchange_rates,
+            &incoming.exchange_rates,
+            |rate| {
+                format!(
+                    "currency={}, year={}, month={}",
+                    rate.currency, rate.year, rate.month
+                )
+            },
+        );
+        let highlight_rules = conflicts.records(
+            "highlight_rules",
+            &self.highlight_rules,
+            &incoming.highlight_rules,
+            |rule| id(rule.id),
+        );
+        let tags = conflicts.records("tags", &self.tags, &incoming.tags, |tag| id(tag.id));
+        let company_tags = conflicts.records(
+            "company_tags",
+            &self.company_tags,
+            &incoming.company_tags,
+            |link| format!("company_id={}, tag_id={}", link.company_id, link.tag_id),
+        );
+        let attachments = conflicts.records(
+            "attachments",
+            &self.attachments,
+            &incoming.attachments,
+            |attachment| id(attachment.id),
+        );
+        let period_log = conflicts.records(
+            "period_log",
+            &self.period_log,
+            &incoming.period_log,
+            |log| id(log.id),
+        );
+        let merge_log =
+            conflicts.records("merge_log", &self.merge_log, &incoming.merge_log, |log| {
+                id(log.id)
+            });
+
+        conflicts.unique("accounts", &self.accounts, &accounts, "code", |account| {
+            (account.id, account.code.clone())
+        });
+        conflicts.unique(
+            "periods",
+            &self.periods,
+            &periods,
+            "year, month",
+            |period| (period.id, (period.year, period.month)),
+        );
+        conflicts.unique("tags", &self.tags, &tags, "name", |tag| {
+            (tag.id, tag.name.to_lowercase())
+        });
+
+        // the database refuses balances of a closed period, a new one can still be closed after
+        let closed: Vec<_> = self
+            .periods
+            .iter()
+            .filter(|period| period.closed)
+            .map(|period| period.id)
+            .collect();
+        for balance in balances
+            .iter()
+            .filter(|balance| closed.contains(&balance.period_id))
+        {
+            conflicts.push(
+                "balances",
+                balance_key(balance),
+                ConflictReason::ClosedPeriod,
+            );
+        }
+
+        if !conflicts.0.is_empty() {
+            return Err(conflicts.0);
+        }
+
+        Ok(Dataset {
+            groups,
+            accounts,
+            periods,
+            companies,
+            balances,
+            exchange_rates,
+            highlight_rules,
+            tags,
+            company_tags,
+            attachments,
+            period_log,
+            merge_log,
+            ..incoming.clone()
+        })
+    }
+}
+
+#[derive(Default)]
+struct Conflicts(Vec<Conflict>);
+
+impl Conflicts {
+    fn push(&mut self, section: &str, key: String, reason: ConflictReason) {
+        self.0.push(Conflict {
+            record: format!("{section}[{key}]"),
+            reason,
+        });
+    }
+
+    /// Incoming records not in the database yet. The ones under a key the database has for a
+    /// different record are conflicts.
+    fn records<T: Clone + PartialEq>(
+        &mut self,
+        section: &str,
+        current: &[T],
+        incoming: &[T],
+        key: impl Fn(&T) -> String,
+    ) -> Vec<T> {
+        let current: HashMap<_, _> = current.iter().map(|record| (key(record), record)).collect();
+
+        let mut new = Vec::new();
+        for record in incoming {
+            match current.get(&key(record)) {
+                Some(&existing) if existing == record => {}
+                Some(_) => self.push(section, key(record), ConflictReason::Differs),
+                None => new.push(record.clone()),
+            }
+        }
+        new
+    }
+
+    /// New records taking a unique value an existing record of another id has.
+    fn unique<T, V: Eq + Hash>(
+        &mut self,
+        section: &str,
+        current: &[T],
+        new: &[T],
+        field: &'static str,
+        value: impl Fn(&T) -> (i64, V),
+    ) {
+        let taken: HashMap<_, _> = current.iter().map(&value).map(|(id, v)| (v, id)).collect();
+        for record in new {
+            let (id, value) = value(record);
+            if taken.get(&value).is_some_and(|&existing| existing != id) {
+                self.push(
+                    section,
+                    format!("id={id}"),
+                    ConflictReason::Duplicate(field),
+                );
+            }
+        }
+    }
+}
+
+/// Asks how a dataset file is to be imported before it is picked.
+#[derive(Default)]
+pub struct DatasetImportDialog {
+    pub open: bool,
+    mode: DatasetMode,
+}
+
+impl DatasetImportDialog {
+    /// Returns the mode once the file is to be picked.
+    pub fn dialog_ui(&mut self, ctx: &egui::Context, language: Language) -> Option<DatasetMode> {
+        if !self.open {
+            return None;
+        }
+        let mut chosen = None;
+        let mut close = false;
+
+        egui::Window::new(language.tr(Message::ImportDataset))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.radio_value(
+                    &mut self.mode,
+                    DatasetMode::Merge,
+                    language.tr(Message::DatasetMerge),
+                );
+                ui.weak(language.tr(Message::DatasetMergeHint));
+                ui.radio_value(
+                    &mut self.mode,
+                    DatasetMode::Replace,
+                    language.tr(Message::DatasetReplace),
+                );
+                if self.mode == DatasetMode::Replace {
+                    ui.colored_label(
+                        ui.visuals().warn_fg_color,
+                        language.tr(Message::DatasetReplaceWarning),
+                    );
+                } else {
+                    ui.weak(language.tr(Message::DatasetReplaceHint));
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(language.tr(Message::ChooseFile)).clicked() {
+                        chosen = Some(self.mode);
+                        close = true;
+                    }
+                    if ui.button(language.tr(Message::Cancel)).clicked() {
+                        close = true;
+                    }
+                });
+            });
+
+        if close {
+            self.open = false;
+        }
+        chosen
+    }
+}
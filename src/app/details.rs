@@ -0,0 +1,330 @@
+use egui::Color32;
+
+use super::{
+    attachments::{attachments_ui, AttachmentAction},
+    format::NumberFormat,
+    groups::group_combo,
+    i18n::{Language, Message},
+    model::{Attachment, Company, CompanyDetails, Group, Tag},
+    tags::tag_chip,
+    validation::{
+        validate_bic, validate_currency, validate_email, validate_iban, validate_phone,
+        validate_tax_id,
+    },
+};
+
+pub enum DetailsAction {
+    Save,
+    Close,
+    Attachment(AttachmentAction),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DetailsTab {
+    Details,
+    Attachments,
+}
+
+pub struct DetailsForm {
+    pub id: i64,
+    pub name: String,
+    details: CompanyDetails,
+    debit_limit: String,
+    credit_limit: String,
+    number_format: NumberFormat,
+    /// Names of the tags, the ones not known yet are created on save.
+    pub tags: Vec<String>,
+    new_tag: String,
+    pub tab: DetailsTab,
+    pub attachments: Vec<Attachment>,
+    /// Set when the attachments are to be fetched again.
+    pub need_attachments: bool,
+}
+
+type Validator = fn(&str) -> Result<(), Message>;
+
+impl DetailsForm {
+    pub fn new(company: &Company, tags: Vec<String>, number_format: &NumberFormat) -> Self {
+        let limit = |limit: Option<f64>| limit.map(|limit| number_format.format_exact(limit));
+
+        Self {
+            id: company.id,
+            name: company.name.clone(),
+            details: company.details(),
+            debit_limit: limit(company.debit_limit).unwrap_or_default(),
+            credit_limit: limit(company.credit_limit).unwrap_or_default(),
+            number_format: number_format.clone(),
+            tags,
+            new_tag: String::new(),
+            tab: DetailsTab::Details,
+            attachments: Vec::new(),
+            need_attachments: true,
+        }
+    }
+
+    /// Details as they are to be saved.
+    pub fn details(&self) -> CompanyDetails {
+        CompanyDetails {
+            debit_limit: parse_limit(&self.debit_limit, &self.number_format)
+                .ok()
+                .flatten(),
+            credit_limit: parse_limit(&self.credit_limit, &self.number_format)
+                .ok()
+                .flatten(),
+            ..self.details.clone()
+        }
+    }
+
+    /// Single line fields besides the tax id, which also shows what kind of number it is.
+    fn fields(&mut self) -> [(Message, &mut String, Option<Validator>); 8] {
+        let CompanyDetails {
+            legal_address,
+            contact_person,
+            phone,
+            email,
+            iban,
+            bic,
+            contract_number,
+            currency,
+            ..
+        } = &mut self.details;
+
+        [
+            (Message::LegalAddress, legal_address, None),
+            (Message::ContactPerson, contact_person, None),
+            (Message::Phone, phone, Some(validate_phone)),
+            (Message::Email, email, Some(validate_email)),
+            (Message::Iban, iban, Some(validate_iban)),
+            (Message::Bic, bic, Some(validate_bic)),
+            (Message::ContractNumber, contract_number, None),
+            (Message::Currency, currency, Some(validate_currency)),
+        ]
+    }
+
+    pub fn is_valid(&mut self) -> bool {
+        let tax_id_valid =
+            self.details.tax_id.is_empty() || validate_tax_id(&self.details.tax_id).is_ok();
+
+        let limits_valid = [&self.debit_limit, &self.credit_limit]
+            .into_iter()
+            .all(|limit| parse_limit(limit, &self.number_format).is_ok());
+
+        tax_id_valid
+            && limits_valid
+            && self
+                .fields()
+                .into_iter()
+                .all(|(_, value, validator)| match validator {
+                    Some(validator) if !value.is_empty() => validator(value).is_ok(),
+                    _ => true,
+                })
+    }
+
+    pub fn details_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        groups: &[Group],
+        tags: &[Tag],
+        language: Language,
+    ) -> Option<DetailsAction> {
+        let mut action = None;
+
+        ui.horizontal(|ui| {
+            ui.heading(&self.name);
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button("✖").clicked() {
+                    action = Some(DetailsAction::Close);
+                }
+            });
+        });
+        ui.label(format!("{}: {}", language.tr(Message::Id), self.id));
+        ui.horizontal(|ui| {
+            ui.selectable_value(
+                &mut self.tab,
+                DetailsTab::Details,
+                language.tr(Message::DetailsTab),
+            );
+            let attachments = match self.attachments.len() {
+                0 => language.tr(Message::Attachments).to_string(),
+                count => format!("{} ({count})", language.tr(Message::Attachments)),
+            };
+            ui.selectable_value(&mut self.tab, DetailsTab::Attachments, attachments);
+        });
+        ui.separator();
+
+        if self.tab == DetailsTab::Attachments {
+            return attachments_ui(ui, &self.attachments, language)
+                .map(DetailsAction::Attachment)
+                .or(action);
+        }
+
+        egui::Grid::new("company_details_grid")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label(language.tr(Message::Group));
+                group_combo(
+                    ui,
+                    "company_details_group",
+                    &mut self.details.group_id,
+                    groups,
+                    None,
+                    language,
+                );
+                ui.end_row();
+
+                ui.label(language.tr(Message::Tags));
+                ui.vertical(|ui| self.tags_ui(ui, tags, language));
+                ui.end_row();
+
+                ui.label(language.tr(Message::TaxId));
+                ui.vertical(|ui| {
+                    ui.text_edit_singleline(&mut self.details.tax_id);
+                    if !self.details.tax_id.is_empty() {
+                        match validate_tax_id(&self.details.tax_id) {
+                            Ok(kind) => ui.weak(language.tr(kind.title())),
+                            Err(error) => ui.colored_label(Color32::RED, language.tr(error)),
+                        };
+                    }
+                });
+                ui.end_row();
+
+                for (title, value, validator) in self.fields() {
+                    ui.label(language.tr(title));
+                    ui.vertical(|ui| {
+                        ui.text_edit_singleline(value);
+                        if let Some(Err(error)) = validator
+                            .filter(|_| !value.is_empty())
+                            .map(|validator| validator(value))
+                        {
+                            ui.colored_label(Color32::RED, language.tr(error));
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+
+        ui.separator();
+        ui.strong(language.tr(Message::CreditLimits));
+        egui::Grid::new("company_limits_grid")
+            .num_columns(2)
+            .show(ui, |ui| {
+                let limits = [
+                    (Message::DebitLimit, &mut self.debit_limit),
+                    (Message::CreditLimit, &mut self.credit_limit),
+                ];
+                for (title, limit) in limits {
+                    ui.label(language.tr(title));
+                    ui.vertical(|ui| {
+                        ui.text_edit_singleline(limit);
+                        if let Err(error) = parse_limit(limit, &self.number_format) {
+                            ui.colored_label(Color32::RED, language.tr(error));
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+        ui.separator();
+
+        ui.label(language.tr(Message::Note));
+        ui.add(
+            egui::TextEdit::multiline(&mut self.details.note)
+                .desired_width(f32::INFINITY)
+                .desired_rows(4),
+        );
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            let valid = self.is_valid();
+            if ui
+                .add_enabled(valid, egui::Button::new(language.tr(Message::Save)))
+                .clicked()
+            {
+                action = Some(DetailsAction::Save);
+            }
+            if ui.button(language.tr(Message::Cancel)).clicked() {
+                action = Some(DetailsAction::Close);
+            }
+        });
+
+        action
+    }
+}
+
+impl DetailsForm {
+    /// The tags as chips to remove, a field for a new one and the other known tags to pick.
+    fn tags_ui(&mut self, ui: &mut egui::Ui, tags: &[Tag], language: Language) {
+        let known = |name: &str| {
+            tags.iter()
+                .find(|tag| tag.name.to_lowercase() == name.to_lowercase())
+                .cloned()
+        };
+
+        ui.horizontal_wrapped(|ui| {
+            let mut removed = None;
+            for (i, name) in self.tags.iter().enumerate() {
+                let tag = known(name).unwrap_or(Tag {
+                    id: 0,
+                    name: name.clone(),
+                });
+                tag_chip(ui, &tag);
+                if ui.small_button("✖").clicked() {
+                    removed = Some(i);
+                }
+            }
+            if let Some(i) = removed {
+                self.tags.remove(i);
+            }
+        });
+
+        ui.horizontal(|ui| {
+            let field = ui.add(
+                egui::TextEdit::singleline(&mut self.new_tag)
+                    .hint_text(language.tr(Message::NewTag))
+                    .desired_width(120.0),
+            );
+            let entered =
+                field.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter));
+            if (ui.button(language.tr(Message::AddTag)).clicked() || entered)
+                && !self.new_tag.trim().is_empty()
+            {
+                let name = self.new_tag.trim().to_string();
+                self.add_tag(known(&name).map_or(name, |tag| tag.name));
+                self.new_tag.clear();
+            }
+        });
+
+        ui.horizontal_wrapped(|ui| {
+            for tag in tags {
+                if !self.has_tag(&tag.name) && ui.small_button(format!("+ {}", tag.name)).clicked()
+                {
+                    self.add_tag(tag.name.clone());
+                }
+            }
+        });
+    }
+
+    fn has_tag(&self, name: &str) -> bool {
+        self.tags
+            .iter()
+            .any(|tag| tag.to_lowercase() == name.to_lowercase())
+    }
+
+    fn add_tag(&mut self, name: String) {
+        if !self.has_tag(&name) {
+            self.tags.push(name);
+        }
+    }
+}
+
+/// An empty limit is no limit.
+fn parse_limit(limit: &str, number_format: &NumberFormat) -> Result<Option<f64>, Message> {
+    if limit.trim().is_empty() {
+        return Ok(None);
+    }
+
+    match number_format.parse(limit) {
+        Ok(limit) if limit >= 0. => Ok(Some(limit)),
+        _ => Err(Message::NotANumber),
+    }
+}
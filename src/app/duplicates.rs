@@ -0,0 +1,207 @@
+use super::{
+    i18n::{Language, Message},
+    model::Company,
+};
+
+/// Legal forms, written before or after the name, that don't tell counterparties apart.
+const LEGAL_FORMS: [&str; 16] = [
+    "ооо", "оао", "зао", "пао", "ао", "ип", "тоо", "ано", "нко", "mmc", "asc", "qsc", "llc", "ltd",
+    "inc", "jsc",
+];
+
+/// The words of the name without quotes, punctuation and the legal form, in alphabetical order,
+/// so "ООО «Ромашка»" and "Ромашка ООО" come out the same.
+pub fn normalize_name(name: &str) -> String {
+    let name: String = name
+        .to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'ё' => 'е',
+            c if c.is_alphanumeric() => c,
+            _ => ' ',
+        })
+        .collect();
+
+    let mut words: Vec<_> = name
+        .split_whitespace()
+        .filter(|word| !LEGAL_FORMS.contains(word))
+        .collect();
+    words.sort_unstable();
+    words.join(" ")
+}
+
+/// Two counterparties that look like the same one, the older first.
+pub struct DuplicatePair {
+    pub first: (i64, String),
+    pub second: (i64, String),
+    pub same_name: bool,
+    pub same_tax_id: bool,
+}
+
+/// Pairs with the same tax ID or the same normalized name. Different tax IDs tell companies
+/// with the same name apart, so those aren't suggested, and neither are companies kept in
+/// different currencies, whose balances can't be added up.
+pub fn find_duplicates(companies: &[&Company]) -> Vec<DuplicatePair> {
+    let mut companies: Vec<_> = companies
+        .iter()
+        .map(|company| {
+            (
+                company,
+                normalize_name(&company.name),
+                company.tax_id.trim(),
+            )
+        })
+        .collect();
+    companies.sort_by_key(|(company, _, _)| company.id);
+
+    let mut pairs = Vec::new();
+    for (i, (first, first_name, first_tax_id)) in companies.iter().enumerate() {
+        for (second, second_name, second_tax_id) in &companies[i + 1..] {
+            let both_tax_ids = !first_tax_id.is_empty() && !second_tax_id.is_empty();
+            let same_tax_id = both_tax_ids && first_tax_id == second_tax_id;
+            let same_name = !first_name.is_empty() && first_name == second_name;
+
+            if !same_currency(&first.currency, &second.currency) {
+                continue;
+            }
+            if same_tax_id || (same_name && !both_tax_ids) {
+                pairs.push(DuplicatePair {
+                    first: (first.id, first.name.clone()),
+                    second: (second.id, second.name.clone()),
+                    same_name,
+                    same_tax_id,
+                });
+            }
+        }
+    }
+
+    pairs
+}
+
+/// The duplicate goes into the company that is kept.
+pub struct MergeRequest {
+    pub keep_id: i64,
+    pub merged_id: i64,
+}
+
+/// An empty currency is the reporting one and goes with any other.
+pub fn same_currency(first: &str, second: &str) -> bool {
+    first.is_empty() || second.is_empty() || first == second
+}
+
+#[derive(Debug)]
+pub enum MergeError {
+    /// The companies keep their balances in different currencies.
+    Currencies {
+        kept: String,
+        merged: String,
+    },
+    Database(sqlx::Error),
+}
+
+impl From<sqlx::Error> for MergeError {
+    fn from(error: sqlx::Error) -> Self {
+        MergeError::Database(error)
+    }
+}
+
+impl MergeError {
+    pub fn describe(&self, language: Language) -> String {
+        match self {
+            MergeError::Currencies { kept, merged } => language.trf(
+                Message::MergeCurrenciesDiffer,
+                &[("kept", kept), ("merged", merged)],
+            ),
+            MergeError::Database(error) => error.to_string(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct DuplicatesWindow {
+    pub open: bool,
+    /// The merge waiting to be confirmed, with the names of the kept company and the duplicate.
+    confirm: Option<(MergeRequest, String, String)>,
+}
+
+impl DuplicatesWindow {
+    pub fn window_ui(
+        &mut self,
+        ctx: &egui::Context,
+        pairs: &[DuplicatePair],
+        language: Language,
+    ) -> Option<MergeRequest> {
+        let mut merge = None;
+        let mut open = self.open;
+
+        egui::Window::new(language.tr(Message::Duplicates))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                if let Some((_, kept, merged)) = &self.confirm {
+                    ui.label(
+                        language.trf(Message::MergeConfirm, &[("merged", merged), ("kept", kept)]),
+                    );
+                    ui.horizontal(|ui| {
+                        if ui.button(language.tr(Message::Merge)).clicked() {
+                            merge = self.confirm.take().map(|(request, _, _)| request);
+                        }
+                        if ui.button(language.tr(Message::Cancel)).clicked() {
+                            self.confirm = None;
+                        }
+                    });
+                    return;
+                }
+
+                if pairs.is_empty() {
+                    ui.label(language.tr(Message::NoDuplicates));
+                    return;
+                }
+
+                egui::ScrollArea::vertical()
+                    .max_height(400.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("duplicates_grid")
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for pair in pairs {
+                                    ui.label(&pair.first.1);
+                                    ui.label(&pair.second.1);
+
+                                    let reason = match (pair.same_name, pair.same_tax_id) {
+                                        (true, true) => format!(
+                                            "{}, {}",
+                                            language.tr(Message::SameName),
+                                            language.tr(Message::SameTaxId)
+                                        ),
+                                        (false, true) => language.tr(Message::SameTaxId).into(),
+                                        _ => language.tr(Message::SameName).into(),
+                                    };
+                                    ui.weak(reason);
+
+                                    for (keep, merged) in
+                                        [(&pair.first, &pair.second), (&pair.second, &pair.first)]
+                                    {
+                                        let text =
+                                            language.trf(Message::MergeInto, &[("name", &keep.1)]);
+                                        if ui.button(text).clicked() {
+                                            self.confirm = Some((
+                                                MergeRequest {
+                                                    keep_id: keep.0,
+                                                    merged_id: merged.0,
+                                                },
+                                                keep.1.clone(),
+                                                merged.1.clone(),
+                                            ));
+                                        }
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            });
+
+        self.open = open;
+        merge
+    }
+}
@@ -1,36 +1,52 @@
-use rust_xlsxwriter::{Format, FormatBorder, Workbook, XlsxError};
-use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 
-use super::model::Company;
-pub fn export_to_pdf() {
-    todo!()
-}
+use rust_xlsxwriter::{
+    utility::cell_range, ColNum, Format, FormatAlign, FormatBorder, Formula, RowNum, Workbook,
+    Worksheet, XlsxError,
+};
+
+use super::{
+    format::NumberFormat,
+    groups::{group_items, GroupedItem},
+    highlight::matching_rule,
+    i18n::{Language, Message},
+    model::{Company, Group, HighlightRule, Period},
+    TotalRow,
+};
+
+mod aging;
+mod html;
+mod ods;
+mod pdf;
+mod reconciliation;
+mod workbook;
+
+pub use aging::aging_to_excel;
+pub use html::export_to_html;
+pub use ods::export_to_ods;
+pub use pdf::export_to_pdf;
+pub use reconciliation::{reconciliation_to_excel, reconciliation_to_pdf, Reconciliation};
+pub use workbook::{periods_to_excel, PeriodSheet, WorkbookCover};
 
-#[derive(Deserialize, Serialize)]
 pub struct CompanyExcel {
-    #[serde(rename = "Код")]
     id: i64,
 
-    #[serde(rename = "Наименование")]
     name: String,
 
-    #[serde(rename = "Начало-Дебет")]
     remainder_begin_month_debit: Option<f64>,
 
-    #[serde(rename = "Начало-Кредит")]
     remainder_begin_month_credit: Option<f64>,
 
-    #[serde(rename = "Оборот-Дебет")]
     debit_turnover: f64,
 
-    #[serde(rename = "Оборот-Кредит")]
     credit_turnover: f64,
 
-    #[serde(rename = "Конец-Дебет")]
     remainder_end_month_debit: Option<f64>,
 
-    #[serde(rename = "Конец-Кредит")]
     remainder_end_month_credit: Option<f64>,
+
+    /// Background of the row, `RRGGBB`.
+    highlight: Option<String>,
 }
 
 pub fn map_to_excel(company: &Company) -> CompanyExcel {
@@ -43,7 +59,7 @@ pub fn map_to_excel(company: &Company) -> CompanyExcel {
             None
         },
         remainder_begin_month_credit: if company.remainder_begin_month < 0. {
-            Some(company.remainder_end_month)
+            Some(-company.remainder_begin_month)
         } else {
             None
         },
@@ -55,27 +71,346 @@ pub fn map_to_excel(company: &Company) -> CompanyExcel {
             None
         },
         remainder_end_month_credit: if company.remainder_end_month < 0. {
-            Some(company.remainder_end_month)
+            Some(-company.remainder_end_month)
         } else {
             None
         },
+        highlight: None,
     }
 }
 
-pub fn export_to_excel(rows: &[CompanyExcel]) -> Result<Vec<u8>, XlsxError> {
+/// Rows of an exported sheet in the order they are written, with the same group headers and
+/// subtotals as `CompanyTable`. Pass no groups to get a flat list.
+pub enum SheetRow {
+    Group {
+        name: String,
+        depth: usize,
+    },
+    Company(CompanyExcel),
+    Subtotal {
+        name: String,
+        depth: usize,
+        total: TotalRow,
+    },
+    Total(TotalRow),
+}
+
+/// Companies matching a highlight rule get its color.
+pub fn build_sheet(
+    companies: &[&Company],
+    groups: &[Group],
+    rules: &[HighlightRule],
+    language: Language,
+) -> Vec<SheetRow> {
+    let items: Vec<_> = companies
+        .iter()
+        .map(|&company| (company, company))
+        .collect();
+
+    let mut rows: Vec<_> = group_items(&items, groups, &HashSet::new(), language)
+        .into_iter()
+        .map(|item| match item {
+            GroupedItem::Header { name, depth, .. } => SheetRow::Group { name, depth },
+            GroupedItem::Item(company) => SheetRow::Company(CompanyExcel {
+                highlight: matching_rule(rules, company).map(HighlightRule::hex),
+                ..map_to_excel(company)
+            }),
+            GroupedItem::Subtotal { name, depth, total } => {
+                SheetRow::Subtotal { name, depth, total }
+            }
+        })
+        .collect();
+
+    rows.push(SheetRow::Total(companies.iter().copied().collect()));
+    rows
+}
+
+fn total_values(total: &TotalRow) -> [f64; 6] {
+    [
+        total.remainder_begin_month_pos,
+        total.remainder_begin_month_neg,
+        total.debit_turnover,
+        total.credit_turnover,
+        total.remainder_end_month_pos,
+        total.remainder_end_month_neg,
+    ]
+}
+
+impl CompanyExcel {
+    fn values(&self) -> [Option<f64>; 6] {
+        [
+            self.remainder_begin_month_debit,
+            self.remainder_begin_month_credit,
+            Some(self.debit_turnover),
+            Some(self.credit_turnover),
+            self.remainder_end_month_debit,
+            self.remainder_end_month_credit,
+        ]
+    }
+}
+
+/// Text for XML and HTML, in content and in quoted attributes.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// What the title block above an exported sheet says.
+pub struct SheetTitle {
+    pub organization: String,
+    pub period: Option<Period>,
+}
+
+impl SheetTitle {
+    fn lines(&self, language: Language) -> [String; 2] {
+        let title = match &self.period {
+            Some(period) => language.trf(
+                Message::TurnoverSheetFor,
+                &[("from", &period.first_day()), ("to", &period.last_day())],
+            ),
+            None => language.tr(Message::TurnoverSheet).to_string(),
+        };
+
+        [self.organization.clone(), title]
+    }
+}
+
+/// Rows of the title block and the blank row under it.
+const TITLE_ROWS: RowNum = 3;
+/// Rows of the header: the titles of the balances and their debit and credit below.
+const HEADER_ROWS: RowNum = 2;
+const FIRST_VALUE_COL: ColNum = 2;
+
+pub fn export_to_excel(
+    rows: &[SheetRow],
+    title: &SheetTitle,
+    number_format: &NumberFormat,
+    language: Language,
+) -> Result<Vec<u8>, XlsxError> {
     let mut workbook = Workbook::new();
-    // Add a worksheet to the workbook.
     let worksheet = workbook.add_worksheet();
+    write_sheet(worksheet, rows, title, number_format, language)?;
+
+    workbook.save_to_buffer()
+}
+
+/// Rows of companies as runs of consecutive rows, what a total adds up. Subtotals are left out
+/// so nested groups aren't counted twice.
+#[derive(Default)]
+struct CompanyRuns(Vec<(RowNum, RowNum)>);
 
-    // Add some formats to use with the serialization data.
+impl CompanyRuns {
+    fn push(&mut self, row: RowNum) {
+        match self.0.last_mut() {
+            Some((_, last)) if *last + 1 == row => *last = row,
+            _ => self.0.push((row, row)),
+        }
+    }
+
+    /// `SUM` over the column with the value it comes to, for viewers that don't recalculate.
+    fn sum(&self, col: ColNum, value: f64) -> Formula {
+        let ranges: Vec<_> = self
+            .0
+            .iter()
+            .map(|(first, last)| cell_range(*first, col, *last, col))
+            .collect();
+
+        Formula::new(format!("=SUM({})", ranges.join(","))).set_result(value.to_string())
+    }
+}
+
+/// The sheet as it is on screen: a title block, a frozen two-level header, the companies with
+/// their groups outlined and subtotals and totals that stay live formulas.
+pub fn write_sheet(
+    worksheet: &mut Worksheet,
+    rows: &[SheetRow],
+    title: &SheetTitle,
+    number_format: &NumberFormat,
+    language: Language,
+) -> Result<(), XlsxError> {
+    let title_format = Format::new().set_bold().set_font_size(14);
     let header_format = Format::new()
         .set_bold()
         .set_border(FormatBorder::Thin)
+        .set_align(FormatAlign::Center)
+        .set_align(FormatAlign::VerticalCenter)
+        .set_text_wrap()
         .set_background_color("C6E0B4");
+    let cell_format = Format::new().set_border(FormatBorder::Thin);
+    let number_cell_format = cell_format
+        .clone()
+        .set_num_format(number_format.spreadsheet_format());
+    let bold_format = cell_format.clone().set_bold();
+    let bold_number_format = number_cell_format.clone().set_bold();
 
-    worksheet.deserialize_headers_with_format::<CompanyExcel>(0, 0, &header_format)?;
+    let header = TITLE_ROWS;
+    worksheet.merge_range(
+        header,
+        0,
+        header + 1,
+        0,
+        language.tr(Message::Id),
+        &header_format,
+    )?;
+    worksheet.merge_range(
+        header,
+        1,
+        header + 1,
+        1,
+        language.tr(Message::Name),
+        &header_format,
+    )?;
+    for col in 0..6 {
+        let side = if col % 2 == 0 {
+            Message::Debit
+        } else {
+            Message::Credit
+        };
+        worksheet.write_with_format(
+            header + 1,
+            FIRST_VALUE_COL + col,
+            language.tr(side),
+            &header_format,
+        )?;
+    }
 
-    worksheet.serialize(&rows)?;
+    // first row of every group that is still open, the rows up to its subtotal get outlined,
+    // and the companies its subtotal adds up
+    let mut open_groups: Vec<(RowNum, CompanyRuns)> = Vec::new();
+    let mut all_companies = CompanyRuns::default();
+    let first_row = header + HEADER_ROWS;
 
-    workbook.save_to_buffer()
+    for (i, row) in rows.iter().enumerate() {
+        let row_num = first_row + i as RowNum;
+
+        match row {
+            SheetRow::Group { name, depth } => {
+                let format = bold_format.clone().set_indent(*depth as u8);
+                worksheet.write_blank(row_num, 0, &cell_format)?;
+                worksheet.write_with_format(row_num, 1, name, &format)?;
+                for col in 0..6 {
+                    worksheet.write_blank(row_num, FIRST_VALUE_COL + col, &cell_format)?;
+                }
+                open_groups.push((row_num + 1, CompanyRuns::default()));
+            }
+            SheetRow::Company(company) => {
+                let (text_format, number_format) = match &company.highlight {
+                    Some(color) => (
+                        cell_format.clone().set_background_color(color.as_str()),
+                        number_cell_format
+                            .clone()
+                            .set_background_color(color.as_str()),
+                    ),
+                    None => (cell_format.clone(), number_cell_format.clone()),
+                };
+
+                worksheet.write_with_format(row_num, 0, company.id, &text_format)?;
+                worksheet.write_with_format(row_num, 1, &company.name, &text_format)?;
+                write_values(worksheet, row_num, &company.values(), &number_format)?;
+
+                all_companies.push(row_num);
+                for (_, companies) in &mut open_groups {
+                    companies.push(row_num);
+                }
+            }
+            SheetRow::Subtotal { name, depth, total } => {
+                let label = language.trf(Message::SubtotalFor, &[("group", name)]);
+                let format = bold_format.clone().set_indent(*depth as u8);
+                worksheet.write_blank(row_num, 0, &bold_format)?;
+                worksheet.write_with_format(row_num, 1, label, &format)?;
+
+                let (first, companies) = open_groups.pop().unwrap_or_default();
+                write_sums(worksheet, row_num, &companies, total, &bold_number_format)?;
+                if first > 0 && first < row_num {
+                    worksheet.group_rows(first, row_num - 1)?;
+                }
+            }
+            SheetRow::Total(total) => {
+                worksheet.write_blank(row_num, 0, &bold_format)?;
+                worksheet.write_with_format(
+                    row_num,
+                    1,
+                    language.tr(Message::Total),
+                    &bold_format,
+                )?;
+                write_sums(
+                    worksheet,
+                    row_num,
+                    &all_companies,
+                    total,
+                    &bold_number_format,
+                )?;
+            }
+        }
+    }
+
+    // the widths come from the cells written so far, the title and the merged balance titles
+    // are written after so their length doesn't widen the first columns
+    worksheet.autofit();
+
+    let titles = [
+        Message::RemainderBeginMonth,
+        Message::Turnover,
+        Message::RemainderEndMonth,
+    ];
+    for (i, title) in titles.into_iter().enumerate() {
+        let col = FIRST_VALUE_COL + i as ColNum * 2;
+        worksheet.merge_range(
+            header,
+            col,
+            header,
+            col + 1,
+            language.tr(title),
+            &header_format,
+        )?;
+    }
+    worksheet.set_row_height(header, 30)?;
+
+    for (row, line) in title.lines(language).into_iter().enumerate() {
+        worksheet.write_with_format(row as RowNum, 0, line, &title_format)?;
+    }
+
+    worksheet.set_freeze_panes(first_row, FIRST_VALUE_COL)?;
+    worksheet.set_repeat_rows(header, header + 1)?;
+
+    Ok(())
+}
+
+/// Empty sides are still written as cells of the table, blank.
+fn write_values(
+    worksheet: &mut Worksheet,
+    row: RowNum,
+    values: &[Option<f64>; 6],
+    format: &Format,
+) -> Result<(), XlsxError> {
+    for (col, value) in values.iter().enumerate() {
+        let col = FIRST_VALUE_COL + col as ColNum;
+        match value {
+            Some(value) => worksheet.write_number_with_format(row, col, *value, format)?,
+            None => worksheet.write_blank(row, col, format)?,
+        };
+    }
+
+    Ok(())
+}
+
+fn write_sums(
+    worksheet: &mut Worksheet,
+    row: RowNum,
+    companies: &CompanyRuns,
+    total: &TotalRow,
+    format: &Format,
+) -> Result<(), XlsxError> {
+    for (col, value) in total_values(total).into_iter().enumerate() {
+        let col = FIRST_VALUE_COL + col as ColNum;
+        if companies.0.is_empty() {
+            worksheet.write_number_with_format(row, col, value, format)?;
+        } else {
+            worksheet.write_formula_with_format(row, col, companies.sum(col, value), format)?;
+        }
+    }
+
+    Ok(())
 }
@@ -0,0 +1,92 @@
+use rust_xlsxwriter::{Format, FormatAlign, FormatBorder, Workbook, Worksheet, XlsxError};
+
+use crate::app::{
+    aging::{aging_total, AgingRow, BUCKETS},
+    format::NumberFormat,
+    i18n::{Language, Message},
+};
+
+pub fn aging_to_excel(
+    rows: &[AgingRow],
+    date: &str,
+    number_format: &NumberFormat,
+    language: Language,
+) -> Result<Vec<u8>, XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    let title_format = Format::new().set_bold();
+    let header_format = Format::new()
+        .set_bold()
+        .set_border(FormatBorder::Thin)
+        .set_align(FormatAlign::Center)
+        .set_text_wrap();
+    let cell_format = Format::new().set_border(FormatBorder::Thin);
+    let number_cell_format = cell_format
+        .clone()
+        .set_num_format(number_format.spreadsheet_format());
+    let total_format = cell_format.clone().set_bold();
+    let number_total_format = number_cell_format.clone().set_bold();
+
+    worksheet.set_column_width(0, 8)?;
+    worksheet.set_column_width(1, 40)?;
+    worksheet.set_column_range_width(2, 11, 14)?;
+
+    worksheet.write_with_format(
+        0,
+        0,
+        language.trf(Message::AgingAsOf, &[("date", &date)]),
+        &title_format,
+    )?;
+
+    worksheet.merge_range(2, 0, 3, 0, language.tr(Message::Id), &header_format)?;
+    worksheet.merge_range(2, 1, 3, 1, language.tr(Message::Name), &header_format)?;
+    for (i, side) in [Message::Receivables, Message::Payables]
+        .into_iter()
+        .enumerate()
+    {
+        let col = 2 + i as u16 * 5;
+        worksheet.merge_range(2, col, 2, col + 4, language.tr(side), &header_format)?;
+
+        let titles = BUCKETS.into_iter().chain([Message::Total]);
+        for (offset, title) in titles.enumerate() {
+            worksheet.write_with_format(
+                3,
+                col + offset as u16,
+                language.tr(title),
+                &header_format,
+            )?;
+        }
+    }
+
+    let mut row = 4;
+    for aging in rows {
+        worksheet.write_number_with_format(row, 0, aging.id as f64, &cell_format)?;
+        worksheet.write_with_format(row, 1, &aging.name, &cell_format)?;
+        write_sides(worksheet, row, aging, &number_cell_format)?;
+        row += 1;
+    }
+
+    worksheet.write_blank(row, 0, &total_format)?;
+    worksheet.write_with_format(row, 1, language.tr(Message::Total), &total_format)?;
+    write_sides(worksheet, row, &aging_total(rows), &number_total_format)?;
+
+    workbook.save_to_buffer()
+}
+
+fn write_sides(
+    worksheet: &mut Worksheet,
+    row: u32,
+    aging: &AgingRow,
+    format: &Format,
+) -> Result<(), XlsxError> {
+    let values = AgingRow::values(&aging.receivables)
+        .into_iter()
+        .chain(AgingRow::values(&aging.payables));
+
+    for (col, value) in values.enumerate() {
+        worksheet.write_number_with_format(row, 2 + col as u16, value, format)?;
+    }
+
+    Ok(())
+}
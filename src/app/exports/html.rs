@@ -0,0 +1,156 @@
+use std::fmt::Write as _;
+
+use super::{escape, total_values, SheetRow, SheetTitle};
+use crate::app::{
+    format::NumberFormat,
+    i18n::{Language, Message},
+};
+
+/// Screen styles first, then what changes on paper: landscape A4, the header on every page,
+/// no rows split across pages and the highlight colors kept.
+const STYLE: &str = r#"
+body { font-family: "Segoe UI", Roboto, "Helvetica Neue", Arial, sans-serif; font-size: 13px; margin: 24px; color: #222; }
+h1 { font-size: 18px; margin: 0 0 4px; }
+h2 { font-size: 14px; font-weight: normal; margin: 0 0 16px; color: #555; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #999; padding: 3px 6px; }
+th { background: #c6e0b4; text-align: center; vertical-align: middle; }
+td.number { text-align: right; white-space: nowrap; font-variant-numeric: tabular-nums; }
+thead th { position: sticky; top: 0; }
+thead tr:nth-child(2) th { top: 24px; }
+tr.group td { font-weight: bold; background: #f2f2f2; }
+tr.subtotal td, tr.total td { font-weight: bold; }
+tr.total td { border-top: 2px solid #222; }
+@media print {
+  @page { size: A4 landscape; margin: 10mm; }
+  body { margin: 0; font-size: 9pt; }
+  thead { display: table-header-group; }
+  thead th { position: static; }
+  tr { page-break-inside: avoid; break-inside: avoid; }
+  th, td, tr.group td { -webkit-print-color-adjust: exact; print-color-adjust: exact; }
+}
+"#;
+
+/// Pixels a level of groups is indented by.
+const INDENT: usize = 16;
+
+/// The sheet as one self-contained page with the header of `CompanyTable`, to share by link or
+/// print from a browser.
+pub fn export_to_html(
+    rows: &[SheetRow],
+    title: &SheetTitle,
+    number_format: &NumberFormat,
+    language: Language,
+) -> String {
+    let [organization, heading] = title.lines(language);
+    let mut html = String::new();
+
+    _ = write!(
+        html,
+        r#"<!DOCTYPE html>
+<html lang="{}">
+<head>
+<meta charset="utf-8">
+<title>{} – {}</title>
+<style>{STYLE}</style>
+</head>
+<body>
+<h1>{}</h1>
+<h2>{}</h2>
+<table>
+<thead>
+"#,
+        language.code(),
+        escape(&organization),
+        escape(&heading),
+        escape(&organization),
+        escape(&heading),
+    );
+
+    _ = write!(
+        html,
+        r#"<tr><th rowspan="2">{}</th><th rowspan="2">{}</th>"#,
+        escape(language.tr(Message::Id)),
+        escape(language.tr(Message::Name)),
+    );
+    for title in [
+        Message::RemainderBeginMonth,
+        Message::Turnover,
+        Message::RemainderEndMonth,
+    ] {
+        _ = write!(
+            html,
+            r#"<th colspan="2">{}</th>"#,
+            escape(language.tr(title))
+        );
+    }
+    html.push_str("</tr>\n<tr>");
+    for side in [Message::Debit, Message::Credit].repeat(3) {
+        _ = write!(html, "<th>{}</th>", escape(language.tr(side)));
+    }
+    html.push_str("</tr>\n</thead>\n<tbody>\n");
+
+    let amount = |value: Option<f64>| value.map(|value| number_format.format(value));
+
+    for row in rows {
+        match row {
+            SheetRow::Group { name, depth } => {
+                _ = writeln!(
+                    html,
+                    r#"<tr class="group"><td></td><td colspan="7" style="padding-left: {}px">{}</td></tr>"#,
+                    6 + depth * INDENT,
+                    escape(name)
+                );
+            }
+            SheetRow::Company(company) => {
+                match &company.highlight {
+                    Some(color) => {
+                        _ = write!(html, r##"<tr style="background: #{}">"##, escape(color))
+                    }
+                    None => html.push_str("<tr>"),
+                }
+                _ = write!(
+                    html,
+                    r#"<td class="number">{}</td><td>{}</td>"#,
+                    company.id,
+                    escape(&company.name)
+                );
+                write_values(&mut html, company.values().map(amount));
+                html.push_str("</tr>\n");
+            }
+            SheetRow::Subtotal { name, depth, total } => {
+                let label = language.trf(Message::SubtotalFor, &[("group", name)]);
+                _ = write!(
+                    html,
+                    r#"<tr class="subtotal"><td></td><td style="padding-left: {}px">{}</td>"#,
+                    6 + depth * INDENT,
+                    escape(&label)
+                );
+                write_values(&mut html, total_values(total).map(Some).map(amount));
+                html.push_str("</tr>\n");
+            }
+            SheetRow::Total(total) => {
+                _ = write!(
+                    html,
+                    r#"<tr class="total"><td></td><td>{}</td>"#,
+                    escape(language.tr(Message::Total))
+                );
+                write_values(&mut html, total_values(total).map(Some).map(amount));
+                html.push_str("</tr>\n");
+            }
+        }
+    }
+
+    html.push_str("</tbody>\n</table>\n</body>\n</html>\n");
+    html
+}
+
+fn write_values(html: &mut String, values: [Option<String>; 6]) {
+    for value in values {
+        _ = write!(
+            html,
+            r#"<td class="number">{}</td>"#,
+            escape(&value.unwrap_or_default())
+        );
+    }
+}
@@ -0,0 +1,453 @@
+use std::{
+    collections::BTreeSet,
+    fmt::Write as _,
+    io::{Cursor, Write},
+};
+
+use rust_xlsxwriter::{utility::column_number_to_name, ColNum, RowNum};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+use super::{
+    escape, total_values, CompanyRuns, SheetRow, SheetTitle, FIRST_VALUE_COL, HEADER_ROWS,
+    TITLE_ROWS,
+};
+use crate::app::{
+    format::NumberFormat,
+    i18n::{Language, Message},
+    TotalRow,
+};
+
+const MIMETYPE: &str = "application/vnd.oasis.opendocument.spreadsheet";
+
+const MANIFEST: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<manifest:manifest xmlns:manifest="urn:oasis:names:tc:opendocument:xmlns:manifest:1.0" manifest:version="1.2">
+ <manifest:file-entry manifest:full-path="/" manifest:media-type="application/vnd.oasis.opendocument.spreadsheet"/>
+ <manifest:file-entry manifest:full-path="content.xml" manifest:media-type="text/xml"/>
+ <manifest:file-entry manifest:full-path="settings.xml" manifest:media-type="text/xml"/>
+</manifest:manifest>"#;
+
+const NAMESPACES: &str = r#"xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:style="urn:oasis:names:tc:opendocument:xmlns:style:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0" xmlns:fo="urn:oasis:names:tc:opendocument:xmlns:xsl-fo-compatible:1.0" xmlns:number="urn:oasis:names:tc:opendocument:xmlns:datastyle:1.0" xmlns:of="urn:oasis:names:tc:opendocument:xmlns:of:1.2" xmlns:config="urn:oasis:names:tc:opendocument:xmlns:config:1.0" office:version="1.2""#;
+
+const SHEET_NAME: &str = "Sheet1";
+const BORDER: &str = r#"fo:border="0.5pt solid #000000""#;
+/// Rough width of a character of the default font, to size the columns to their contents.
+const CHAR_WIDTH_CM: f32 = 0.21;
+
+/// The same sheet as [`super::export_to_excel`] as an OpenDocument spreadsheet, for LibreOffice.
+pub fn export_to_ods(
+    rows: &[SheetRow],
+    title: &SheetTitle,
+    number_format: &NumberFormat,
+    language: Language,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+
+    // the mimetype goes first and uncompressed, that is how the format is recognized
+    let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+    zip.start_file("mimetype", stored)?;
+    zip.write_all(MIMETYPE.as_bytes())?;
+
+    let deflated = SimpleFileOptions::default();
+    zip.start_file("META-INF/manifest.xml", deflated)?;
+    zip.write_all(MANIFEST.as_bytes())?;
+    zip.start_file("content.xml", deflated)?;
+    zip.write_all(content(rows, title, number_format, language).as_bytes())?;
+    zip.start_file("settings.xml", deflated)?;
+    zip.write_all(settings().as_bytes())?;
+
+    Ok(zip.finish()?.into_inner())
+}
+
+/// Freezes the title block, the header and the code and name columns, like the xlsx.
+fn settings() -> String {
+    let first_row = TITLE_ROWS + HEADER_ROWS;
+    let item = |name: &str, kind: &str, value: u32| {
+        format!(
+            r#"<config:config-item config:name="{name}" config:type="{kind}">{value}</config:config-item>"#
+        )
+    };
+
+    let items = [
+        item("HorizontalSplitMode", "short", 2),
+        item("VerticalSplitMode", "short", 2),
+        item("HorizontalSplitPosition", "int", FIRST_VALUE_COL.into()),
+        item("VerticalSplitPosition", "int", first_row),
+        item("ActiveSplitRange", "short", 2),
+        item("PositionLeft", "int", 0),
+        item("PositionRight", "int", FIRST_VALUE_COL.into()),
+        item("PositionTop", "int", 0),
+        item("PositionBottom", "int", first_row),
+    ]
+    .concat();
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?><office:document-settings {NAMESPACES}><office:settings><config:config-item-set config:name="ooo:view-settings"><config:config-item-map-indexed config:name="Views"><config:config-item-map-entry><config:config-item config:name="ViewId" config:type="string">view1</config:config-item><config:config-item-map-named config:name="Tables"><config:config-item-map-entry config:name="{SHEET_NAME}">{items}</config:config-item-map-entry></config:config-item-map-named></config:config-item-map-entry></config:config-item-map-indexed></config:config-item-set></office:settings></office:document-settings>"#
+    )
+}
+
+/// Cell styles by their use, the highlight colors and indents get a style each.
+fn styles(rows: &[SheetRow], number_format: &NumberFormat) -> String {
+    let mut styles = String::new();
+
+    let grouping = number_format.group_separator.is_some();
+    let places = number_format.decimal_places;
+    _ = write!(
+        styles,
+        r#"<number:number-style style:name="N0"><number:number number:decimal-places="{places}" number:min-decimal-places="{places}" number:min-integer-digits="1" number:grouping="{grouping}"/></number:number-style>"#
+    );
+
+    let mut cell_style = |name: &str, number: bool, cell: &str, text: &str, paragraph: &str| {
+        let data_style = if number {
+            r#" style:data-style-name="N0""#
+        } else {
+            ""
+        };
+        _ = write!(
+            styles,
+            r#"<style:style style:name="{name}" style:family="table-cell"{data_style}><style:table-cell-properties {cell}/><style:paragraph-properties {paragraph}/><style:text-properties {text}/></style:style>"#
+        );
+    };
+
+    let bold = r#"fo:font-weight="bold""#;
+    cell_style(
+        "title",
+        false,
+        "",
+        r#"fo:font-weight="bold" fo:font-size="14pt""#,
+        "",
+    );
+    cell_style(
+        "header",
+        false,
+        &format!(
+            r##"{BORDER} fo:background-color="#C6E0B4" fo:wrap-option="wrap" style:vertical-align="middle""##
+        ),
+        bold,
+        r#"fo:text-align="center""#,
+    );
+    cell_style("cell", false, BORDER, "", "");
+    cell_style("number", true, BORDER, "", "");
+    cell_style("bold", false, BORDER, bold, "");
+    cell_style("bold_number", true, BORDER, bold, "");
+
+    let mut depths = BTreeSet::new();
+    let mut colors = BTreeSet::new();
+    for row in rows {
+        match row {
+            SheetRow::Group { depth, .. } | SheetRow::Subtotal { depth, .. } => {
+                depths.insert(*depth);
+            }
+            SheetRow::Company(company) => colors.extend(company.highlight.as_deref()),
+            SheetRow::Total(_) => {}
+        }
+    }
+    for depth in depths {
+        let margin = format!(r#"fo:margin-left="{}cm""#, depth as f32 * 0.3);
+        cell_style(&format!("indent{depth}"), false, BORDER, bold, &margin);
+    }
+    for color in colors {
+        let cell = format!(r##"{BORDER} fo:background-color="#{color}""##);
+        cell_style(&format!("fill{color}"), false, &cell, "", "");
+        cell_style(&format!("fill{color}_number"), true, &cell, "", "");
+    }
+
+    styles
+}
+
+/// Widths of the code, name and amount columns from the longest text in each.
+fn column_widths(rows: &[SheetRow], number_format: &NumberFormat, language: Language) -> [f32; 3] {
+    let mut chars = [4, language.tr(Message::Name).chars().count(), 10];
+
+    for row in rows {
+        let (name, values) = match row {
+            SheetRow::Group { name, .. } => (name.clone(), None),
+            SheetRow::Company(company) => {
+                chars[0] = chars[0].max(company.id.to_string().len());
+                (company.name.clone(), Some(company.values()))
+            }
+            SheetRow::Subtotal { name, total, .. } => (
+                language.trf(Message::SubtotalFor, &[("group", name)]),
+                Some(total_values(total).map(Some)),
+            ),
+            SheetRow::Total(total) => (
+                language.tr(Message::Total).to_string(),
+                Some(total_values(total).map(Some)),
+            ),
+        };
+
+        chars[1] = chars[1].max(name.chars().count());
+        for value in values.into_iter().flatten().flatten() {
+            chars[2] = chars[2].max(number_format.format(value).chars().count());
+        }
+    }
+
+    chars.map(|chars| chars as f32 * CHAR_WIDTH_CM + 0.4)
+}
+
+fn content(
+    rows: &[SheetRow],
+    title: &SheetTitle,
+    number_format: &NumberFormat,
+    language: Language,
+) -> String {
+    let mut xml = String::new();
+    let widths = column_widths(rows, number_format, language);
+
+    _ = write!(
+        xml,
+        r#"<?xml version="1.0" encoding="UTF-8"?><office:document-content {NAMESPACES}><office:automatic-styles>{}"#,
+        styles(rows, number_format)
+    );
+    for (name, width) in ["co_id", "co_name", "co_number"].into_iter().zip(widths) {
+        _ = write!(
+            xml,
+            r#"<style:style style:name="{name}" style:family="table-column"><style:table-column-properties style:column-width="{width:.2}cm"/></style:style>"#
+        );
+    }
+    _ = write!(
+        xml,
+        r#"</office:automatic-styles><office:body><office:spreadsheet><table:table table:name="{SHEET_NAME}"><table:table-column table:style-name="co_id"/><table:table-column table:style-name="co_name"/><table:table-column table:style-name="co_number" table:number-columns-repeated="6"/>"#
+    );
+
+    for line in title.lines(language) {
+        xml.push_str("<table:table-row>");
+        text_cell(&mut xml, &line, "title");
+        xml.push_str("</table:table-row>");
+    }
+    xml.push_str("<table:table-row><table:table-cell/></table:table-row>");
+
+    header(&mut xml, language);
+
+    let mut open_groups: Vec<CompanyRuns> = Vec::new();
+    let mut all_companies = CompanyRuns::default();
+    let first_row = TITLE_ROWS + HEADER_ROWS;
+
+    for (i, row) in rows.iter().enumerate() {
+        let row_num = first_row + i as RowNum;
+
+        match row {
+            SheetRow::Group { name, depth } => {
+                xml.push_str("<table:table-row>");
+                empty_cell(&mut xml, "cell");
+                text_cell(&mut xml, name, &format!("indent{depth}"));
+                for _ in 0..6 {
+                    empty_cell(&mut xml, "cell");
+                }
+                xml.push_str("</table:table-row><table:table-row-group>");
+                open_groups.push(CompanyRuns::default());
+            }
+            SheetRow::Company(company) => {
+                let (text_style, number_style) = match &company.highlight {
+                    Some(color) => (format!("fill{color}"), format!("fill{color}_number")),
+                    None => ("cell".to_string(), "number".to_string()),
+                };
+
+                xml.push_str("<table:table-row>");
+                number_cell(&mut xml, company.id as f64, &text_style);
+                text_cell(&mut xml, &company.name, &text_style);
+                for value in company.values() {
+                    match value {
+                        Some(value) => number_cell(&mut xml, value, &number_style),
+                        None => empty_cell(&mut xml, &number_style),
+                    }
+                }
+                xml.push_str("</table:table-row>");
+
+                all_companies.push(row_num);
+                for companies in &mut open_groups {
+                    companies.push(row_num);
+                }
+            }
+            SheetRow::Subtotal { name, depth, total } => {
+                let companies = open_groups.pop().unwrap_or_default();
+                let label = language.trf(Message::SubtotalFor, &[("group", name)]);
+
+                xml.push_str("</table:table-row-group><table:table-row>");
+                empty_cell(&mut xml, "bold");
+                text_cell(&mut xml, &label, &format!("indent{depth}"));
+                sum_cells(&mut xml, &companies, total);
+                xml.push_str("</table:table-row>");
+            }
+            SheetRow::Total(total) => {
+                xml.push_str("<table:table-row>");
+                empty_cell(&mut xml, "bold");
+                text_cell(&mut xml, language.tr(Message::Total), "bold");
+                sum_cells(&mut xml, &all_companies, total);
+                xml.push_str("</table:table-row>");
+            }
+        }
+    }
+
+    // a group the rows ended in before its subtotal
+    for _ in open_groups {
+        xml.push_str("</table:table-row-group>");
+    }
+
+    xml.push_str("</table:table></office:spreadsheet></office:body></office:document-content>");
+    xml
+}
+
+/// The code and the name span both header rows, each balance spans its debit and credit.
+fn header(xml: &mut String, language: Language) {
+    let spanned = |xml: &mut String, text: &str, rows: u32, cols: u32| {
+        _ = write!(
+            xml,
+            r#"<table:table-cell table:style-name="header" table:number-rows-spanned="{rows}" table:number-columns-spanned="{cols}" office:value-type="string"><text:p>{}</text:p></table:table-cell>"#,
+            escape(text)
+        );
+    };
+    let covered = r#"<table:covered-table-cell table:style-name="header"/>"#;
+
+    xml.push_str("<table:table-row>");
+    spanned(xml, language.tr(Message::Id), 2, 1);
+    spanned(xml, language.tr(Message::Name), 2, 1);
+    for title in [
+        Message::RemainderBeginMonth,
+        Message::Turnover,
+        Message::RemainderEndMonth,
+    ] {
+        spanned(xml, language.tr(title), 1, 2);
+        xml.push_str(covered);
+    }
+    xml.push_str("</table:table-row><table:table-row>");
+    xml.push_str(covered);
+    xml.push_str(covered);
+    for side in [Message::Debit, Message::Credit].repeat(3) {
+        text_cell(xml, language.tr(side), "header");
+    }
+    xml.push_str("</table:table-row>");
+}
+
+fn text_cell(xml: &mut String, text: &str, style: &str) {
+    _ = write!(
+        xml,
+        r#"<table:table-cell table:style-name="{style}" office:value-type="string"><text:p>{}</text:p></table:table-cell>"#,
+        escape(text)
+    );
+}
+
+fn number_cell(xml: &mut String, value: f64, style: &str) {
+    _ = write!(
+        xml,
+        r#"<table:table-cell table:style-name="{style}" office:value-type="float" office:value="{value}"><text:p>{value}</text:p></table:table-cell>"#
+    );
+}
+
+fn empty_cell(xml: &mut String, style: &str) {
+    _ = write!(xml, r#"<table:table-cell table:style-name="{style}"/>"#);
+}
+
+/// `SUM` formulas over the companies with the values they come to, for readers that don't
+/// recalculate.
+fn sum_cells(xml: &mut String, companies: &CompanyRuns, total: &TotalRow) {
+    for (col, value) in total_values(total).into_iter().enumerate() {
+        if companies.0.is_empty() {
+            number_cell(xml, value, "bold_number");
+            continue;
+        }
+
+        let column = column_number_to_name(FIRST_VALUE_COL + col as ColNum);
+        let ranges: Vec<_> = companies
+            .0
+            .iter()
+            .map(|&(first, last)| {
+                if first == last {
+                    format!("[.{column}{}]", first + 1)
+                } else {
+                    format!("[.{column}{}:.{column}{}]", first + 1, last + 1)
+                }
+            })
+            .collect();
+        _ = write!(
+            xml,
+            r#"<table:table-cell table:style-name="bold_number" table:formula="of:=SUM({})" office:value-type="float" office:value="{value}"><text:p>{value}</text:p></table:table-cell>"#,
+            ranges.join(";")
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use calamine::{open_workbook_auto, Data, Reader};
+
+    use super::*;
+    use crate::app::{
+        exports::build_sheet,
+        model::{Company, Group},
+    };
+
+    fn company(id: i64, name: &str, begin: f64, end: f64, group_id: Option<i64>) -> Company {
+        Company {
+            id,
+            name: name.to_string(),
+            remainder_begin_month: begin,
+            debit_turnover: 300.,
+            credit_turnover: 300. + begin - end,
+            remainder_end_month: end,
+            group_id,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn reopened_sheet_has_the_values_and_totals() {
+        let companies = [
+            company(1, "Alpha & Co", 500., 300., Some(1)),
+            company(2, "Beta", -200., -50., Some(1)),
+            company(3, "Gamma", 0., 75.5, None),
+        ];
+        let companies: Vec<_> = companies.iter().collect();
+        let groups = [Group {
+            id: 1,
+            name: "Suppliers".to_string(),
+            parent_id: None,
+        }];
+        let rows = build_sheet(&companies, &groups, &[], Language::En);
+        let title = SheetTitle {
+            organization: "Our <Organization>".to_string(),
+            period: None,
+        };
+
+        let bytes = export_to_ods(&rows, &title, &NumberFormat::default(), Language::En).unwrap();
+        let path = std::env::temp_dir().join(format!("company_list_{}.ods", std::process::id()));
+        std::fs::write(&path, bytes).unwrap();
+
+        let mut workbook = open_workbook_auto(&path).unwrap();
+        let range = workbook.worksheet_range_at(0).unwrap().unwrap();
+        let formulas = workbook.worksheet_formula(SHEET_NAME).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let cell = |row: u32, col: u32| range.get_value((row, col)).cloned();
+        let text = |value: &str| Some(Data::String(value.to_string()));
+        let number = |value: f64| Some(Data::Float(value));
+
+        assert_eq!(cell(0, 0), text("Our <Organization>"));
+        assert_eq!(cell(3, 0), text(Language::En.tr(Message::Id)));
+        assert_eq!(cell(4, 2), text(Language::En.tr(Message::Debit)));
+        assert_eq!(cell(4, 3), text(Language::En.tr(Message::Credit)));
+
+        // the group with its two companies and subtotal, then the ungrouped one and the total
+        assert_eq!(cell(5, 1), text("Suppliers"));
+        assert_eq!(cell(6, 0), number(1.));
+        assert_eq!(cell(6, 1), text("Alpha & Co"));
+        assert_eq!(cell(6, 2), number(500.));
+        assert_eq!(cell(6, 3), Some(Data::Empty));
+        assert_eq!(cell(7, 3), number(200.));
+        assert_eq!(cell(7, 7), number(50.));
+        assert_eq!(cell(8, 2), number(500.));
+        assert_eq!(cell(8, 3), number(200.));
+        assert_eq!(cell(10, 1), text("Gamma"));
+        assert_eq!(cell(10, 6), number(75.5));
+
+        assert_eq!(cell(12, 1), text(Language::En.tr(Message::Total)));
+        assert_eq!(cell(12, 2), number(500.));
+        assert_eq!(cell(12, 3), number(200.));
+        assert_eq!(cell(12, 4), number(900.));
+        assert_eq!(cell(12, 6), number(375.5));
+        assert_eq!(cell(12, 7), number(50.));
+
+        let formulas: Vec<_> = formulas.used_cells().map(|(.., formula)| formula).collect();
+        assert!(formulas.contains(&&"of:=SUM([.C7:.C8])".to_string()));
+        assert!(formulas.contains(&&"of:=SUM([.G7:.G8];[.G11])".to_string()));
+    }
+}
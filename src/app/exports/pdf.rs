@@ -0,0 +1,295 @@
+use ab_glyph::{Font, FontArc};
+use printpdf::{
+    IndirectFontRef, Line, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference, Point,
+};
+
+use super::{total_values, SheetRow};
+use crate::app::{
+    format::NumberFormat,
+    i18n::{Language, Message},
+};
+
+// A4
+pub(super) const A4_LONG: f32 = 297.0;
+pub(super) const A4_SHORT: f32 = 210.0;
+pub(super) const MARGIN: f32 = 10.0;
+
+const ID_WIDTH: f32 = 15.0;
+const NAME_WIDTH: f32 = 77.0;
+const NUMBER_WIDTH: f32 = 30.0;
+
+pub(super) const ROW_HEIGHT: f32 = 6.0;
+pub(super) const FONT_SIZE: f32 = 9.0;
+pub(super) const PADDING: f32 = 1.5;
+const INDENT: f32 = 3.0;
+const PT_TO_MM: f32 = 25.4 / 72.0;
+
+/// egui ships this font with Cyrillic and Azerbaijani letters, so the PDF doesn't depend on
+/// what is installed in the system.
+const FONT_NAME: &str = "Ubuntu-Light";
+
+/// Writes rows of text top to bottom, measuring text with the same font that gets embedded.
+/// Coordinates are in millimeters from the bottom left corner of the page.
+pub(super) struct PdfWriter {
+    document: PdfDocumentReference,
+    layer: PdfLayerReference,
+    font: IndirectFontRef,
+    metrics: FontArc,
+    page_width: f32,
+    page_height: f32,
+    /// Top of the next row.
+    pub y: f32,
+    pub font_size: f32,
+}
+
+impl PdfWriter {
+    pub fn new(
+        title: &str,
+        page_width: f32,
+        page_height: f32,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let fonts = egui::FontDefinitions::default();
+        let font_data = fonts
+            .font_data
+            .get(FONT_NAME)
+            .ok_or("the built-in font is missing")?
+            .font
+            .to_vec();
+
+        let (document, page, layer) = PdfDocument::new(title, Mm(page_width), Mm(page_height), "1");
+        let font = document.add_external_font(font_data.as_slice())?;
+        let layer = document.get_page(page).get_layer(layer);
+
+        Ok(Self {
+            document,
+            layer,
+            font,
+            metrics: FontArc::try_from_vec(font_data)?,
+            page_width,
+            page_height,
+            y: page_height - MARGIN,
+            font_size: FONT_SIZE,
+        })
+    }
+
+    pub fn page_width(&self) -> f32 {
+        self.page_width
+    }
+
+    /// Whether `height` more millimeters still fit above the bottom margin.
+    pub fn fits(&self, height: f32) -> bool {
+        self.y - height >= MARGIN
+    }
+
+    pub fn new_page(&mut self) {
+        let (page, layer) = self
+            .document
+            .add_page(Mm(self.page_width), Mm(self.page_height), "1");
+        self.layer = self.document.get_page(page).get_layer(layer);
+        self.y = self.page_height - MARGIN;
+    }
+
+    pub fn save(self) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        Ok(self.document.save_to_bytes()?)
+    }
+
+    /// Writes text from the left edge of a cell in the current row, cutting it to the cell width.
+    pub fn text_left(&self, text: &str, left: f32, width: f32) {
+        self.text(&self.fit(text, width), left + PADDING, self.y);
+    }
+
+    /// Cuts the text so that it fits into a cell of the given width.
+    pub fn fit(&self, text: &str, width: f32) -> String {
+        let available = width - PADDING * 2.;
+        let mut text = text.to_string();
+
+        if self.width(&text) > available {
+            while !text.is_empty() && self.width(&format!("{text}…")) > available {
+                text.pop();
+            }
+            text.push('…');
+        }
+
+        text
+    }
+
+    /// Writes text word wrapped to the width, one row per line.
+    pub fn paragraph(&mut self, text: &str, left: f32, width: f32) {
+        let mut line = String::new();
+
+        for word in text.split_whitespace() {
+            let candidate = if line.is_empty() {
+                word.to_string()
+            } else {
+                format!("{line} {word}")
+            };
+
+            if !line.is_empty() && self.width(&candidate) > width - PADDING * 2. {
+                self.text_left(&line, left, width);
+                self.y -= ROW_HEIGHT;
+                line = word.to_string();
+            } else {
+                line = candidate;
+            }
+        }
+
+        if !line.is_empty() {
+            self.text_left(&line, left, width);
+            self.y -= ROW_HEIGHT;
+        }
+    }
+
+    /// Writes text in the current row so that it ends at the right edge of a cell.
+    pub fn text_right(&self, text: &str, right: f32) {
+        self.text(text, right - PADDING - self.width(text), self.y);
+    }
+
+    pub fn centered(&self, text: &str, left: f32, width: f32, top: f32) {
+        let text = self.fit(text, width);
+        let x = left + ((width - self.width(&text)) / 2.).max(PADDING);
+        self.text(&text, x, top);
+    }
+
+    /// Writes one line of text vertically centered in the row starting at `top`.
+    pub fn text(&self, text: &str, x: f32, top: f32) {
+        let baseline = top - ROW_HEIGHT / 2. - self.font_size * PT_TO_MM / 3.;
+        self.layer
+            .use_text(text, self.font_size, Mm(x), Mm(baseline), &self.font);
+    }
+
+    /// Width of the text in millimeters.
+    pub fn width(&self, text: &str) -> f32 {
+        let units: f32 = text
+            .chars()
+            .map(|c| self.metrics.h_advance_unscaled(self.metrics.glyph_id(c)))
+            .sum();
+
+        units / self.metrics.units_per_em().unwrap_or(1000.) * self.font_size * PT_TO_MM
+    }
+
+    pub fn line(&self, x1: f32, y1: f32, x2: f32, y2: f32) {
+        self.layer.add_line(Line {
+            points: vec![
+                (Point::new(Mm(x1), Mm(y1)), false),
+                (Point::new(Mm(x2), Mm(y2)), false),
+            ],
+            is_closed: false,
+        });
+    }
+}
+
+pub fn export_to_pdf(
+    rows: &[SheetRow],
+    number_format: &NumberFormat,
+    language: Language,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut writer = PdfWriter::new(language.tr(Message::Application), A4_LONG, A4_SHORT)?;
+    sheet_header(&mut writer, language);
+
+    for row in rows {
+        if !writer.fits(ROW_HEIGHT) {
+            writer.new_page();
+            sheet_header(&mut writer, language);
+        }
+
+        match row {
+            SheetRow::Group { name, depth } => {
+                sheet_name(&writer, name, *depth);
+            }
+            SheetRow::Company(company) => {
+                writer.text_left(&company.id.to_string(), MARGIN, ID_WIDTH);
+                sheet_name(&writer, &company.name, 0);
+                sheet_numbers(&writer, &company.values(), number_format);
+            }
+            SheetRow::Subtotal { name, depth, total } => {
+                let label = language.trf(Message::SubtotalFor, &[("group", name)]);
+                sheet_name(&writer, &label, *depth);
+                sheet_numbers(&writer, &total_values(total).map(Some), number_format);
+                sheet_rule(&writer, MARGIN + ID_WIDTH, writer.y - ROW_HEIGHT);
+            }
+            SheetRow::Total(total) => {
+                sheet_rule(&writer, MARGIN, writer.y);
+                sheet_name(&writer, language.tr(Message::Total), 0);
+                sheet_numbers(&writer, &total_values(total).map(Some), number_format);
+            }
+        }
+
+        writer.y -= ROW_HEIGHT;
+    }
+
+    writer.save()
+}
+
+/// Two header rows: the code and the name span both, each balance is split into debit and
+/// credit below its title.
+fn sheet_header(writer: &mut PdfWriter, language: Language) {
+    let numbers_left = MARGIN + ID_WIDTH + NAME_WIDTH;
+    let right = numbers_left + NUMBER_WIDTH * 6.;
+    let top = writer.y;
+
+    sheet_rule(writer, MARGIN, top);
+
+    let middle = top - ROW_HEIGHT / 2.;
+    writer.centered(language.tr(Message::Id), MARGIN, ID_WIDTH, middle);
+    writer.centered(
+        language.tr(Message::Name),
+        MARGIN + ID_WIDTH,
+        NAME_WIDTH,
+        middle,
+    );
+
+    let titles = [
+        Message::RemainderBeginMonth,
+        Message::Turnover,
+        Message::RemainderEndMonth,
+    ];
+    for (i, title) in titles.into_iter().enumerate() {
+        let left = numbers_left + NUMBER_WIDTH * 2. * i as f32;
+        writer.centered(language.tr(title), left, NUMBER_WIDTH * 2., top);
+
+        for (j, side) in [Message::Debit, Message::Credit].into_iter().enumerate() {
+            let left = left + NUMBER_WIDTH * j as f32;
+            writer.centered(language.tr(side), left, NUMBER_WIDTH, top - ROW_HEIGHT);
+        }
+    }
+
+    writer.line(numbers_left, top - ROW_HEIGHT, right, top - ROW_HEIGHT);
+    sheet_rule(writer, MARGIN, top - ROW_HEIGHT * 2.);
+
+    let mut x = MARGIN;
+    for width in [
+        0.,
+        ID_WIDTH,
+        NAME_WIDTH,
+        NUMBER_WIDTH * 2.,
+        NUMBER_WIDTH * 2.,
+    ] {
+        x += width;
+        writer.line(x, top, x, top - ROW_HEIGHT * 2.);
+    }
+    writer.line(right, top, right, top - ROW_HEIGHT * 2.);
+
+    writer.y -= ROW_HEIGHT * 2.;
+}
+
+fn sheet_name(writer: &PdfWriter, name: &str, depth: usize) {
+    let indent = INDENT * depth as f32;
+    writer.text_left(name, MARGIN + ID_WIDTH + indent, NAME_WIDTH - indent);
+}
+
+fn sheet_numbers(writer: &PdfWriter, values: &[Option<f64>; 6], number_format: &NumberFormat) {
+    let mut right = MARGIN + ID_WIDTH + NAME_WIDTH;
+
+    for value in values {
+        right += NUMBER_WIDTH;
+        if let Some(value) = value {
+            writer.text_right(&number_format.format(value.abs()), right);
+        }
+    }
+}
+
+/// Horizontal line across the whole table.
+fn sheet_rule(writer: &PdfWriter, left: f32, y: f32) {
+    let right = MARGIN + ID_WIDTH + NAME_WIDTH + NUMBER_WIDTH * 6.;
+    writer.line(left, y, right, y);
+}
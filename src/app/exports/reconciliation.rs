@@ -0,0 +1,369 @@
+use rust_xlsxwriter::{Format, FormatAlign, FormatBorder, Workbook, XlsxError};
+
+use super::pdf::{PdfWriter, A4_LONG, A4_SHORT, FONT_SIZE, MARGIN, ROW_HEIGHT};
+use crate::app::{
+    format::NumberFormat,
+    i18n::{Language, Message},
+    model::{Company, Organization, Period, PeriodBalance},
+};
+
+/// Everything a reconciliation statement with one counterparty is made of.
+pub struct Reconciliation {
+    pub organization: Organization,
+    pub company: Company,
+    pub from: Period,
+    pub to: Period,
+    /// Balances of the periods from `from` to `to` that have any, oldest first.
+    pub balances: Vec<(Period, PeriodBalance)>,
+}
+
+/// A row of our side of the table: what it is, debit and credit.
+type Line = (String, Option<f64>, Option<f64>);
+
+impl Reconciliation {
+    fn organization_name(&self, language: Language) -> String {
+        if self.organization.name.trim().is_empty() {
+            language.tr(Message::OurOrganization).to_string()
+        } else {
+            self.organization.name.clone()
+        }
+    }
+
+    fn opening(&self) -> f64 {
+        self.balances
+            .first()
+            .map_or(0., |(_, balance)| balance.remainder_begin_month)
+    }
+
+    fn closing(&self) -> f64 {
+        self.balances
+            .last()
+            .map_or(0., |(_, balance)| balance.remainder_end_month)
+    }
+
+    /// Opening balance, turnover of every period, turnover of the whole range and the closing
+    /// balance. A positive balance is a debt of the counterparty, so it goes to the debit.
+    fn lines(&self, language: Language) -> Vec<Line> {
+        let balance_line = |title: Message, balance: f64| -> Line {
+            let (debit, credit) = if balance >= 0. {
+                (Some(balance), None)
+            } else {
+                (None, Some(-balance))
+            };
+            (language.tr(title).to_string(), debit, credit)
+        };
+
+        let mut lines = vec![balance_line(Message::OpeningBalance, self.opening())];
+
+        lines.extend(self.balances.iter().map(|(period, balance)| {
+            (
+                language.trf(Message::TurnoverFor, &[("period", &period.label())]),
+                Some(balance.debit_turnover),
+                Some(balance.credit_turnover),
+            )
+        }));
+
+        let debit = self.balances.iter().map(|(_, b)| b.debit_turnover).sum();
+        let credit = self.balances.iter().map(|(_, b)| b.credit_turnover).sum();
+        lines.push((
+            language.tr(Message::PeriodTurnover).to_string(),
+            Some(debit),
+            Some(credit),
+        ));
+
+        lines.push(balance_line(Message::ClosingBalance, self.closing()));
+        lines
+    }
+
+    fn conclusion(&self, number_format: &NumberFormat, language: Language) -> String {
+        let closing = self.closing();
+        let date = self.to.last_day();
+        let organization = self.organization_name(language);
+
+        let (debtor, creditor) = match closing {
+            c if c > 0. => (&self.company.name, &organization),
+            c if c < 0. => (&organization, &self.company.name),
+            _ => return language.trf(Message::NoDebt, &[("date", &date)]),
+        };
+
+        language.trf(
+            Message::DebtOwed,
+            &[
+                ("date", &date),
+                ("debtor", debtor),
+                ("creditor", creditor),
+                ("amount", &number_format.format(closing.abs())),
+            ],
+        )
+    }
+
+    fn titles(&self, language: Language) -> [String; 3] {
+        [
+            language.tr(Message::ReconciliationTitle).to_string(),
+            language.trf(
+                Message::ReconciliationRange,
+                &[
+                    ("from", &self.from.first_day()),
+                    ("to", &self.to.last_day()),
+                ],
+            ),
+            language.trf(
+                Message::ReconciliationParties,
+                &[
+                    ("organization", &self.organization_name(language)),
+                    ("counterparty", &self.company.name),
+                ],
+            ),
+        ]
+    }
+
+    /// Name, tax id, address and signatory of both sides.
+    fn parties(&self, language: Language) -> [[String; 4]; 2] {
+        let tax_id = |value: &str| format!("{}: {}", language.tr(Message::TaxId), value);
+
+        [
+            [
+                language.trf(
+                    Message::OnBehalfOf,
+                    &[("party", &self.organization_name(language))],
+                ),
+                tax_id(&self.organization.tax_id),
+                self.organization.legal_address.clone(),
+                self.organization.signatory.clone(),
+            ],
+            [
+                language.trf(Message::OnBehalfOf, &[("party", &self.company.name)]),
+                tax_id(&self.company.tax_id),
+                self.company.legal_address.clone(),
+                self.company.contact_person.clone(),
+            ],
+        ]
+    }
+}
+
+// portrait A4, two sides of 95 mm
+const SIDE_WIDTH: f32 = (A4_SHORT - MARGIN * 2.) / 2.;
+const AMOUNT_WIDTH: f32 = 25.0;
+const CONTENTS_WIDTH: f32 = SIDE_WIDTH - AMOUNT_WIDTH * 2.;
+
+pub fn reconciliation_to_pdf(
+    reconciliation: &Reconciliation,
+    number_format: &NumberFormat,
+    language: Language,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut writer = PdfWriter::new(language.tr(Message::ReconciliationTitle), A4_SHORT, A4_LONG)?;
+    let page_width = writer.page_width();
+    let content_width = page_width - MARGIN * 2.;
+
+    for (i, title) in reconciliation.titles(language).iter().enumerate() {
+        writer.font_size = if i == 0 { FONT_SIZE * 1.5 } else { FONT_SIZE };
+        writer.centered(title, MARGIN, content_width, writer.y);
+        writer.y -= ROW_HEIGHT * if i == 0 { 1.5 } else { 1. };
+    }
+    writer.font_size = FONT_SIZE;
+    writer.y -= ROW_HEIGHT;
+
+    // two header rows over both sides
+    let top = writer.y;
+    let sides = [
+        reconciliation.organization_name(language),
+        reconciliation.company.name.clone(),
+    ];
+    for (i, party) in sides.iter().enumerate() {
+        let left = MARGIN + SIDE_WIDTH * i as f32;
+        let title = language.trf(Message::AccordingTo, &[("party", party)]);
+        writer.centered(&title, left, SIDE_WIDTH, top);
+
+        let columns = [
+            (Message::Contents, CONTENTS_WIDTH),
+            (Message::Debit, AMOUNT_WIDTH),
+            (Message::Credit, AMOUNT_WIDTH),
+        ];
+        let mut x = left;
+        for (title, width) in columns {
+            writer.centered(language.tr(title), x, width, top - ROW_HEIGHT);
+            x += width;
+        }
+    }
+    writer.line(MARGIN, top, page_width - MARGIN, top);
+    writer.line(
+        MARGIN,
+        top - ROW_HEIGHT,
+        page_width - MARGIN,
+        top - ROW_HEIGHT,
+    );
+    writer.y -= ROW_HEIGHT * 2.;
+
+    for (label, debit, credit) in reconciliation.lines(language) {
+        if !writer.fits(ROW_HEIGHT) {
+            writer.new_page();
+        }
+
+        writer.line(MARGIN, writer.y, page_width - MARGIN, writer.y);
+        writer.text_left(&label, MARGIN, CONTENTS_WIDTH);
+
+        let mut right = MARGIN + CONTENTS_WIDTH;
+        for amount in [debit, credit] {
+            right += AMOUNT_WIDTH;
+            if let Some(amount) = amount {
+                writer.text_right(&number_format.format(amount), right);
+            }
+        }
+
+        writer.y -= ROW_HEIGHT;
+    }
+    writer.line(MARGIN, writer.y, page_width - MARGIN, writer.y);
+
+    // the counterparty's side stays empty for them to fill in
+    for i in 0..=2 {
+        let x = MARGIN + SIDE_WIDTH * i as f32;
+        writer.line(x, top, x, writer.y);
+    }
+    for side in 0..2 {
+        let left = MARGIN + SIDE_WIDTH * side as f32;
+        for x in [left + CONTENTS_WIDTH, left + CONTENTS_WIDTH + AMOUNT_WIDTH] {
+            writer.line(x, top - ROW_HEIGHT, x, writer.y);
+        }
+    }
+
+    writer.y -= ROW_HEIGHT;
+    writer.paragraph(
+        &reconciliation.conclusion(number_format, language),
+        MARGIN,
+        content_width,
+    );
+    writer.y -= ROW_HEIGHT;
+
+    if !writer.fits(ROW_HEIGHT * 6.) {
+        writer.new_page();
+    }
+
+    let top = writer.y;
+    for (i, [title, tax_id, address, signatory]) in
+        reconciliation.parties(language).iter().enumerate()
+    {
+        let left = MARGIN + SIDE_WIDTH * i as f32;
+        writer.y = top;
+
+        for text in [title, tax_id, address] {
+            writer.text_left(text, left, SIDE_WIDTH);
+            writer.y -= ROW_HEIGHT;
+        }
+
+        writer.y -= ROW_HEIGHT;
+        writer.text_left(
+            &format!("________________ / {signatory} /"),
+            left,
+            SIDE_WIDTH,
+        );
+        writer.y -= ROW_HEIGHT;
+        writer.text_left(language.tr(Message::Stamp), left, SIDE_WIDTH);
+    }
+
+    writer.save()
+}
+
+pub fn reconciliation_to_excel(
+    reconciliation: &Reconciliation,
+    number_format: &NumberFormat,
+    language: Language,
+) -> Result<Vec<u8>, XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+
+    let title_format = Format::new().set_bold().set_align(FormatAlign::Center);
+    let centered = Format::new().set_align(FormatAlign::Center);
+    let header_format = Format::new()
+        .set_bold()
+        .set_border(FormatBorder::Thin)
+        .set_align(FormatAlign::Center)
+        .set_text_wrap();
+    let cell_format = Format::new().set_border(FormatBorder::Thin);
+    let number_cell_format = cell_format
+        .clone()
+        .set_num_format(number_format.spreadsheet_format());
+
+    for (col, width) in [(0, 30.), (1, 14.), (2, 14.), (3, 30.), (4, 14.), (5, 14.)] {
+        worksheet.set_column_width(col, width)?;
+    }
+
+    let titles = reconciliation.titles(language);
+    for (row, title) in titles.iter().enumerate() {
+        let format = if row == 0 { &title_format } else { &centered };
+        worksheet.merge_range(row as u32, 0, row as u32, 5, title, format)?;
+    }
+
+    let header = titles.len() as u32 + 1;
+    let sides = [
+        reconciliation.organization_name(language),
+        reconciliation.company.name.clone(),
+    ];
+    for (i, party) in sides.iter().enumerate() {
+        let col = i as u16 * 3;
+        let title = language.trf(Message::AccordingTo, &[("party", party)]);
+        worksheet.merge_range(header, col, header, col + 2, &title, &header_format)?;
+
+        for (offset, title) in [Message::Contents, Message::Debit, Message::Credit]
+            .into_iter()
+            .enumerate()
+        {
+            worksheet.write_with_format(
+                header + 1,
+                col + offset as u16,
+                language.tr(title),
+                &header_format,
+            )?;
+        }
+    }
+
+    let mut row = header + 2;
+    for (label, debit, credit) in reconciliation.lines(language) {
+        worksheet.write_with_format(row, 0, &label, &cell_format)?;
+
+        for (col, amount) in [(1, debit), (2, credit)] {
+            match amount {
+                Some(amount) => {
+                    worksheet.write_number_with_format(row, col, amount, &number_cell_format)?
+                }
+                None => worksheet.write_blank(row, col, &cell_format)?,
+            };
+        }
+        for col in 3..6 {
+            worksheet.write_blank(row, col, &cell_format)?;
+        }
+
+        row += 1;
+    }
+
+    row += 1;
+    worksheet.merge_range(
+        row,
+        0,
+        row,
+        5,
+        &reconciliation.conclusion(number_format, language),
+        &Format::new().set_text_wrap(),
+    )?;
+    worksheet.set_row_height(row, 30)?;
+
+    row += 2;
+    for (i, [title, tax_id, address, signatory]) in
+        reconciliation.parties(language).iter().enumerate()
+    {
+        let col = i as u16 * 3;
+        let lines = [
+            title.clone(),
+            tax_id.clone(),
+            address.clone(),
+            String::new(),
+            format!("________________ / {signatory} /"),
+            language.tr(Message::Stamp).to_string(),
+        ];
+
+        for (offset, text) in lines.iter().enumerate() {
+            worksheet.write(row + offset as u32, col, text)?;
+        }
+    }
+
+    workbook.save_to_buffer()
+}
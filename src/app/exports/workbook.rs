@@ -0,0 +1,230 @@
+use std::collections::BTreeMap;
+
+use rust_xlsxwriter::{
+    utility::cell_range, DocProperties, Format, FormatBorder, Formula, Workbook, Worksheet,
+    XlsxError,
+};
+
+use super::{write_sheet, SheetRow, SheetTitle};
+use crate::app::{
+    format::NumberFormat,
+    i18n::{Language, Message},
+    model::Period,
+};
+
+/// The sheet of one month of a workbook over several periods.
+pub struct PeriodSheet {
+    pub period: Period,
+    pub rows: Vec<SheetRow>,
+}
+
+/// What the cover sheet says about the workbook besides the periods.
+pub struct WorkbookCover {
+    pub organization: String,
+    /// The account the balances are kept on, or that they are consolidated.
+    pub account: String,
+    pub reporting_currency: String,
+}
+
+/// A cover sheet, a summary of the closing balances of every company month by month and a sheet
+/// per period laid out like the single period export. Periods go oldest first.
+pub fn periods_to_excel(
+    sheets: &[PeriodSheet],
+    cover: &WorkbookCover,
+    number_format: &NumberFormat,
+    language: Language,
+) -> Result<Vec<u8>, XlsxError> {
+    let mut workbook = Workbook::new();
+    workbook.set_properties(
+        &DocProperties::new()
+            .set_title(language.tr(Message::TurnoverSheets))
+            .set_company(&cover.organization),
+    );
+
+    write_cover(
+        workbook
+            .add_worksheet()
+            .set_name(language.tr(Message::Cover))?,
+        sheets,
+        cover,
+        language,
+    )?;
+    write_summary(
+        workbook
+            .add_worksheet()
+            .set_name(language.tr(Message::Summary))?,
+        sheets,
+        number_format,
+        language,
+    )?;
+
+    for sheet in sheets {
+        let title = SheetTitle {
+            organization: cover.organization.clone(),
+            period: Some(sheet.period.clone()),
+        };
+        let worksheet = workbook.add_worksheet().set_name(sheet.period.label())?;
+        write_sheet(worksheet, &sheet.rows, &title, number_format, language)?;
+    }
+
+    workbook.save_to_buffer()
+}
+
+fn write_cover(
+    worksheet: &mut Worksheet,
+    sheets: &[PeriodSheet],
+    cover: &WorkbookCover,
+    language: Language,
+) -> Result<(), XlsxError> {
+    let title_format = Format::new().set_bold().set_font_size(16);
+    let label_format = Format::new().set_bold();
+
+    let range = match (sheets.first(), sheets.last()) {
+        (Some(first), Some(last)) => {
+            format!("{} – {}", first.period.first_day(), last.period.last_day())
+        }
+        _ => String::new(),
+    };
+    let companies = summary_companies(sheets).len();
+
+    worksheet.write_with_format(0, 0, language.tr(Message::TurnoverSheets), &title_format)?;
+
+    let lines = [
+        (Message::OurOrganization, cover.organization.clone()),
+        (Message::Account, cover.account.clone()),
+        (Message::Period, range),
+        (Message::PeriodsCount, sheets.len().to_string()),
+        (Message::Companies, companies.to_string()),
+        (Message::ReportingCurrency, cover.reporting_currency.clone()),
+    ];
+    for (row, (label, value)) in lines.into_iter().enumerate() {
+        let row = row as u32 + 2;
+        worksheet.write_with_format(row, 0, language.tr(label), &label_format)?;
+        worksheet.write(row, 1, value)?;
+    }
+
+    worksheet.set_column_width(0, 24)?;
+    worksheet.set_column_width(1, 48)?;
+
+    Ok(())
+}
+
+/// Every company found in any of the periods by its id, with its latest name.
+fn summary_companies(sheets: &[PeriodSheet]) -> BTreeMap<i64, &str> {
+    let mut companies = BTreeMap::new();
+    for sheet in sheets {
+        for row in &sheet.rows {
+            if let SheetRow::Company(company) = row {
+                companies.insert(company.id, company.name.as_str());
+            }
+        }
+    }
+    companies
+}
+
+/// The closing balance of the company in the period, a credit one below zero.
+fn closing_balance(sheet: &PeriodSheet, id: i64) -> Option<f64> {
+    sheet.rows.iter().find_map(|row| match row {
+        SheetRow::Company(company) if company.id == id => {
+            let [.., debit, credit] = company.values();
+            Some(debit.unwrap_or_default() - credit.unwrap_or_default())
+        }
+        _ => None,
+    })
+}
+
+/// Companies down, periods across with the change over the whole range at the end. Credit
+/// balances are negative and shown in red, so a counterparty switching sides stands out.
+fn write_summary(
+    worksheet: &mut Worksheet,
+    sheets: &[PeriodSheet],
+    number_format: &NumberFormat,
+    language: Language,
+) -> Result<(), XlsxError> {
+    let number_code = number_format.spreadsheet_format();
+    let header_format = Format::new()
+        .set_bold()
+        .set_border(FormatBorder::Thin)
+        .set_background_color("C6E0B4");
+    let cell_format = Format::new().set_border(FormatBorder::Thin);
+    let number_cell_format = cell_format
+        .clone()
+        .set_num_format(format!("{number_code};[Red]-{number_code}"));
+    let bold_format = cell_format.clone().set_bold();
+    let bold_number_format = number_cell_format.clone().set_bold();
+
+    let header = 2;
+    let first_value_col = 2;
+    let change_col = first_value_col + sheets.len() as u16;
+    worksheet.write_with_format(header, 0, language.tr(Message::Id), &header_format)?;
+    worksheet.write_with_format(header, 1, language.tr(Message::Name), &header_format)?;
+    for (i, sheet) in sheets.iter().enumerate() {
+        let col = first_value_col + i as u16;
+        worksheet.write_with_format(header, col, sheet.period.label(), &header_format)?;
+    }
+    worksheet.write_with_format(
+        header,
+        change_col,
+        language.tr(Message::Change),
+        &header_format,
+    )?;
+
+    let companies = summary_companies(sheets);
+    let mut totals = vec![0.; sheets.len() + 1];
+    let mut row = header + 1;
+    for (&id, name) in &companies {
+        worksheet.write_with_format(row, 0, id, &cell_format)?;
+        worksheet.write_with_format(row, 1, *name, &cell_format)?;
+
+        let balances: Vec<_> = sheets
+            .iter()
+            .map(|sheet| closing_balance(sheet, id))
+            .collect();
+        for (i, balance) in balances.iter().enumerate() {
+            let col = first_value_col + i as u16;
+            match balance {
+                Some(balance) => {
+                    worksheet.write_number_with_format(row, col, *balance, &number_cell_format)?
+                }
+                None => worksheet.write_blank(row, col, &number_cell_format)?,
+            };
+        }
+
+        let change = balances.last().copied().flatten().unwrap_or_default()
+            - balances.first().copied().flatten().unwrap_or_default();
+        worksheet.write_number_with_format(row, change_col, change, &number_cell_format)?;
+
+        let values = balances.iter().map(|balance| balance.unwrap_or_default());
+        for (total, value) in totals.iter_mut().zip(values.chain([change])) {
+            *total += value;
+        }
+        row += 1;
+    }
+
+    worksheet.write_blank(row, 0, &bold_format)?;
+    worksheet.write_with_format(row, 1, language.tr(Message::Total), &bold_format)?;
+    for (col, total) in (first_value_col..=change_col).zip(totals) {
+        if companies.is_empty() {
+            worksheet.write_number_with_format(row, col, total, &bold_number_format)?;
+        } else {
+            let sum = Formula::new(format!(
+                "=SUM({})",
+                cell_range(header + 1, col, row - 1, col)
+            ))
+            .set_result(total.to_string());
+            worksheet.write_formula_with_format(row, col, sum, &bold_number_format)?;
+        }
+    }
+
+    // the title goes in after the widths are fitted, it would widen the code column
+    worksheet.autofit();
+    worksheet.write_with_format(
+        0,
+        0,
+        language.tr(Message::ClosingBalances),
+        &Format::new().set_bold(),
+    )?;
+    worksheet.set_freeze_panes(header + 1, first_value_col)?;
+
+    Ok(())
+}
@@ -0,0 +1,165 @@
+use std::{iter::Peekable, str::Chars};
+
+use super::{
+    format::NumberFormat,
+    i18n::{Language, Message},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpressionError {
+    InvalidNumber(String),
+    UnexpectedCharacter(char),
+    Incomplete,
+    UnclosedParenthesis,
+    DivisionByZero,
+}
+
+impl ExpressionError {
+    pub fn message(&self, language: Language) -> String {
+        match self {
+            ExpressionError::InvalidNumber(value) => {
+                language.trf(Message::ExpressionInvalidNumber, &[("value", value)])
+            }
+            ExpressionError::UnexpectedCharacter(c) => {
+                language.trf(Message::ExpressionUnexpected, &[("char", c)])
+            }
+            ExpressionError::Incomplete => language.tr(Message::ExpressionIncomplete).to_string(),
+            ExpressionError::UnclosedParenthesis => {
+                language.tr(Message::ExpressionParenthesis).to_string()
+            }
+            ExpressionError::DivisionByZero => {
+                language.tr(Message::ExpressionDivisionByZero).to_string()
+            }
+        }
+    }
+}
+
+/// Evaluates `+ - * /` with parentheses over numbers written in the number format, so
+/// `1 200,50+350-40*2` works with a decimal comma. A blank field is zero.
+pub fn evaluate(input: &str, number_format: &NumberFormat) -> Result<f64, ExpressionError> {
+    if input.trim().is_empty() {
+        return Ok(0.);
+    }
+
+    let mut parser = Parser {
+        chars: input.chars().peekable(),
+        number_format,
+    };
+    let value = parser.sum()?;
+
+    match parser.next_token() {
+        None => Ok(value),
+        Some(c) => Err(ExpressionError::UnexpectedCharacter(c)),
+    }
+}
+
+/// Whether the input is more than a single number, so a preview of the result is worth showing.
+pub fn is_expression(input: &str) -> bool {
+    input
+        .trim()
+        .trim_start_matches('-')
+        .contains(['+', '-', '*', '/', '(', ')'])
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+    number_format: &'a NumberFormat,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    fn peek_token(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.chars.peek().copied()
+    }
+
+    fn next_token(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.chars.next()
+    }
+
+    fn sum(&mut self) -> Result<f64, ExpressionError> {
+        let mut value = self.product()?;
+
+        while let Some(operator @ ('+' | '-')) = self.peek_token() {
+            self.chars.next();
+            let right = self.product()?;
+            value = if operator == '+' {
+                value + right
+            } else {
+                value - right
+            };
+        }
+
+        Ok(value)
+    }
+
+    fn product(&mut self) -> Result<f64, ExpressionError> {
+        let mut value = self.factor()?;
+
+        while let Some(operator @ ('*' | '/')) = self.peek_token() {
+            self.chars.next();
+            let right = self.factor()?;
+            value = if operator == '*' {
+                value * right
+            } else if right == 0. {
+                return Err(ExpressionError::DivisionByZero);
+            } else {
+                value / right
+            };
+        }
+
+        Ok(value)
+    }
+
+    fn factor(&mut self) -> Result<f64, ExpressionError> {
+        match self.peek_token() {
+            None => Err(ExpressionError::Incomplete),
+            Some('-') => {
+                self.chars.next();
+                Ok(-self.factor()?)
+            }
+            Some('+') => {
+                self.chars.next();
+                self.factor()
+            }
+            Some('(') => {
+                self.chars.next();
+                let value = self.sum()?;
+                match self.next_token() {
+                    Some(')') => Ok(value),
+                    Some(c) => Err(ExpressionError::UnexpectedCharacter(c)),
+                    None => Err(ExpressionError::UnclosedParenthesis),
+                }
+            }
+            Some(c) if is_number_char(c, self.number_format) => self.number(),
+            Some(c) => Err(ExpressionError::UnexpectedCharacter(c)),
+        }
+    }
+
+    /// Digits with the separators of the number format, group separators may be spaces.
+    fn number(&mut self) -> Result<f64, ExpressionError> {
+        let number_format = self.number_format;
+        let mut text = String::new();
+        while let Some(c) = self
+            .chars
+            .next_if(|&c| is_number_char(c, number_format) || c.is_whitespace())
+        {
+            text.push(c);
+        }
+
+        let text = text.trim();
+        number_format
+            .parse(text)
+            .map_err(|_| ExpressionError::InvalidNumber(text.to_string()))
+    }
+}
+
+fn is_number_char(c: char, number_format: &NumberFormat) -> bool {
+    c.is_ascii_digit()
+        || c == number_format.decimal_separator
+        || Some(c) == number_format.group_separator
+}
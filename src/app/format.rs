@@ -0,0 +1,111 @@
+use std::num::ParseFloatError;
+
+use super::i18n::{Language, Message};
+
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug, PartialEq)]
+pub struct NumberFormat {
+    pub decimal_separator: char,
+    pub group_separator: Option<char>,
+    pub decimal_places: usize,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            decimal_separator: ',',
+            group_separator: Some(' '),
+            decimal_places: 2,
+        }
+    }
+}
+
+impl NumberFormat {
+    pub const DECIMAL_SEPARATORS: [char; 2] = [',', '.'];
+    pub const GROUP_SEPARATORS: [Option<char>; 5] =
+        [Some(' '), Some('\u{a0}'), Some('.'), Some(','), None];
+    pub const MAX_DECIMAL_PLACES: usize = 6;
+
+    pub fn format(&self, value: f64) -> String {
+        self.separate(value, format!("{:.*}", self.decimal_places, value.abs()))
+    }
+
+    /// Like `format`, but every digit of the value is kept, so it reads back the same. The
+    /// fraction still has at least `decimal_places` digits.
+    pub fn format_exact(&self, value: f64) -> String {
+        let mut fixed = value.abs().to_string();
+        let places = fixed
+            .split_once('.')
+            .map_or(0, |(_, fraction)| fraction.len());
+        if places < self.decimal_places {
+            if places == 0 {
+                fixed.push('.');
+            }
+            fixed.push_str(&"0".repeat(self.decimal_places - places));
+        }
+        self.separate(value, fixed)
+    }
+
+    /// `fixed` is the absolute value of `value` with a `.` before the fraction.
+    fn separate(&self, value: f64, fixed: String) -> String {
+        let (integer, fraction) = match fixed.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (fixed.as_str(), None),
+        };
+
+        let mut result = String::with_capacity(fixed.len() + integer.len() / 3 + 1);
+
+        // "-0,00" looks silly, so the sign is only kept if something non-zero survived rounding
+        if value < 0. && fixed.bytes().any(|b| matches!(b, b'1'..=b'9')) {
+            result.push('-');
+        }
+
+        for (i, digit) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i) % 3 == 0 {
+                if let Some(separator) = self.group_separator {
+                    result.push(separator);
+                }
+            }
+            result.push(digit);
+        }
+
+        if let Some(fraction) = fraction {
+            result.push(self.decimal_separator);
+            result.push_str(fraction);
+        }
+
+        result
+    }
+
+    pub fn parse(&self, input: &str) -> Result<f64, ParseFloatError> {
+        let normalized: String = input
+            .chars()
+            .filter(|c| !c.is_whitespace() && Some(*c) != self.group_separator)
+            .map(|c| if c == self.decimal_separator { '.' } else { c })
+            .collect();
+
+        normalized.parse()
+    }
+
+    /// Number format code for spreadsheet cells. Separators are always written in the
+    /// `#,##0.00` form, the spreadsheet application localizes them on display.
+    pub fn spreadsheet_format(&self) -> String {
+        let integer = match self.group_separator {
+            Some(_) => "#,##0",
+            None => "0",
+        };
+
+        match self.decimal_places {
+            0 => integer.to_string(),
+            places => format!("{integer}.{}", "0".repeat(places)),
+        }
+    }
+}
+
+pub fn separator_label(separator: Option<char>, language: Language) -> String {
+    match separator {
+        Some(' ') => language.tr(Message::Space).to_string(),
+        Some('\u{a0}') => language.tr(Message::NonBreakingSpace).to_string(),
+        Some(c) => format!("\"{c}\""),
+        None => language.tr(Message::NoSeparator).to_string(),
+    }
+}
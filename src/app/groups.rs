@@ -0,0 +1,397 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{
+    i18n::{Language, Message},
+    model::{Company, Group},
+    TotalRow,
+};
+
+pub enum GroupedItem<T> {
+    Header {
+        id: Option<i64>,
+        name: String,
+        depth: usize,
+        collapsed: bool,
+    },
+    Item(T),
+    Subtotal {
+        name: String,
+        depth: usize,
+        total: TotalRow,
+    },
+}
+
+/// Lays companies out under their groups: a header, the group's own companies, its subgroups and
+/// a subtotal that includes the subgroups. Companies without a group come last under their own
+/// header. When nobody belongs to a group the items are returned as they are.
+pub fn group_items<T: Copy>(
+    items: &[(T, &Company)],
+    groups: &[Group],
+    collapsed: &HashSet<Option<i64>>,
+    language: Language,
+) -> Vec<GroupedItem<T>> {
+    let known: HashSet<_> = groups.iter().map(|group| group.id).collect();
+
+    let mut members: HashMap<Option<i64>, Vec<(T, &Company)>> = HashMap::new();
+    for &(item, company) in items {
+        let group_id = company.group_id.filter(|id| known.contains(id));
+        members.entry(group_id).or_default().push((item, company));
+    }
+
+    if members.keys().all(Option::is_none) {
+        return items
+            .iter()
+            .map(|&(item, _)| GroupedItem::Item(item))
+            .collect();
+    }
+
+    let mut children: HashMap<Option<i64>, Vec<&Group>> = HashMap::new();
+    for group in groups {
+        let parent_id = group.parent_id.filter(|id| known.contains(id));
+        children.entry(parent_id).or_default().push(group);
+    }
+
+    let layout = Layout {
+        members: &members,
+        children: &children,
+        collapsed,
+    };
+
+    let mut result = Vec::new();
+    for group in children.get(&None).into_iter().flatten() {
+        layout.push_group(&mut result, Some(group.id), &group.name, 0);
+    }
+    layout.push_group(&mut result, None, language.tr(Message::NoGroup), 0);
+
+    result
+}
+
+struct Layout<'a, 'b, T> {
+    members: &'b HashMap<Option<i64>, Vec<(T, &'a Company)>>,
+    children: &'b HashMap<Option<i64>, Vec<&'b Group>>,
+    collapsed: &'b HashSet<Option<i64>>,
+}
+
+impl<'a, 'b, T: Copy> Layout<'a, 'b, T> {
+    fn push_group(
+        &self,
+        result: &mut Vec<GroupedItem<T>>,
+        id: Option<i64>,
+        name: &str,
+        depth: usize,
+    ) {
+        let companies = self.subtree_companies(id);
+        if companies.is_empty() {
+            return;
+        }
+
+        let collapsed = self.collapsed.contains(&id);
+        result.push(GroupedItem::Header {
+            id,
+            name: name.to_string(),
+            depth,
+            collapsed,
+        });
+
+        if !collapsed {
+            let own = self.members.get(&id).into_iter().flatten();
+            result.extend(own.map(|&(item, _)| GroupedItem::Item(item)));
+
+            // companies without a group are the root of everything else, not a parent
+            if id.is_some() {
+                for child in self.children.get(&id).into_iter().flatten() {
+                    self.push_group(result, Some(child.id), &child.name, depth + 1);
+                }
+            }
+        }
+
+        result.push(GroupedItem::Subtotal {
+            name: name.to_string(),
+            depth,
+            total: companies.into_iter().collect(),
+        });
+    }
+
+    fn subtree_companies(&self, id: Option<i64>) -> Vec<&'a Company> {
+        let own = self
+            .members
+            .get(&id)
+            .into_iter()
+            .flatten()
+            .map(|&(_, company)| company);
+
+        let nested = id
+            .and_then(|_| self.children.get(&id))
+            .into_iter()
+            .flatten()
+            .flat_map(|child| self.subtree_companies(Some(child.id)));
+
+        own.chain(nested).collect()
+    }
+}
+
+/// Group name with all of its parents, e.g. `Suppliers / Foreign`.
+pub fn group_path(groups: &[Group], id: i64) -> String {
+    let mut names = Vec::new();
+    let mut current = groups.iter().find(|group| group.id == id);
+
+    while let Some(group) = current {
+        // a cycle can't be created from the editor, but the database doesn't forbid one
+        if names.len() > groups.len() {
+            break;
+        }
+        names.push(group.name.as_str());
+        current = group
+            .parent_id
+            .and_then(|parent_id| groups.iter().find(|group| group.id == parent_id));
+    }
+
+    names.reverse();
+    names.join(" / ")
+}
+
+/// Groups whose chain of parents comes back to them, given the parent of every group by its id.
+/// The editor can't make one, a changed parent or an imported file could.
+pub fn parent_cycles(parents: &HashMap<i64, Option<i64>>) -> Vec<i64> {
+    let mut cyclic: Vec<_> = parents
+        .keys()
+        .copied()
+        .filter(|&id| {
+            let mut current = parents.get(&id).copied().flatten();
+            for _ in 0..parents.len() {
+                match current {
+                    Some(parent_id) if parent_id == id => return true,
+                    Some(parent_id) => current = parents.get(&parent_id).copied().flatten(),
+                    None => return false,
+                }
+            }
+            false
+        })
+        .collect();
+    cyclic.sort_unstable();
+    cyclic
+}
+
+fn is_descendant(groups: &[Group], id: i64, ancestor: i64) -> bool {
+    let mut current = Some(id);
+    let mut steps = 0;
+
+    while let Some(group_id) = current {
+        if group_id == ancestor {
+            return true;
+        }
+        if steps > groups.len() {
+            return false;
+        }
+        steps += 1;
+        current = groups
+            .iter()
+            .find(|group| group.id == group_id)
+            .and_then(|group| group.parent_id);
+    }
+
+    false
+}
+
+/// Combo box over all groups plus "no group". `exclude` hides a group and its subgroups, which
+/// is used when picking a parent so a group can't end up inside itself.
+pub fn group_combo(
+    ui: &mut egui::Ui,
+    id_source: &str,
+    selected: &mut Option<i64>,
+    groups: &[Group],
+    exclude: Option<i64>,
+    language: Language,
+) {
+    let label = |id: Option<i64>| match id {
+        Some(id) => group_path(groups, id),
+        None => language.tr(Message::NoGroup).to_string(),
+    };
+
+    let mut options: Vec<_> = groups
+        .iter()
+        .filter(|group| !exclude.is_some_and(|exclude| is_descendant(groups, group.id, exclude)))
+        .map(|group| (Some(group.id), label(Some(group.id))))
+        .collect();
+    options.sort_by(|a, b| a.1.cmp(&b.1));
+
+    egui::ComboBox::from_id_source(id_source)
+        .selected_text(label(*selected))
+        .width(200.0)
+        .show_ui(ui, |ui| {
+            ui.selectable_value(selected, None, label(None));
+            for (id, text) in options {
+                ui.selectable_value(selected, id, text);
+            }
+        });
+}
+
+pub enum GroupAction {
+    Add {
+        name: String,
+        parent_id: Option<i64>,
+    },
+    Edit(Group),
+    Delete(i64),
+}
+
+#[derive(Debug)]
+pub enum GroupError {
+    /// The group would end up among its own subgroups.
+    Cycle(String),
+    Database(sqlx::Error),
+}
+
+impl From<sqlx::Error> for GroupError {
+    fn from(error: sqlx::Error) -> Self {
+        GroupError::Database(error)
+    }
+}
+
+impl GroupError {
+    pub fn describe(&self, language: Language) -> String {
+        match self {
+            GroupError::Cycle(group) => language.trf(Message::GroupCycle, &[("group", group)]),
+            GroupError::Database(error) => error.to_string(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct GroupEditor {
+    pub open: bool,
+    new_name: String,
+    new_parent_id: Option<i64>,
+    editing: Option<Group>,
+    /// The group waiting for its deletion to be confirmed, with the number of its subgroups
+    /// that go with it.
+    confirm_delete: Option<(Group, usize)>,
+}
+
+impl GroupEditor {
+    pub fn editor_ui(
+        &mut self,
+        ctx: &egui::Context,
+        groups: &[Group],
+        language: Language,
+    ) -> Option<GroupAction> {
+        let mut action = None;
+        let mut open = self.open;
+
+        egui::Window::new(language.tr(Message::Groups))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                if let Some((group, subgroups)) = &self.confirm_delete {
+                    ui.label(language.trf(
+                        Message::DeleteGroupConfirm,
+                        &[
+                            ("group", &group_path(groups, group.id)),
+                            ("count", subgroups),
+                        ],
+                    ));
+                    ui.horizontal(|ui| {
+                        if ui.button(language.tr(Message::Delete)).clicked() {
+                            action = self
+                                .confirm_delete
+                                .take()
+                                .map(|(group, _)| GroupAction::Delete(group.id));
+                        }
+                        if ui.button(language.tr(Message::Cancel)).clicked() {
+                            self.confirm_delete = None;
+                        }
+                    });
+                    return;
+                }
+
+                let mut sorted: Vec<_> = groups
+                    .iter()
+                    .map(|group| (group_path(groups, group.id), group))
+                    .collect();
+                sorted.sort_by(|a, b| a.0.cmp(&b.0));
+
+                egui::Grid::new("groups_grid").striped(true).show(ui, |ui| {
+                    for (path, group) in sorted {
+                        match &mut self.editing {
+                            Some(editing) if editing.id == group.id => {
+                                ui.text_edit_singleline(&mut editing.name);
+                                group_combo(
+                                    ui,
+                                    "editing_group_parent",
+                                    &mut editing.parent_id,
+                                    groups,
+                                    Some(editing.id),
+                                    language,
+                                );
+                                if ui
+                                    .add_enabled(
+                                        !editing.name.trim().is_empty(),
+                                        egui::Button::new(language.tr(Message::Save)),
+                                    )
+                                    .clicked()
+                                {
+                                    action = self.editing.take().map(GroupAction::Edit);
+                                }
+                                if ui.button(language.tr(Message::Cancel)).clicked() {
+                                    self.editing = None;
+                                }
+                            }
+                            _ => {
+                                ui.label(path);
+                                ui.label("");
+                                if ui.button(language.tr(Message::Edit)).clicked() {
+                                    self.editing = Some(group.clone());
+                                }
+                                if ui.button(language.tr(Message::Delete)).clicked() {
+                                    let subgroups = groups
+                                        .iter()
+                                        .filter(|other| {
+                                            other.id != group.id
+                                                && is_descendant(groups, other.id, group.id)
+                                        })
+                                        .count();
+                                    if subgroups == 0 {
+                                        action = Some(GroupAction::Delete(group.id));
+                                    } else {
+                                        self.confirm_delete = Some((group.clone(), subgroups));
+                                    }
+                                }
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.new_name)
+                            .hint_text(language.tr(Message::NewGroup)),
+                    );
+                    group_combo(
+                        ui,
+                        "new_group_parent",
+                        &mut self.new_parent_id,
+                        groups,
+                        None,
+                        language,
+                    );
+                    if ui
+                        .add_enabled(
+                            !self.new_name.trim().is_empty(),
+                            egui::Button::new(language.tr(Message::AddGroup)),
+                        )
+                        .clicked()
+                    {
+                        action = Some(GroupAction::Add {
+                            name: std::mem::take(&mut self.new_name).trim().to_string(),
+                            parent_id: self.new_parent_id,
+                        });
+                    }
+                });
+            });
+
+        self.open = open;
+        action
+    }
+}
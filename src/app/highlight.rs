@@ -0,0 +1,271 @@
+use egui::Color32;
+use strum::IntoEnumIterator;
+
+use super::{
+    format::NumberFormat,
+    i18n::{Language, Message},
+    model::{Company, Comparison, HighlightRule, RuleField},
+};
+
+/// How much of the rule color shows through in the table.
+const FILL_ALPHA: u8 = 70;
+
+impl RuleField {
+    pub fn title(self) -> Message {
+        match self {
+            RuleField::OpeningDebit => Message::RuleOpeningDebit,
+            RuleField::OpeningCredit => Message::RuleOpeningCredit,
+            RuleField::DebitTurnover => Message::RuleDebitTurnover,
+            RuleField::CreditTurnover => Message::RuleCreditTurnover,
+            RuleField::Turnover => Message::RuleTurnover,
+            RuleField::ClosingDebit => Message::RuleClosingDebit,
+            RuleField::ClosingCredit => Message::RuleClosingCredit,
+        }
+    }
+
+    fn value(self, company: &Company) -> f64 {
+        let debit = |balance: f64| balance.max(0.);
+        let credit = |balance: f64| (-balance).max(0.);
+
+        match self {
+            RuleField::OpeningDebit => debit(company.remainder_begin_month),
+            RuleField::OpeningCredit => credit(company.remainder_begin_month),
+            RuleField::DebitTurnover => company.debit_turnover,
+            RuleField::CreditTurnover => company.credit_turnover,
+            RuleField::Turnover => company.debit_turnover + company.credit_turnover,
+            RuleField::ClosingDebit => debit(company.remainder_end_month),
+            RuleField::ClosingCredit => credit(company.remainder_end_month),
+        }
+    }
+}
+
+impl Comparison {
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Comparison::Greater => ">",
+            Comparison::GreaterOrEqual => "≥",
+            Comparison::Less => "<",
+            Comparison::LessOrEqual => "≤",
+            Comparison::Equal => "=",
+            Comparison::NotEqual => "≠",
+        }
+    }
+
+    /// Amounts are equal when they match to the kopeck.
+    fn holds(self, left: f64, right: f64) -> bool {
+        let equal = (left - right).abs() < 0.005;
+
+        match self {
+            Comparison::Greater => left > right && !equal,
+            Comparison::GreaterOrEqual => left > right || equal,
+            Comparison::Less => left < right && !equal,
+            Comparison::LessOrEqual => left < right || equal,
+            Comparison::Equal => equal,
+            Comparison::NotEqual => !equal,
+        }
+    }
+}
+
+impl HighlightRule {
+    pub fn matches(&self, company: &Company) -> bool {
+        self.comparison.holds(self.field.value(company), self.value)
+    }
+
+    fn rgb(&self) -> [u8; 3] {
+        parse_color(&self.color).unwrap_or_default()
+    }
+
+    /// The color as `RRGGBB`, whatever was stored, so it can go into a file as it is. Shown
+    /// black in the table when unreadable, so it's black here too.
+    pub fn hex(&self) -> String {
+        color_hex(self.rgb())
+    }
+
+    /// Translucent, so the text and the selection stay readable on top of it.
+    pub fn fill(&self) -> Color32 {
+        let [r, g, b] = self.rgb();
+        Color32::from_rgba_unmultiplied(r, g, b, FILL_ALPHA)
+    }
+
+    fn describe(&self, number_format: &NumberFormat, language: Language) -> String {
+        format!(
+            "{} {} {}",
+            language.tr(self.field.title()),
+            self.comparison.symbol(),
+            number_format.format(self.value)
+        )
+    }
+}
+
+/// The first rule the company matches.
+pub fn matching_rule<'a>(
+    rules: &'a [HighlightRule],
+    company: &Company,
+) -> Option<&'a HighlightRule> {
+    rules.iter().find(|rule| rule.matches(company))
+}
+
+fn parse_color(hex: &str) -> Option<[u8; 3]> {
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+fn color_hex([r, g, b]: [u8; 3]) -> String {
+    format!("{r:02X}{g:02X}{b:02X}")
+}
+
+pub enum RuleAction {
+    /// The id is given by the database.
+    Add(HighlightRule),
+    Edit(HighlightRule),
+    Delete(i64),
+}
+
+/// A rule as it is being typed in.
+struct RuleDraft {
+    field: RuleField,
+    comparison: Comparison,
+    value: String,
+    color: [u8; 3],
+}
+
+impl Default for RuleDraft {
+    fn default() -> Self {
+        Self {
+            field: RuleField::ClosingDebit,
+            comparison: Comparison::Greater,
+            value: String::new(),
+            color: [224, 102, 102],
+        }
+    }
+}
+
+impl RuleDraft {
+    fn from_rule(rule: &HighlightRule, number_format: &NumberFormat) -> Self {
+        Self {
+            field: rule.field,
+            comparison: rule.comparison,
+            value: number_format.format(rule.value),
+            color: rule.rgb(),
+        }
+    }
+
+    fn to_rule(&self, id: i64, number_format: &NumberFormat) -> Option<HighlightRule> {
+        Some(HighlightRule {
+            id,
+            field: self.field,
+            comparison: self.comparison,
+            value: number_format.parse(&self.value).ok()?,
+            color: color_hex(self.color),
+        })
+    }
+
+    fn draft_ui(&mut self, ui: &mut egui::Ui, id_source: &str, language: Language) {
+        egui::ComboBox::from_id_source((id_source, "field"))
+            .selected_text(language.tr(self.field.title()))
+            .show_ui(ui, |ui| {
+                for field in RuleField::iter() {
+                    ui.selectable_value(&mut self.field, field, language.tr(field.title()));
+                }
+            });
+        egui::ComboBox::from_id_source((id_source, "comparison"))
+            .selected_text(self.comparison.symbol())
+            .width(40.0)
+            .show_ui(ui, |ui| {
+                for comparison in Comparison::iter() {
+                    ui.selectable_value(&mut self.comparison, comparison, comparison.symbol());
+                }
+            });
+        ui.add(egui::TextEdit::singleline(&mut self.value).desired_width(90.0));
+        ui.color_edit_button_srgb(&mut self.color);
+    }
+}
+
+#[derive(Default)]
+pub struct RulesEditor {
+    pub open: bool,
+    new: RuleDraft,
+    editing: Option<(i64, RuleDraft)>,
+}
+
+impl RulesEditor {
+    pub fn editor_ui(
+        &mut self,
+        ctx: &egui::Context,
+        rules: &[HighlightRule],
+        number_format: &NumberFormat,
+        language: Language,
+    ) -> Option<RuleAction> {
+        let mut action = None;
+        let mut open = self.open;
+
+        egui::Window::new(language.tr(Message::HighlightRules))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.weak(language.tr(Message::HighlightRulesHint));
+                egui::Grid::new("rules_grid").striped(true).show(ui, |ui| {
+                    for rule in rules {
+                        match &mut self.editing {
+                            Some((id, draft)) if *id == rule.id => {
+                                ui.horizontal(|ui| draft.draft_ui(ui, "editing_rule", language));
+                                let edited = draft.to_rule(rule.id, number_format);
+                                if ui
+                                    .add_enabled(
+                                        edited.is_some(),
+                                        egui::Button::new(language.tr(Message::Save)),
+                                    )
+                                    .clicked()
+                                {
+                                    action = edited.map(RuleAction::Edit);
+                                    self.editing = None;
+                                }
+                                if ui.button(language.tr(Message::Cancel)).clicked() {
+                                    self.editing = None;
+                                }
+                            }
+                            _ => {
+                                ui.horizontal(|ui| {
+                                    let (rect, _) = ui.allocate_exact_size(
+                                        egui::vec2(16.0, 16.0),
+                                        egui::Sense::hover(),
+                                    );
+                                    let [r, g, b] = rule.rgb();
+                                    ui.painter()
+                                        .rect_filled(rect, 2.0, Color32::from_rgb(r, g, b));
+                                    ui.label(rule.describe(number_format, language));
+                                });
+                                if ui.button(language.tr(Message::Edit)).clicked() {
+                                    self.editing =
+                                        Some((rule.id, RuleDraft::from_rule(rule, number_format)));
+                                }
+                                if ui.button(language.tr(Message::Delete)).clicked() {
+                                    action = Some(RuleAction::Delete(rule.id));
+                                }
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    self.new.draft_ui(ui, "new_rule", language);
+                    let rule = self.new.to_rule(0, number_format);
+                    if ui
+                        .add_enabled(
+                            rule.is_some(),
+                            egui::Button::new(language.tr(Message::AddRule)),
+                        )
+                        .clicked()
+                    {
+                        action = rule.map(RuleAction::Add);
+                        self.new.value.clear();
+                    }
+                });
+            });
+
+        self.open = open;
+        action
+    }
+}
@@ -0,0 +1,1019 @@
+#[derive(
+    serde::Deserialize,
+    serde::Serialize,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    strum::EnumIter,
+)]
+pub enum Language {
+    #[default]
+    Ru,
+    En,
+    Az,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Message {
+    File,
+    ExportAs,
+    Excel,
+    Exit,
+    View,
+    DarkTheme,
+    LightTheme,
+    Language,
+    NumberFormat,
+    DecimalSeparator,
+    ThousandsSeparator,
+    DecimalPlaces,
+    Preview,
+    Space,
+    NonBreakingSpace,
+    NoSeparator,
+    Application,
+    NormalMode,
+    AddMode,
+    EditMode,
+    AddRow,
+    Edit,
+    Delete,
+    Save,
+    Cancel,
+    Id,
+    Name,
+    RemainderBeginMonth,
+    Turnover,
+    RemainderEndMonth,
+    Debit,
+    Credit,
+    Total,
+    Dismiss,
+    PastedRows,
+    PasteColumnCount,
+    PasteEmptyName,
+    PasteInvalidNumber,
+    PasteBothRemainders,
+    TableTab,
+    DashboardTab,
+    TopDebtors,
+    TopCreditors,
+    TurnoverComparison,
+    Filter,
+    TaxId,
+    Inn,
+    Voen,
+    TaxIdNotDigits,
+    TaxIdLength,
+    TaxIdChecksum,
+    LegalAddress,
+    ContactPerson,
+    Phone,
+    PhoneFormat,
+    Email,
+    EmailFormat,
+    Iban,
+    IbanFormat,
+    IbanChecksum,
+    Bic,
+    BicFormat,
+    ContractNumber,
+    Note,
+    Pdf,
+    Groups,
+    Group,
+    NoGroup,
+    NewGroup,
+    AddGroup,
+    ShowGroups,
+    SubtotalFor,
+    Account,
+    Accounts,
+    AllAccounts,
+    AccountCode,
+    AddAccount,
+    ActiveAccount,
+    PassiveAccount,
+    ActivePassiveAccount,
+    ConsolidatedReadOnly,
+    Period,
+    NewPeriod,
+    ClosePeriod,
+    ReopenPeriod,
+    ReopenConfirm,
+    PeriodClosedReadOnly,
+    SaveFailed,
+    TotalsOverTime,
+    DebitBalance,
+    CreditBalance,
+    ReconciliationAct,
+    ReconciliationTitle,
+    ReconciliationRange,
+    ReconciliationParties,
+    AccordingTo,
+    Contents,
+    OpeningBalance,
+    TurnoverFor,
+    PeriodTurnover,
+    ClosingBalance,
+    DebtOwed,
+    NoDebt,
+    OnBehalfOf,
+    PeriodFrom,
+    PeriodTo,
+    OurOrganization,
+    Signatory,
+    Stamp,
+    AgingTab,
+    Receivables,
+    Payables,
+    Days0To30,
+    Days31To60,
+    Days61To90,
+    DaysOver90,
+    AgingAsOf,
+    NoPeriods,
+    Currency,
+    CurrencyFormat,
+    ExchangeRates,
+    ReportingCurrency,
+    Rate,
+    AddRate,
+    ImportCsv,
+    RatesCsvHint,
+    RatesImportFailed,
+    Revaluation,
+    RevaluationFor,
+    OpeningRate,
+    ClosingRate,
+    ExchangeDifference,
+    ExchangeGains,
+    ExchangeLosses,
+    MissingRates,
+    DebitLimit,
+    CreditLimit,
+    Limit,
+    Excess,
+    OverLimitAlerts,
+    OverLimitOnly,
+    CreditLimits,
+    NotANumber,
+    RuleOpeningDebit,
+    RuleOpeningCredit,
+    RuleDebitTurnover,
+    RuleCreditTurnover,
+    RuleTurnover,
+    RuleClosingDebit,
+    RuleClosingCredit,
+    HighlightRules,
+    HighlightRulesHint,
+    AddRule,
+    ExpressionInvalidNumber,
+    ExpressionUnexpected,
+    ExpressionIncomplete,
+    ExpressionParenthesis,
+    ExpressionDivisionByZero,
+    EmptyName,
+    InvalidRow,
+    BulkEdit,
+    ZeroTurnovers,
+    BulkField,
+    BulkBefore,
+    BulkAfter,
+    BulkApply,
+    Duplicates,
+    NoDuplicates,
+    SameName,
+    SameTaxId,
+    MergeInto,
+    Merge,
+    MergeConfirm,
+    CompaniesMerged,
+    Tags,
+    AnyTags,
+    NoTags,
+    TagSubtotals,
+    TagSubtotalsHint,
+    Companies,
+    NewTag,
+    AddTag,
+    DetailsTab,
+    Attachments,
+    AttachFile,
+    DropToAttach,
+    NoAttachments,
+    Kilobytes,
+    SaveAs,
+    DropFilesHere,
+    DropToImport,
+    ImportUnknownFormat,
+    ImportFailed,
+    ImportedRows,
+    ImportedRates,
+    ImportWhileEditing,
+    TurnoverSheet,
+    TurnoverSheetFor,
+    PeriodsWorkbook,
+    ExportPeriods,
+    TurnoverSheets,
+    Cover,
+    Summary,
+    PeriodsCount,
+    ClosingBalances,
+    Change,
+    Ods,
+    CliUsage,
+    CliUnknownFormat,
+    CliUnknownPeriod,
+    Html,
+    ConflictDiffers,
+    ConflictDuplicate,
+    ConflictClosedPeriod,
+    NotADataset,
+    UnsupportedDatasetVersion,
+    DatasetConflicts,
+    Json,
+    ImportDataset,
+    DatasetMerge,
+    DatasetMergeHint,
+    DatasetReplace,
+    DatasetReplaceHint,
+    DatasetReplaceWarning,
+    ChooseFile,
+    DatasetImported,
+    MergeCurrenciesDiffer,
+    DeleteAccountConfirm,
+    DatasetClosedPeriods,
+    GroupCycle,
+    DatasetGroupCycles,
+    DeleteGroupConfirm,
+}
+
+impl Language {
+    /// Name of the language in the language itself, so it can be found in the menu whatever
+    /// language is currently selected.
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Language::Ru => "Русский",
+            Language::En => "English",
+            Language::Az => "Azərbaycanca",
+        }
+    }
+
+    /// ISO 639-1 code, for documents that declare their language.
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::Ru => "ru",
+            Language::En => "en",
+            Language::Az => "az",
+        }
+    }
+
+    pub fn tr(self, message: Message) -> &'static str {
+        match self {
+            Language::Ru => ru(message),
+            Language::En => en(message),
+            Language::Az => az(message),
+        }
+    }
+
+    /// Same as [`Language::tr`], but also substitutes `{name}` placeholders of the entry.
+    pub fn trf(self, message: Message, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+        args.iter()
+            .fold(self.tr(message).to_string(), |text, (name, value)| {
+                text.replace(&format!("{{{name}}}"), &value.to_string())
+            })
+    }
+}
+
+fn ru(message: Message) -> &'static str {
+    match message {
+        Message::File => "Файл",
+        Message::ExportAs => "Экспорт в..",
+        Message::Excel => "Excel",
+        Message::Exit => "Выход",
+        Message::View => "Вид",
+        Message::DarkTheme => "🌙 Тёмная",
+        Message::LightTheme => "☀ Светлая",
+        Message::Language => "Язык",
+        Message::NumberFormat => "Формат чисел",
+        Message::DecimalSeparator => "Десятичный разделитель",
+        Message::ThousandsSeparator => "Разделитель разрядов",
+        Message::DecimalPlaces => "Знаков после запятой",
+        Message::Preview => "Пример",
+        Message::Space => "пробел",
+        Message::NonBreakingSpace => "неразрывный пробел",
+        Message::NoSeparator => "нет",
+        Message::Application => "Приложение",
+        Message::NormalMode => "Обычный режим",
+        Message::AddMode => "Режим добавления",
+        Message::EditMode => "Режим редактирования",
+        Message::AddRow => "Добавить строку",
+        Message::Edit => "Изменить",
+        Message::Delete => "Удалить",
+        Message::Save => "Сохранить",
+        Message::Cancel => "Отмена",
+        Message::Id => "Код",
+        Message::Name => "Наименование",
+        Message::RemainderBeginMonth => "Остаток на начало месяца",
+        Message::Turnover => "Оборот за месяц",
+        Message::RemainderEndMonth => "Остаток на конец",
+        Message::Debit => "Дебет",
+        Message::Credit => "Кредит",
+        Message::Total => "ИТОГО",
+        Message::Dismiss => "Скрыть",
+        Message::PastedRows => "Вставлено строк: {count}",
+        Message::PasteColumnCount => {
+            "Строка {line}: ожидалось от 5 до 8 столбцов, найдено {found}"
+        }
+        Message::PasteEmptyName => "Строка {line}: не указано наименование",
+        Message::PasteInvalidNumber => {
+            "Строка {line}, столбец {column}: «{value}» не является числом"
+        }
+        Message::PasteBothRemainders => {
+            "Строка {line}: остаток на начало указан и по дебету, и по кредиту"
+        }
+        Message::TableTab => "Таблица",
+        Message::DashboardTab => "Панель",
+        Message::TopDebtors => "Крупнейшие дебиторы",
+        Message::TopCreditors => "Крупнейшие кредиторы",
+        Message::TurnoverComparison => "Обороты по дебету и кредиту",
+        Message::Filter => "Фильтр",
+        Message::TaxId => "ИНН / VÖEN",
+        Message::Inn => "ИНН",
+        Message::Voen => "VÖEN",
+        Message::TaxIdNotDigits => "Должен состоять только из цифр",
+        Message::TaxIdLength => "Должен содержать 10 или 12 цифр",
+        Message::TaxIdChecksum => "Неверное контрольное число",
+        Message::LegalAddress => "Юридический адрес",
+        Message::ContactPerson => "Контактное лицо",
+        Message::Phone => "Телефон",
+        Message::PhoneFormat => "Неверный номер телефона",
+        Message::Email => "Эл. почта",
+        Message::EmailFormat => "Неверный адрес эл. почты",
+        Message::Iban => "IBAN",
+        Message::IbanFormat => "Неверный формат IBAN",
+        Message::IbanChecksum => "Неверная контрольная сумма IBAN",
+        Message::Bic => "БИК / SWIFT",
+        Message::BicFormat => "Неверный формат БИК / SWIFT",
+        Message::ContractNumber => "Номер договора",
+        Message::Note => "Примечание",
+        Message::Pdf => "PDF",
+        Message::Groups => "Группы",
+        Message::Group => "Группа",
+        Message::NoGroup => "Без группы",
+        Message::NewGroup => "Новая группа",
+        Message::AddGroup => "Добавить группу",
+        Message::ShowGroups => "Группировать по группам",
+        Message::SubtotalFor => "Итого: {group}",
+        Message::Account => "Счёт",
+        Message::Accounts => "План счетов",
+        Message::AllAccounts => "Все счета (сводно)",
+        Message::AccountCode => "Код",
+        Message::AddAccount => "Добавить счёт",
+        Message::ActiveAccount => "Активный",
+        Message::PassiveAccount => "Пассивный",
+        Message::ActivePassiveAccount => "Активно-пассивный",
+        Message::ConsolidatedReadOnly => {
+            "Сводные остатки только для просмотра, выберите счёт для изменения"
+        }
+        Message::Period => "Период",
+        Message::NewPeriod => "Новый период",
+        Message::ClosePeriod => "Закрыть период",
+        Message::ReopenPeriod => "Открыть период",
+        Message::ReopenConfirm => "Период {period} закрыт. Открыть его для изменений? Это будет записано в журнал периодов.",
+        Message::PeriodClosedReadOnly => "Период закрыт, остатки изменить нельзя",
+        Message::SaveFailed => "Не удалось сохранить: {error}",
+        Message::TotalsOverTime => "Итоги по периодам",
+        Message::DebitBalance => "Дебетовое сальдо",
+        Message::CreditBalance => "Кредитовое сальдо",
+        Message::ReconciliationAct => "Акт сверки",
+        Message::ReconciliationTitle => "Акт сверки взаимных расчётов",
+        Message::ReconciliationRange => "за период с {from} по {to}",
+        Message::ReconciliationParties => "между {organization} и {counterparty}",
+        Message::AccordingTo => "По данным {party}",
+        Message::Contents => "Содержание",
+        Message::OpeningBalance => "Сальдо начальное",
+        Message::TurnoverFor => "Обороты за {period}",
+        Message::PeriodTurnover => "Обороты за период",
+        Message::ClosingBalance => "Сальдо конечное",
+        Message::DebtOwed => "На {date} задолженность {debtor} перед {creditor} составляет {amount}",
+        Message::NoDebt => "На {date} задолженность отсутствует",
+        Message::OnBehalfOf => "От {party}",
+        Message::PeriodFrom => "С",
+        Message::PeriodTo => "По",
+        Message::OurOrganization => "Наша организация",
+        Message::Signatory => "Подписант",
+        Message::Stamp => "М.П.",
+        Message::AgingTab => "Сроки задолженности",
+        Message::Receivables => "Дебиторская задолженность",
+        Message::Payables => "Кредиторская задолженность",
+        Message::Days0To30 => "0–30 дн.",
+        Message::Days31To60 => "31–60 дн.",
+        Message::Days61To90 => "61–90 дн.",
+        Message::DaysOver90 => "90+ дн.",
+        Message::AgingAsOf => "Сроки задолженности на {date}",
+        Message::NoPeriods => "Нет периодов",
+        Message::Currency => "Валюта",
+        Message::CurrencyFormat => "Неверный код валюты, например USD",
+        Message::ExchangeRates => "Курсы валют",
+        Message::ReportingCurrency => "Валюта отчётности",
+        Message::Rate => "Курс",
+        Message::AddRate => "Добавить курс",
+        Message::ImportCsv => "Импорт из CSV",
+        Message::RatesCsvHint => "Строки вида: валюта;ММ.ГГГГ;курс",
+        Message::RatesImportFailed => "Не удалось прочитать строку {line} файла курсов",
+        Message::Revaluation => "Переоценка",
+        Message::RevaluationFor => "Переоценка с {from} по {to}",
+        Message::OpeningRate => "Курс на начало",
+        Message::ClosingRate => "Курс на конец",
+        Message::ExchangeDifference => "Курсовая разница",
+        Message::ExchangeGains => "Положительные курсовые разницы",
+        Message::ExchangeLosses => "Отрицательные курсовые разницы",
+        Message::MissingRates => "Нет курса за период для: {currencies}, суммы взяты один к одному",
+        Message::DebitLimit => "Макс. дебетовое сальдо",
+        Message::CreditLimit => "Макс. аванс",
+        Message::Limit => "Лимит",
+        Message::Excess => "Превышение",
+        Message::OverLimitAlerts => "Превышение лимитов: {count}",
+        Message::OverLimitOnly => "Только сверх лимита",
+        Message::CreditLimits => "Лимиты задолженности",
+        Message::NotANumber => "Не является числом",
+        Message::RuleOpeningDebit => "Остаток на начало, дебет",
+        Message::RuleOpeningCredit => "Остаток на начало, кредит",
+        Message::RuleDebitTurnover => "Оборот по дебету",
+        Message::RuleCreditTurnover => "Оборот по кредиту",
+        Message::RuleTurnover => "Оборот по дебету и кредиту",
+        Message::RuleClosingDebit => "Остаток на конец, дебет",
+        Message::RuleClosingCredit => "Остаток на конец, кредит",
+        Message::HighlightRules => "Правила подсветки",
+        Message::HighlightRulesHint => "Строка окрашивается цветом первого подходящего правила",
+        Message::AddRule => "Добавить правило",
+        Message::ExpressionInvalidNumber => "«{value}» — не число",
+        Message::ExpressionUnexpected => "Неожиданный символ «{char}»",
+        Message::ExpressionIncomplete => "Выражение не закончено",
+        Message::ExpressionParenthesis => "Не закрыта скобка",
+        Message::ExpressionDivisionByZero => "Деление на ноль",
+        Message::EmptyName => "Не указано наименование",
+        Message::InvalidRow => "«{name}»: {error}",
+        Message::BulkEdit => "Массовое изменение",
+        Message::ZeroTurnovers => "Обнулить обороты",
+        Message::BulkField => "Что изменить",
+        Message::BulkBefore => "Сейчас",
+        Message::BulkAfter => "Станет",
+        Message::BulkApply => "Применить к строкам: {count}",
+        Message::Duplicates => "Поиск дубликатов",
+        Message::NoDuplicates => "Дубликаты не найдены",
+        Message::SameName => "одинаковое наименование",
+        Message::SameTaxId => "одинаковый ИНН",
+        Message::MergeInto => "Объединить в «{name}»",
+        Message::Merge => "Объединить",
+        Message::MergeConfirm => "Остатки и обороты «{merged}» будут перенесены в «{kept}», а сама «{merged}» удалена. Отменить это нельзя.",
+        Message::CompaniesMerged => "«{merged}» объединена с «{kept}»: перенесено остатков {moved}, сложено {combined}",
+        Message::Tags => "Теги",
+        Message::AnyTags => "Сбросить",
+        Message::NoTags => "Без тегов",
+        Message::TagSubtotals => "Итоги по тегам",
+        Message::TagSubtotalsHint => "Компания с несколькими тегами входит в итог каждого из них",
+        Message::Companies => "Компаний",
+        Message::NewTag => "Новый тег",
+        Message::AddTag => "Добавить тег",
+        Message::DetailsTab => "Реквизиты",
+        Message::Attachments => "Файлы",
+        Message::AttachFile => "Прикрепить файл…",
+        Message::DropToAttach => "или перетащите файлы в окно",
+        Message::NoAttachments => "Файлов нет",
+        Message::Kilobytes => "{size} КБ",
+        Message::SaveAs => "Сохранить как…",
+        Message::DropFilesHere => "Отпустите, чтобы прикрепить к «{name}»",
+        Message::DropToImport => "Отпустите, чтобы импортировать таблицу Excel или CSV",
+        Message::ImportUnknownFormat => "ожидалась таблица xlsx, xls, ods или csv",
+        Message::ImportFailed => "Не удалось импортировать {file}: {error}",
+        Message::ImportedRows => "{file}: строк к добавлению: {count}, проверьте и сохраните",
+        Message::ImportedRates => "{file}: импортировано курсов: {count}",
+        Message::ImportWhileEditing => "{file} не импортирован: сначала завершите редактирование",
+        Message::TurnoverSheet => "Оборотно-сальдовая ведомость",
+        Message::TurnoverSheetFor => "Оборотно-сальдовая ведомость за период с {from} по {to}",
+        Message::PeriodsWorkbook => "Excel по периодам…",
+        Message::ExportPeriods => "Выгрузить периодов: {count}",
+        Message::TurnoverSheets => "Оборотно-сальдовые ведомости по месяцам",
+        Message::Cover => "Титул",
+        Message::Summary => "Сводка",
+        Message::PeriodsCount => "Периодов",
+        Message::ClosingBalances => "Сальдо на конец месяца: дебетовое со знаком плюс, кредитовое со знаком минус",
+        Message::Change => "Изменение",
+        Message::Ods => "ODS (LibreOffice)",
+        Message::CliUsage => "Использование: company_calc export <файл.xlsx|файл.ods|файл.pdf|файл.html|файл.json> [ММ.ГГГГ]\n               company_calc import <файл.json> merge|replace",
+        Message::CliUnknownFormat => "Формат файла определяется расширением: xlsx, ods, pdf, html или json",
+        Message::CliUnknownPeriod => "Нет периода {period}",
+        Message::Html => "HTML",
+        Message::ConflictDiffers => "{record}: в базе под этим ключом другая запись",
+        Message::ConflictDuplicate => "{record}: значение {field} уже занято другой записью",
+        Message::ConflictClosedPeriod => "{record}: период закрыт",
+        Message::NotADataset => "Это не файл данных приложения",
+        Message::UnsupportedDatasetVersion => "Файл версии {version} записан более новой версией приложения, поддерживается до {supported}",
+        Message::DatasetConflicts => "Конфликтов: {count}, ничего не импортировано",
+        Message::Json => "JSON (все данные)",
+        Message::ImportDataset => "Импорт данных из JSON…",
+        Message::DatasetMerge => "Добавить к имеющимся",
+        Message::DatasetMergeHint => "Совпадающие записи пропускаются, при расхождении по id ничего не импортируется",
+        Message::DatasetReplace => "Заменить все данные",
+        Message::DatasetReplaceHint => "Все данные удаляются и загружаются из файла",
+        Message::DatasetReplaceWarning => "Все компании, периоды и остатки будут удалены и загружены из файла. Это нельзя отменить. Закрытые периоды нужно сначала открыть заново.",
+        Message::ChooseFile => "Выбрать файл…",
+        Message::DatasetImported => "{file}: добавлено компаний {companies}, остатков {balances}, прочих записей {other}",
+        Message::MergeCurrenciesDiffer => "Компании ведутся в разных валютах ({kept} и {merged}), их остатки нельзя сложить",
+        Message::DeleteAccountConfirm => "Удалить счёт {account}? Остатки по нему будут удалены вместе с ним",
+        Message::DatasetClosedPeriods => "Закрытые периоды {periods} были бы удалены. Сначала откройте их заново",
+        Message::GroupCycle => "Группа «{group}» не может входить в саму себя или в свою подгруппу",
+        Message::DatasetGroupCycles => "Группы {groups} входят сами в себя",
+        Message::DeleteGroupConfirm => "Удалить группу «{group}» вместе с подгруппами ({count})? Их компании останутся без группы",
+    }
+}
+
+fn en(message: Message) -> &'static str {
+    match message {
+        Message::File => "File",
+        Message::ExportAs => "Export as..",
+        Message::Excel => "Excel",
+        Message::Exit => "Exit",
+        Message::View => "View",
+        Message::DarkTheme => "🌙 Dark",
+        Message::LightTheme => "☀ Light",
+        Message::Language => "Language",
+        Message::NumberFormat => "Number format",
+        Message::DecimalSeparator => "Decimal separator",
+        Message::ThousandsSeparator => "Thousands separator",
+        Message::DecimalPlaces => "Decimal places",
+        Message::Preview => "Preview",
+        Message::Space => "space",
+        Message::NonBreakingSpace => "non-breaking space",
+        Message::NoSeparator => "none",
+        Message::Application => "Application",
+        Message::NormalMode => "Normal mode",
+        Message::AddMode => "Add mode",
+        Message::EditMode => "Edit mode",
+        Message::AddRow => "Add row",
+        Message::Edit => "Edit",
+        Message::Delete => "Delete",
+        Message::Save => "Save",
+        Message::Cancel => "Cancel",
+        Message::Id => "Code",
+        Message::Name => "Name",
+        Message::RemainderBeginMonth => "Balance at beginning of month",
+        Message::Turnover => "Turnover for the month",
+        Message::RemainderEndMonth => "Balance at end of month",
+        Message::Debit => "Debit",
+        Message::Credit => "Credit",
+        Message::Total => "TOTAL",
+        Message::Dismiss => "Dismiss",
+        Message::PastedRows => "Pasted rows: {count}",
+        Message::PasteColumnCount => "Line {line}: expected 5 to 8 columns, found {found}",
+        Message::PasteEmptyName => "Line {line}: name is missing",
+        Message::PasteInvalidNumber => "Line {line}, column {column}: \"{value}\" is not a number",
+        Message::PasteBothRemainders => {
+            "Line {line}: opening balance is filled in both debit and credit"
+        }
+        Message::TableTab => "Table",
+        Message::DashboardTab => "Dashboard",
+        Message::TopDebtors => "Top debtors",
+        Message::TopCreditors => "Top creditors",
+        Message::TurnoverComparison => "Debit and credit turnover",
+        Message::Filter => "Filter",
+        Message::TaxId => "Tax ID (INN / VÖEN)",
+        Message::Inn => "INN",
+        Message::Voen => "VÖEN",
+        Message::TaxIdNotDigits => "Must contain digits only",
+        Message::TaxIdLength => "Must be 10 or 12 digits long",
+        Message::TaxIdChecksum => "Invalid check digit",
+        Message::LegalAddress => "Legal address",
+        Message::ContactPerson => "Contact person",
+        Message::Phone => "Phone",
+        Message::PhoneFormat => "Invalid phone number",
+        Message::Email => "Email",
+        Message::EmailFormat => "Invalid email address",
+        Message::Iban => "IBAN",
+        Message::IbanFormat => "Invalid IBAN format",
+        Message::IbanChecksum => "Invalid IBAN check digits",
+        Message::Bic => "BIC / SWIFT",
+        Message::BicFormat => "Invalid BIC / SWIFT format",
+        Message::ContractNumber => "Contract number",
+        Message::Note => "Note",
+        Message::Pdf => "PDF",
+        Message::Groups => "Groups",
+        Message::Group => "Group",
+        Message::NoGroup => "No group",
+        Message::NewGroup => "New group",
+        Message::AddGroup => "Add group",
+        Message::ShowGroups => "Group by groups",
+        Message::SubtotalFor => "Total: {group}",
+        Message::Account => "Account",
+        Message::Accounts => "Chart of accounts",
+        Message::AllAccounts => "All accounts (consolidated)",
+        Message::AccountCode => "Code",
+        Message::AddAccount => "Add account",
+        Message::ActiveAccount => "Active",
+        Message::PassiveAccount => "Passive",
+        Message::ActivePassiveAccount => "Active-passive",
+        Message::ConsolidatedReadOnly => {
+            "Consolidated balances are read only, pick an account to change them"
+        }
+        Message::Period => "Period",
+        Message::NewPeriod => "New period",
+        Message::ClosePeriod => "Close period",
+        Message::ReopenPeriod => "Reopen period",
+        Message::ReopenConfirm => "Period {period} is closed. Open it for changes? This will be recorded in the period log.",
+        Message::PeriodClosedReadOnly => "The period is closed, balances can not be changed",
+        Message::SaveFailed => "Could not save: {error}",
+        Message::TotalsOverTime => "Totals over time",
+        Message::DebitBalance => "Debit balance",
+        Message::CreditBalance => "Credit balance",
+        Message::ReconciliationAct => "Reconciliation statement",
+        Message::ReconciliationTitle => "Statement of mutual settlements reconciliation",
+        Message::ReconciliationRange => "for the period from {from} to {to}",
+        Message::ReconciliationParties => "between {organization} and {counterparty}",
+        Message::AccordingTo => "According to {party}",
+        Message::Contents => "Description",
+        Message::OpeningBalance => "Opening balance",
+        Message::TurnoverFor => "Turnover for {period}",
+        Message::PeriodTurnover => "Turnover for the period",
+        Message::ClosingBalance => "Closing balance",
+        Message::DebtOwed => "As of {date} {debtor} owes {creditor} {amount}",
+        Message::NoDebt => "As of {date} there is no debt",
+        Message::OnBehalfOf => "On behalf of {party}",
+        Message::PeriodFrom => "From",
+        Message::PeriodTo => "To",
+        Message::OurOrganization => "Our organization",
+        Message::Signatory => "Signatory",
+        Message::Stamp => "(seal)",
+        Message::AgingTab => "Aging",
+        Message::Receivables => "Receivables",
+        Message::Payables => "Payables",
+        Message::Days0To30 => "0–30 days",
+        Message::Days31To60 => "31–60 days",
+        Message::Days61To90 => "61–90 days",
+        Message::DaysOver90 => "90+ days",
+        Message::AgingAsOf => "Aging as of {date}",
+        Message::NoPeriods => "No periods",
+        Message::Currency => "Currency",
+        Message::CurrencyFormat => "Invalid currency code, e.g. USD",
+        Message::ExchangeRates => "Exchange rates",
+        Message::ReportingCurrency => "Reporting currency",
+        Message::Rate => "Rate",
+        Message::AddRate => "Add rate",
+        Message::ImportCsv => "Import CSV",
+        Message::RatesCsvHint => "Lines like: currency;MM.YYYY;rate",
+        Message::RatesImportFailed => "Line {line} of the rates file could not be read",
+        Message::Revaluation => "Revaluation",
+        Message::RevaluationFor => "Revaluation from {from} to {to}",
+        Message::OpeningRate => "Opening rate",
+        Message::ClosingRate => "Closing rate",
+        Message::ExchangeDifference => "Exchange difference",
+        Message::ExchangeGains => "Exchange gains",
+        Message::ExchangeLosses => "Exchange losses",
+        Message::MissingRates => "No rate for the period for: {currencies}, amounts are taken one to one",
+        Message::DebitLimit => "Max debit balance",
+        Message::CreditLimit => "Max advance",
+        Message::Limit => "Limit",
+        Message::Excess => "Excess",
+        Message::OverLimitAlerts => "Over limit: {count}",
+        Message::OverLimitOnly => "Over limit only",
+        Message::CreditLimits => "Credit limits",
+        Message::NotANumber => "Not a number",
+        Message::RuleOpeningDebit => "Opening balance, debit",
+        Message::RuleOpeningCredit => "Opening balance, credit",
+        Message::RuleDebitTurnover => "Debit turnover",
+        Message::RuleCreditTurnover => "Credit turnover",
+        Message::RuleTurnover => "Debit and credit turnover",
+        Message::RuleClosingDebit => "Closing balance, debit",
+        Message::RuleClosingCredit => "Closing balance, credit",
+        Message::HighlightRules => "Highlighting rules",
+        Message::HighlightRulesHint => "A row takes the color of the first rule it matches",
+        Message::AddRule => "Add rule",
+        Message::ExpressionInvalidNumber => "“{value}” is not a number",
+        Message::ExpressionUnexpected => "Unexpected “{char}”",
+        Message::ExpressionIncomplete => "The expression is incomplete",
+        Message::ExpressionParenthesis => "A parenthesis is not closed",
+        Message::ExpressionDivisionByZero => "Division by zero",
+        Message::EmptyName => "Name is missing",
+        Message::InvalidRow => "“{name}”: {error}",
+        Message::BulkEdit => "Bulk edit",
+        Message::ZeroTurnovers => "Zero out turnovers",
+        Message::BulkField => "Change",
+        Message::BulkBefore => "Now",
+        Message::BulkAfter => "Will be",
+        Message::BulkApply => "Apply to {count} rows",
+        Message::Duplicates => "Find duplicates",
+        Message::NoDuplicates => "No duplicates found",
+        Message::SameName => "same name",
+        Message::SameTaxId => "same tax ID",
+        Message::MergeInto => "Merge into “{name}”",
+        Message::Merge => "Merge",
+        Message::MergeConfirm => "Balances and turnovers of “{merged}” will go to “{kept}” and “{merged}” will be deleted. This can’t be undone.",
+        Message::CompaniesMerged => "“{merged}” merged into “{kept}”: {moved} balances moved, {combined} added up",
+        Message::Tags => "Tags",
+        Message::AnyTags => "Clear",
+        Message::NoTags => "No tags",
+        Message::TagSubtotals => "Subtotals by tag",
+        Message::TagSubtotalsHint => "A company with several tags counts in each of them",
+        Message::Companies => "Companies",
+        Message::NewTag => "New tag",
+        Message::AddTag => "Add tag",
+        Message::DetailsTab => "Details",
+        Message::Attachments => "Attachments",
+        Message::AttachFile => "Attach file…",
+        Message::DropToAttach => "or drop files onto the window",
+        Message::NoAttachments => "No attachments",
+        Message::Kilobytes => "{size} KB",
+        Message::SaveAs => "Save as…",
+        Message::DropFilesHere => "Drop to attach to “{name}”",
+        Message::DropToImport => "Drop to import an Excel or CSV table",
+        Message::ImportUnknownFormat => "expected an xlsx, xls, ods or csv table",
+        Message::ImportFailed => "Could not import {file}: {error}",
+        Message::ImportedRows => "{file}: {count} rows to add, check them and save",
+        Message::ImportedRates => "{file}: {count} rates imported",
+        Message::ImportWhileEditing => "{file} was not imported, finish editing first",
+        Message::TurnoverSheet => "Trial balance",
+        Message::TurnoverSheetFor => "Trial balance from {from} to {to}",
+        Message::PeriodsWorkbook => "Excel by periods…",
+        Message::ExportPeriods => "Export {count} periods",
+        Message::TurnoverSheets => "Monthly trial balances",
+        Message::Cover => "Cover",
+        Message::Summary => "Summary",
+        Message::PeriodsCount => "Periods",
+        Message::ClosingBalances => "Closing balances: debit positive, credit negative",
+        Message::Change => "Change",
+        Message::Ods => "ODS (LibreOffice)",
+        Message::CliUsage => "Usage: company_calc export <file.xlsx|file.ods|file.pdf|file.html|file.json> [MM.YYYY]\n       company_calc import <file.json> merge|replace",
+        Message::CliUnknownFormat => "The format is taken from the extension: xlsx, ods, pdf, html or json",
+        Message::CliUnknownPeriod => "There is no period {period}",
+        Message::Html => "HTML",
+        Message::ConflictDiffers => "{record}: the database has a different record under this key",
+        Message::ConflictDuplicate => "{record}: {field} is already taken by another record",
+        Message::ConflictClosedPeriod => "{record}: the period is closed",
+        Message::NotADataset => "This is not a dataset file of the application",
+        Message::UnsupportedDatasetVersion => "The file is of version {version} from a newer version of the application, up to {supported} is supported",
+        Message::DatasetConflicts => "{count} conflicts, nothing was imported",
+        Message::Json => "JSON (all data)",
+        Message::ImportDataset => "Import data from JSON…",
+        Message::DatasetMerge => "Merge into the current data",
+        Message::DatasetMergeHint => "Matching records are skipped, if any id differs nothing is imported",
+        Message::DatasetReplace => "Replace all data",
+        Message::DatasetReplaceHint => "All data is deleted and loaded from the file",
+        Message::DatasetReplaceWarning => "Every company, period and balance will be deleted and loaded from the file. This can’t be undone. Closed periods have to be reopened first.",
+        Message::ChooseFile => "Choose file…",
+        Message::DatasetImported => "{file}: {companies} companies, {balances} balances and {other} other records added",
+        Message::MergeCurrenciesDiffer => "The companies are kept in different currencies ({kept} and {merged}), their balances can't be added up",
+        Message::DeleteAccountConfirm => "Delete the account {account}? Its balances are deleted with it",
+        Message::DatasetClosedPeriods => "The closed periods {periods} would be deleted. Reopen them first",
+        Message::GroupCycle => "The group “{group}” can’t go into itself or one of its subgroups",
+        Message::DatasetGroupCycles => "The groups {groups} are nested in themselves",
+        Message::DeleteGroupConfirm => "Delete the group “{group}” with its {count} subgroups? Their companies are left without a group",
+    }
+}
+
+fn az(message: Message) -> &'static str {
+    match message {
+        Message::File => "Fayl",
+        Message::ExportAs => "İxrac et..",
+        Message::Excel => "Excel",
+        Message::Exit => "Çıxış",
+        Message::View => "Görünüş",
+        Message::DarkTheme => "🌙 Tünd",
+        Message::LightTheme => "☀ Açıq",
+        Message::Language => "Dil",
+        Message::NumberFormat => "Rəqəm formatı",
+        Message::DecimalSeparator => "Onluq ayırıcı",
+        Message::ThousandsSeparator => "Minlik ayırıcı",
+        Message::DecimalPlaces => "Onluq işarələrin sayı",
+        Message::Preview => "Nümunə",
+        Message::Space => "boşluq",
+        Message::NonBreakingSpace => "qırılmaz boşluq",
+        Message::NoSeparator => "yoxdur",
+        Message::Application => "Proqram",
+        Message::NormalMode => "Adi rejim",
+        Message::AddMode => "Əlavə etmə rejimi",
+        Message::EditMode => "Redaktə rejimi",
+        Message::AddRow => "Sətir əlavə et",
+        Message::Edit => "Redaktə et",
+        Message::Delete => "Sil",
+        Message::Save => "Yadda saxla",
+        Message::Cancel => "Ləğv et",
+        Message::Id => "Kod",
+        Message::Name => "Adı",
+        Message::RemainderBeginMonth => "Ayın əvvəlinə qalıq",
+        Message::Turnover => "Ay ərzində dövriyyə",
+        Message::RemainderEndMonth => "Ayın sonuna qalıq",
+        Message::Debit => "Debet",
+        Message::Credit => "Kredit",
+        Message::Total => "CƏMİ",
+        Message::Dismiss => "Gizlət",
+        Message::PastedRows => "Əlavə edilən sətirlər: {count}",
+        Message::PasteColumnCount => {
+            "Sətir {line}: 5-dən 8-ə qədər sütun gözlənilirdi, {found} tapıldı"
+        }
+        Message::PasteEmptyName => "Sətir {line}: ad göstərilməyib",
+        Message::PasteInvalidNumber => "Sətir {line}, sütun {column}: \"{value}\" rəqəm deyil",
+        Message::PasteBothRemainders => {
+            "Sətir {line}: əvvələ qalıq həm debet, həm kredit üzrə göstərilib"
+        }
+        Message::TableTab => "Cədvəl",
+        Message::DashboardTab => "İdarə paneli",
+        Message::TopDebtors => "Ən böyük debitorlar",
+        Message::TopCreditors => "Ən böyük kreditorlar",
+        Message::TurnoverComparison => "Debet və kredit dövriyyəsi",
+        Message::Filter => "Filtr",
+        Message::TaxId => "VÖEN / İNN",
+        Message::Inn => "İNN",
+        Message::Voen => "VÖEN",
+        Message::TaxIdNotDigits => "Yalnız rəqəmlərdən ibarət olmalıdır",
+        Message::TaxIdLength => "10 və ya 12 rəqəmdən ibarət olmalıdır",
+        Message::TaxIdChecksum => "Yoxlama rəqəmi səhvdir",
+        Message::LegalAddress => "Hüquqi ünvan",
+        Message::ContactPerson => "Əlaqə şəxsi",
+        Message::Phone => "Telefon",
+        Message::PhoneFormat => "Telefon nömrəsi səhvdir",
+        Message::Email => "E-poçt",
+        Message::EmailFormat => "E-poçt ünvanı səhvdir",
+        Message::Iban => "IBAN",
+        Message::IbanFormat => "IBAN formatı səhvdir",
+        Message::IbanChecksum => "IBAN yoxlama rəqəmləri səhvdir",
+        Message::Bic => "BIC / SWIFT",
+        Message::BicFormat => "BIC / SWIFT formatı səhvdir",
+        Message::ContractNumber => "Müqavilə nömrəsi",
+        Message::Note => "Qeyd",
+        Message::Pdf => "PDF",
+        Message::Groups => "Qruplar",
+        Message::Group => "Qrup",
+        Message::NoGroup => "Qrupsuz",
+        Message::NewGroup => "Yeni qrup",
+        Message::AddGroup => "Qrup əlavə et",
+        Message::ShowGroups => "Qruplar üzrə qruplaşdır",
+        Message::SubtotalFor => "Cəmi: {group}",
+        Message::Account => "Hesab",
+        Message::Accounts => "Hesablar planı",
+        Message::AllAccounts => "Bütün hesablar (icmal)",
+        Message::AccountCode => "Kod",
+        Message::AddAccount => "Hesab əlavə et",
+        Message::ActiveAccount => "Aktiv",
+        Message::PassiveAccount => "Passiv",
+        Message::ActivePassiveAccount => "Aktiv-passiv",
+        Message::ConsolidatedReadOnly => {
+            "İcmal qalıqlar yalnız baxış üçündür, dəyişmək üçün hesab seçin"
+        }
+        Message::Period => "Dövr",
+        Message::NewPeriod => "Yeni dövr",
+        Message::ClosePeriod => "Dövrü bağla",
+        Message::ReopenPeriod => "Dövrü yenidən aç",
+        Message::ReopenConfirm => "{period} dövrü bağlıdır. Dəyişikliklər üçün açılsın? Bu, dövrlər jurnalında qeyd ediləcək.",
+        Message::PeriodClosedReadOnly => "Dövr bağlıdır, qalıqları dəyişmək olmaz",
+        Message::SaveFailed => "Yadda saxlamaq alınmadı: {error}",
+        Message::TotalsOverTime => "Dövrlər üzrə yekunlar",
+        Message::DebitBalance => "Debet saldo",
+        Message::CreditBalance => "Kredit saldo",
+        Message::ReconciliationAct => "Üzləşmə aktı",
+        Message::ReconciliationTitle => "Qarşılıqlı hesablaşmaların üzləşmə aktı",
+        Message::ReconciliationRange => "{from} - {to} dövrü üzrə",
+        Message::ReconciliationParties => "{organization} və {counterparty} arasında",
+        Message::AccordingTo => "{party} məlumatlarına görə",
+        Message::Contents => "Məzmun",
+        Message::OpeningBalance => "İlkin saldo",
+        Message::TurnoverFor => "{period} üzrə dövriyyə",
+        Message::PeriodTurnover => "Dövr üzrə dövriyyə",
+        Message::ClosingBalance => "Son saldo",
+        Message::DebtOwed => "{date} tarixinə {debtor} {creditor} qarşısında borcu {amount} təşkil edir",
+        Message::NoDebt => "{date} tarixinə borc yoxdur",
+        Message::OnBehalfOf => "{party} adından",
+        Message::PeriodFrom => "Başlanğıc",
+        Message::PeriodTo => "Son",
+        Message::OurOrganization => "Təşkilatımız",
+        Message::Signatory => "İmzalayan",
+        Message::Stamp => "M.Y.",
+        Message::AgingTab => "Borcların müddəti",
+        Message::Receivables => "Debitor borcu",
+        Message::Payables => "Kreditor borcu",
+        Message::Days0To30 => "0–30 gün",
+        Message::Days31To60 => "31–60 gün",
+        Message::Days61To90 => "61–90 gün",
+        Message::DaysOver90 => "90+ gün",
+        Message::AgingAsOf => "{date} tarixinə borcların müddəti",
+        Message::NoPeriods => "Dövr yoxdur",
+        Message::Currency => "Valyuta",
+        Message::CurrencyFormat => "Yanlış valyuta kodu, məsələn USD",
+        Message::ExchangeRates => "Valyuta məzənnələri",
+        Message::ReportingCurrency => "Hesabat valyutası",
+        Message::Rate => "Məzənnə",
+        Message::AddRate => "Məzənnə əlavə et",
+        Message::ImportCsv => "CSV-dən idxal",
+        Message::RatesCsvHint => "Sətirlər: valyuta;AA.İİİİ;məzənnə",
+        Message::RatesImportFailed => "Məzənnə faylının {line} sətri oxunmadı",
+        Message::Revaluation => "Yenidən qiymətləndirmə",
+        Message::RevaluationFor => "{from} - {to} yenidən qiymətləndirmə",
+        Message::OpeningRate => "Əvvələ məzənnə",
+        Message::ClosingRate => "Sonuna məzənnə",
+        Message::ExchangeDifference => "Məzənnə fərqi",
+        Message::ExchangeGains => "Müsbət məzənnə fərqləri",
+        Message::ExchangeLosses => "Mənfi məzənnə fərqləri",
+        Message::MissingRates => "Dövr üçün məzənnə yoxdur: {currencies}, məbləğlər birə-bir götürülüb",
+        Message::DebitLimit => "Maks. debet saldosu",
+        Message::CreditLimit => "Maks. avans",
+        Message::Limit => "Limit",
+        Message::Excess => "Artıq",
+        Message::OverLimitAlerts => "Limitdən artıq: {count}",
+        Message::OverLimitOnly => "Yalnız limitdən artıq",
+        Message::CreditLimits => "Borc limitləri",
+        Message::NotANumber => "Rəqəm deyil",
+        Message::RuleOpeningDebit => "Əvvələ qalıq, debet",
+        Message::RuleOpeningCredit => "Əvvələ qalıq, kredit",
+        Message::RuleDebitTurnover => "Debet dövriyyəsi",
+        Message::RuleCreditTurnover => "Kredit dövriyyəsi",
+        Message::RuleTurnover => "Debet və kredit dövriyyəsi",
+        Message::RuleClosingDebit => "Sonuna qalıq, debet",
+        Message::RuleClosingCredit => "Sonuna qalıq, kredit",
+        Message::HighlightRules => "İşıqlandırma qaydaları",
+        Message::HighlightRulesHint => "Sətir uyğun gələn ilk qaydanın rəngini alır",
+        Message::AddRule => "Qayda əlavə et",
+        Message::ExpressionInvalidNumber => "“{value}” ədəd deyil",
+        Message::ExpressionUnexpected => "Gözlənilməz simvol “{char}”",
+        Message::ExpressionIncomplete => "İfadə tamamlanmayıb",
+        Message::ExpressionParenthesis => "Mötərizə bağlanmayıb",
+        Message::ExpressionDivisionByZero => "Sıfıra bölmə",
+        Message::EmptyName => "Ad göstərilməyib",
+        Message::InvalidRow => "“{name}”: {error}",
+        Message::BulkEdit => "Kütləvi dəyişiklik",
+        Message::ZeroTurnovers => "Dövriyyələri sıfırla",
+        Message::BulkField => "Nəyi dəyişmək",
+        Message::BulkBefore => "İndi",
+        Message::BulkAfter => "Olacaq",
+        Message::BulkApply => "{count} sətrə tətbiq et",
+        Message::Duplicates => "Dublikatların axtarışı",
+        Message::NoDuplicates => "Dublikat tapılmadı",
+        Message::SameName => "eyni ad",
+        Message::SameTaxId => "eyni VÖEN",
+        Message::MergeInto => "“{name}” ilə birləşdir",
+        Message::Merge => "Birləşdir",
+        Message::MergeConfirm => "“{merged}” qalıqları və dövriyyələri “{kept}” üzərinə keçiriləcək, “{merged}” isə silinəcək. Bunu geri qaytarmaq olmaz.",
+        Message::CompaniesMerged => "“{merged}” “{kept}” ilə birləşdirildi: {moved} qalıq köçürüldü, {combined} toplandı",
+        Message::Tags => "Teqlər",
+        Message::AnyTags => "Sıfırla",
+        Message::NoTags => "Teqsiz",
+        Message::TagSubtotals => "Teqlər üzrə yekunlar",
+        Message::TagSubtotalsHint => "Bir neçə teqi olan şirkət onların hər birinin yekununa daxildir",
+        Message::Companies => "Şirkətlər",
+        Message::NewTag => "Yeni teq",
+        Message::AddTag => "Teq əlavə et",
+        Message::DetailsTab => "Rekvizitlər",
+        Message::Attachments => "Fayllar",
+        Message::AttachFile => "Fayl əlavə et…",
+        Message::DropToAttach => "və ya faylları pəncərəyə sürükləyin",
+        Message::NoAttachments => "Fayl yoxdur",
+        Message::Kilobytes => "{size} KB",
+        Message::SaveAs => "Fərqli saxla…",
+        Message::DropFilesHere => "“{name}” şirkətinə əlavə etmək üçün buraxın",
+        Message::DropToImport => "Excel və ya CSV cədvəlini idxal etmək üçün buraxın",
+        Message::ImportUnknownFormat => "xlsx, xls, ods və ya csv cədvəli gözlənilirdi",
+        Message::ImportFailed => "{file} idxal edilmədi: {error}",
+        Message::ImportedRows => "{file}: əlavə ediləcək sətirlər: {count}, yoxlayıb yadda saxlayın",
+        Message::ImportedRates => "{file}: idxal edilən məzənnələr: {count}",
+        Message::ImportWhileEditing => "{file} idxal edilmədi: əvvəlcə redaktəni bitirin",
+        Message::TurnoverSheet => "Dövriyyə-saldo cədvəli",
+        Message::TurnoverSheetFor => "{from} - {to} dövrü üzrə dövriyyə-saldo cədvəli",
+        Message::PeriodsWorkbook => "Dövrlər üzrə Excel…",
+        Message::ExportPeriods => "{count} dövrü ixrac et",
+        Message::TurnoverSheets => "Aylıq dövriyyə-saldo cədvəlləri",
+        Message::Cover => "Titul",
+        Message::Summary => "Xülasə",
+        Message::PeriodsCount => "Dövrlər",
+        Message::ClosingBalances => "Ayın sonuna saldo: debet müsbət, kredit mənfi",
+        Message::Change => "Dəyişiklik",
+        Message::Ods => "ODS (LibreOffice)",
+        Message::CliUsage => "İstifadə: company_calc export <fayl.xlsx|fayl.ods|fayl.pdf|fayl.html|fayl.json> [AA.İİİİ]\n          company_calc import <fayl.json> merge|replace",
+        Message::CliUnknownFormat => "Format genişlənmədən götürülür: xlsx, ods, pdf, html və ya json",
+        Message::CliUnknownPeriod => "{period} dövrü yoxdur",
+        Message::Html => "HTML",
+        Message::ConflictDiffers => "{record}: verilənlər bazasında bu açarla başqa qeyd var",
+        Message::ConflictDuplicate => "{record}: {field} artıq başqa qeydə məxsusdur",
+        Message::ConflictClosedPeriod => "{record}: dövr bağlıdır",
+        Message::NotADataset => "Bu, tətbiqin verilənlər faylı deyil",
+        Message::UnsupportedDatasetVersion => "Fayl {version} versiyasıdır və tətbiqin daha yeni versiyası ilə yazılıb, {supported} versiyasına qədər dəstəklənir",
+        Message::DatasetConflicts => "{count} ziddiyyət, heç nə idxal edilmədi",
+        Message::Json => "JSON (bütün məlumatlar)",
+        Message::ImportDataset => "JSON-dan məlumat idxalı…",
+        Message::DatasetMerge => "Mövcud məlumatlara əlavə et",
+        Message::DatasetMergeHint => "Eyni qeydlər buraxılır, hər hansı id fərqlənərsə heç nə idxal edilmir",
+        Message::DatasetReplace => "Bütün məlumatları əvəz et",
+        Message::DatasetReplaceHint => "Bütün məlumatlar silinir və fayldan yüklənir",
+        Message::DatasetReplaceWarning => "Bütün şirkətlər, dövrlər və qalıqlar silinib fayldan yüklənəcək. Bunu geri qaytarmaq olmaz. Bağlı dövrləri əvvəlcə yenidən açmaq lazımdır.",
+        Message::ChooseFile => "Fayl seç…",
+        Message::DatasetImported => "{file}: {companies} şirkət, {balances} qalıq və {other} digər qeyd əlavə edildi",
+        Message::MergeCurrenciesDiffer => "Şirkətlər müxtəlif valyutalarda aparılır ({kept} və {merged}), onların qalıqlarını toplamaq olmaz",
+        Message::DeleteAccountConfirm => "{account} hesabı silinsin? Onun qalıqları da silinəcək",
+        Message::DatasetClosedPeriods => "Bağlı dövrlər {periods} silinərdi. Əvvəlcə onları yenidən açın",
+        Message::GroupCycle => "“{group}” qrupu özünə və ya öz alt qrupuna daxil ola bilməz",
+        Message::DatasetGroupCycles => "{groups} qrupları özlərinə daxildir",
+        Message::DeleteGroupConfirm => "“{group}” qrupu {count} alt qrupu ilə birlikdə silinsin? Onların şirkətləri qrupsuz qalacaq",
+    }
+}
@@ -0,0 +1,194 @@
+use std::{io::Cursor, path::Path};
+
+use calamine::{Data, Reader};
+
+use super::{
+    clipboard::{parse_cells, PasteError},
+    currency::parse_rates_csv,
+    format::NumberFormat,
+    i18n::{Language, Message},
+    model::ExchangeRate,
+    NewCompanyRow,
+};
+
+/// What an imported file turned out to hold.
+pub enum Imported {
+    /// Rows in the layout of [`parse_cells`], they go to the table to be checked before saving.
+    Companies {
+        rows: Vec<NewCompanyRow>,
+        errors: Vec<PasteError>,
+    },
+    /// A `currency;month;rate` file as the rates window imports it.
+    Rates(Vec<ExchangeRate>),
+}
+
+#[derive(Debug)]
+pub enum ImportError {
+    UnknownFormat,
+    Unreadable(String),
+    RatesLine(usize),
+}
+
+impl ImportError {
+    pub fn describe(&self, language: Language) -> String {
+        match self {
+            ImportError::UnknownFormat => language.tr(Message::ImportUnknownFormat).to_string(),
+            ImportError::Unreadable(error) => error.clone(),
+            ImportError::RatesLine(line) => {
+                language.trf(Message::RatesImportFailed, &[("line", line)])
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileFormat {
+    Spreadsheet,
+    Csv,
+}
+
+/// Spreadsheets are told by their signature, zip for xlsx and ods and OLE for xls, so they open
+/// whatever they are named. Text has no signature and goes by the extension.
+fn detect_format(file_name: &str, contents: &[u8]) -> Option<FileFormat> {
+    const ZIP: &[u8] = b"PK\x03\x04";
+    const OLE: &[u8] = &[0xD0, 0xCF, 0x11, 0xE0];
+
+    if contents.starts_with(ZIP) || contents.starts_with(OLE) {
+        return Some(FileFormat::Spreadsheet);
+    }
+
+    let extension = Path::new(file_name)
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase());
+    match extension.as_deref() {
+        Some("csv" | "tsv" | "txt") => Some(FileFormat::Csv),
+        _ => None,
+    }
+}
+
+/// Reads a file dropped onto the window or picked to import.
+pub fn import_file(
+    file_name: &str,
+    contents: Vec<u8>,
+    number_format: &NumberFormat,
+) -> Result<Imported, ImportError> {
+    match detect_format(file_name, &contents) {
+        Some(FileFormat::Spreadsheet) => {
+            let lines = spreadsheet_lines(contents, number_format)?;
+            Ok(companies(lines, number_format))
+        }
+        Some(FileFormat::Csv) => {
+            let text = decode(&contents);
+            let lines = csv_lines(&text)?;
+
+            // three columns can only be rates, companies take at least five
+            if !lines.is_empty() && lines.iter().all(|(_, cells)| cells.len() == 3) {
+                return parse_rates_csv(&text)
+                    .map(Imported::Rates)
+                    .map_err(ImportError::RatesLine);
+            }
+            Ok(companies(lines, number_format))
+        }
+        None => Err(ImportError::UnknownFormat),
+    }
+}
+
+fn companies(lines: Vec<(usize, Vec<String>)>, number_format: &NumberFormat) -> Imported {
+    let lines = lines
+        .iter()
+        .map(|(line, cells)| (*line, cells.iter().map(String::as_str).collect()));
+    let (rows, errors) = parse_cells(lines, number_format);
+
+    Imported::Companies { rows, errors }
+}
+
+/// UTF-8 when the text is valid as such, windows-1251 otherwise.
+fn decode(contents: &[u8]) -> String {
+    match std::str::from_utf8(contents) {
+        Ok(text) => text.trim_start_matches('\u{feff}').to_string(),
+        Err(_) => encoding_rs::WINDOWS_1251.decode(contents).0.into_owned(),
+    }
+}
+
+/// Semicolons and tabs win over commas, which may as well be decimal separators.
+fn delimiter(text: &str) -> u8 {
+    let first_line = text.lines().find(|line| !line.trim().is_empty());
+    [b';', b'\t']
+        .into_iter()
+        .find(|delimiter| first_line.is_some_and(|line| line.contains(*delimiter as char)))
+        .unwrap_or(b',')
+}
+
+fn csv_lines(text: &str) -> Result<Vec<(usize, Vec<String>)>, ImportError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(delimiter(text))
+        .from_reader(text.as_bytes());
+
+    let mut lines = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|error| ImportError::Unreadable(error.to_string()))?;
+        let line = record
+            .position()
+            .map_or(lines.len() + 1, |position| position.line() as usize);
+        lines.push((line, record.iter().map(str::to_string).collect()));
+    }
+
+    Ok(same_width(lines))
+}
+
+/// The first sheet, numbers written in full in the number format without grouping so they read
+/// back unrounded.
+fn spreadsheet_lines(
+    contents: Vec<u8>,
+    number_format: &NumberFormat,
+) -> Result<Vec<(usize, Vec<String>)>, ImportError> {
+    let unreadable = |error: calamine::Error| ImportError::Unreadable(error.to_string());
+    let number_format = NumberFormat {
+        group_separator: None,
+        ..number_format.clone()
+    };
+
+    let mut workbook =
+        calamine::open_workbook_auto_from_rs(Cursor::new(contents)).map_err(unreadable)?;
+    let range = workbook
+        .worksheet_range_at(0)
+        .ok_or(ImportError::UnknownFormat)?
+        .map_err(unreadable)?;
+    let first_row = range.start().map_or(0, |(row, _)| row as usize);
+
+    let lines = range
+        .rows()
+        .enumerate()
+        .map(|(i, row)| {
+            let cells = row
+                .iter()
+                .map(|cell| match cell {
+                    Data::Empty => String::new(),
+                    Data::Int(value) => number_format.format_exact(*value as f64),
+                    Data::Float(value) => number_format.format_exact(*value),
+                    cell => cell.to_string(),
+                })
+                .collect();
+            (first_row + i + 1, cells)
+        })
+        .collect();
+
+    Ok(same_width(lines))
+}
+
+/// Cut to the last column with anything in it, so stray formatting to the right doesn't change
+/// the column count and a row ending with an empty credit keeps its last cell.
+fn same_width(mut lines: Vec<(usize, Vec<String>)>) -> Vec<(usize, Vec<String>)> {
+    let width = lines
+        .iter()
+        .filter_map(|(_, cells)| cells.iter().rposition(|cell| !cell.trim().is_empty()))
+        .max()
+        .map_or(0, |last| last + 1);
+
+    for (_, cells) in &mut lines {
+        cells.resize(width, String::new());
+    }
+    lines
+}
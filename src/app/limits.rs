@@ -0,0 +1,101 @@
+use egui::Color32;
+
+use super::{
+    format::NumberFormat,
+    i18n::{Language, Message},
+    model::Company,
+};
+
+pub const OVER_LIMIT_COLOR: Color32 = Color32::from_rgba_premultiplied(50, 10, 10, 50);
+
+/// A closing balance above the limit set for the company.
+pub struct Breach {
+    pub id: i64,
+    pub name: String,
+    pub limit_title: Message,
+    pub balance: f64,
+    pub limit: f64,
+}
+
+impl Breach {
+    pub fn excess(&self) -> f64 {
+        self.balance - self.limit
+    }
+}
+
+impl Company {
+    pub fn breach(&self) -> Option<Breach> {
+        let balance = self.remainder_end_month;
+        let (limit_title, balance, limit) = if balance > 0. {
+            (Message::DebitLimit, balance, self.debit_limit?)
+        } else {
+            (Message::CreditLimit, -balance, self.credit_limit?)
+        };
+
+        (balance > limit).then(|| Breach {
+            id: self.id,
+            name: self.name.clone(),
+            limit_title,
+            balance,
+            limit,
+        })
+    }
+
+    pub fn is_over_limit(&self) -> bool {
+        self.breach().is_some()
+    }
+}
+
+/// Every breach with how far the balance goes over. Returns the id of the company clicked.
+pub fn alerts_ui(
+    ui: &mut egui::Ui,
+    breaches: &[Breach],
+    number_format: &NumberFormat,
+    language: Language,
+) -> Option<i64> {
+    let mut clicked = None;
+
+    egui::CollapsingHeader::new(
+        language.trf(Message::OverLimitAlerts, &[("count", &breaches.len())]),
+    )
+    .id_source("over_limit_alerts")
+    .default_open(true)
+    .show(ui, |ui| {
+        egui::ScrollArea::vertical()
+            .max_height(120.0)
+            .show(ui, |ui| {
+                egui::Grid::new("over_limit_grid")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for title in [
+                            Message::Name,
+                            Message::Limit,
+                            Message::RemainderEndMonth,
+                            Message::Excess,
+                        ] {
+                            ui.strong(language.tr(title));
+                        }
+                        ui.end_row();
+
+                        for breach in breaches {
+                            if ui.link(&breach.name).clicked() {
+                                clicked = Some(breach.id);
+                            }
+                            ui.label(format!(
+                                "{}: {}",
+                                language.tr(breach.limit_title),
+                                number_format.format(breach.limit)
+                            ));
+                            ui.label(number_format.format(breach.balance));
+                            ui.colored_label(
+                                ui.visuals().error_fg_color,
+                                number_format.format(breach.excess()),
+                            );
+                            ui.end_row();
+                        }
+                    });
+            });
+    });
+
+    clicked
+}
@@ -1,8 +1,37 @@
+use std::error::Error;
+
 use super::{
+    expression::evaluate,
+    format::NumberFormat,
+    i18n::{Language, Message},
     model::{EditedCompany, NewCompany},
     EditedCompanyRow, NewCompanyRow,
 };
 
+/// Evaluates the field, the error names it.
+fn amount(
+    value: &str,
+    title: Message,
+    number_format: &NumberFormat,
+    language: Language,
+) -> Result<f64, Box<dyn Error>> {
+    evaluate(value, number_format)
+        .map_err(|error| format!("{}: {}", language.tr(title), error.message(language)).into())
+}
+
+fn remainder(
+    pos: &str,
+    neg: &str,
+    number_format: &NumberFormat,
+    language: Language,
+) -> Result<f64, Box<dyn Error>> {
+    Ok(match (pos, neg) {
+        (first, "") => amount(first, Message::RuleOpeningDebit, number_format, language)?,
+        ("", second) => -amount(second, Message::RuleOpeningCredit, number_format, language)?,
+        _ => 0.0,
+    })
+}
+
 pub fn map_to_new(
     NewCompanyRow {
         name,
@@ -11,22 +40,29 @@ pub fn map_to_new(
         debit_turnover,
         credit_turnover,
     }: &NewCompanyRow,
-) -> Result<NewCompany, Box<dyn std::error::Error>> {
-    let new_remainder: f64 = match (
-        remainder_begin_month_pos.as_str(),
-        remainder_begin_month_neg.as_str(),
-    ) {
-        (first, "") => first.parse()?,
-        ("", second) => {
-            let parsed: f64 = second.parse()?;
-            parsed * -1.0
-        }
-        _ => 0.0,
-    };
+    number_format: &NumberFormat,
+    language: Language,
+) -> Result<NewCompany, Box<dyn Error>> {
+    let new_remainder = remainder(
+        remainder_begin_month_pos,
+        remainder_begin_month_neg,
+        number_format,
+        language,
+    )?;
 
-    let parsed_debit: f64 = debit_turnover.parse()?;
+    let parsed_debit = amount(
+        debit_turnover,
+        Message::RuleDebitTurnover,
+        number_format,
+        language,
+    )?;
 
-    let parsed_credit: f64 = credit_turnover.parse()?;
+    let parsed_credit = amount(
+        credit_turnover,
+        Message::RuleCreditTurnover,
+        number_format,
+        language,
+    )?;
 
     Ok(NewCompany {
         name: name.to_string(),
@@ -45,25 +81,32 @@ pub fn map_to_edited(
         debit_turnover,
         credit_turnover,
     }: &EditedCompanyRow,
-) -> Result<EditedCompany, Box<dyn std::error::Error>> {
-    let new_remainder: f64 = match (
-        remainder_begin_month_pos.as_str(),
-        remainder_begin_month_neg.as_str(),
-    ) {
-        (first, "") => first.parse()?,
-        ("", second) => {
-            let parsed: f64 = second.parse()?;
-            parsed * -1.0
-        }
-        _ => 0.0,
-    };
+    number_format: &NumberFormat,
+    language: Language,
+) -> Result<EditedCompany, Box<dyn Error>> {
+    let new_remainder = remainder(
+        remainder_begin_month_pos,
+        remainder_begin_month_neg,
+        number_format,
+        language,
+    )?;
     if name.is_empty() {
-        return Err("no empty string".into());
+        return Err(language.tr(Message::EmptyName).into());
     }
 
-    let parsed_debit: f64 = debit_turnover.parse()?;
+    let parsed_debit = amount(
+        debit_turnover,
+        Message::RuleDebitTurnover,
+        number_format,
+        language,
+    )?;
 
-    let parsed_credit: f64 = credit_turnover.parse()?;
+    let parsed_credit = amount(
+        credit_turnover,
+        Message::RuleCreditTurnover,
+        number_format,
+        language,
+    )?;
     Ok(EditedCompany {
         id: *id,
         name: name.to_string(),
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 #[derive(Default, Debug, Clone)]
 pub struct Company {
     pub id: i64,
@@ -6,6 +8,236 @@ pub struct Company {
     pub debit_turnover: f64,
     pub credit_turnover: f64,
     pub remainder_end_month: f64,
+    pub tax_id: String,
+    pub legal_address: String,
+    pub contact_person: String,
+    pub phone: String,
+    pub email: String,
+    pub iban: String,
+    pub bic: String,
+    pub contract_number: String,
+    pub note: String,
+    pub group_id: Option<i64>,
+    pub currency: String,
+    /// Largest debit balance the customer may have.
+    pub debit_limit: Option<f64>,
+    /// Largest advance, that is credit balance, allowed to the supplier.
+    pub credit_limit: Option<f64>,
+}
+
+impl Company {
+    /// Case insensitive search over the name and every text detail.
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+
+        [
+            &self.name,
+            &self.tax_id,
+            &self.legal_address,
+            &self.contact_person,
+            &self.phone,
+            &self.email,
+            &self.iban,
+            &self.bic,
+            &self.contract_number,
+            &self.note,
+            &self.currency,
+        ]
+        .into_iter()
+        .any(|field| field.to_lowercase().contains(&query))
+    }
+
+    pub fn details(&self) -> CompanyDetails {
+        CompanyDetails {
+            tax_id: self.tax_id.clone(),
+            legal_address: self.legal_address.clone(),
+            contact_person: self.contact_person.clone(),
+            phone: self.phone.clone(),
+            email: self.email.clone(),
+            iban: self.iban.clone(),
+            bic: self.bic.clone(),
+            contract_number: self.contract_number.clone(),
+            note: self.note.clone(),
+            group_id: self.group_id,
+            currency: self.currency.clone(),
+            debit_limit: self.debit_limit,
+            credit_limit: self.credit_limit,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct CompanyDetails {
+    pub tax_id: String,
+    pub legal_address: String,
+    pub contact_person: String,
+    pub phone: String,
+    pub email: String,
+    pub iban: String,
+    pub bic: String,
+    pub contract_number: String,
+    pub note: String,
+    pub group_id: Option<i64>,
+    pub currency: String,
+    /// Largest debit balance the customer may have.
+    pub debit_limit: Option<f64>,
+    /// Largest advance, that is credit balance, allowed to the supplier.
+    pub credit_limit: Option<f64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Group {
+    pub id: i64,
+    pub name: String,
+    pub parent_id: Option<i64>,
+}
+
+/// A free-form label, a company can have any number of them.
+#[derive(Debug, Clone)]
+pub struct Tag {
+    pub id: i64,
+    pub name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Account {
+    pub id: i64,
+    pub code: String,
+    pub name: String,
+    pub kind: AccountKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type, strum::EnumIter)]
+#[sqlx(rename_all = "snake_case")]
+pub enum AccountKind {
+    Active,
+    Passive,
+    ActivePassive,
+}
+
+/// Amount of a company a highlight rule looks at. Debit and credit sides are positive, the side
+/// a balance isn't on is zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type, strum::EnumIter)]
+#[sqlx(rename_all = "snake_case")]
+pub enum RuleField {
+    OpeningDebit,
+    OpeningCredit,
+    DebitTurnover,
+    CreditTurnover,
+    /// Debit and credit turnover together.
+    Turnover,
+    ClosingDebit,
+    ClosingCredit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::Type, strum::EnumIter)]
+#[sqlx(rename_all = "snake_case")]
+pub enum Comparison {
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+    Equal,
+    NotEqual,
+}
+
+#[derive(Debug, Clone)]
+pub struct HighlightRule {
+    pub id: i64,
+    pub field: RuleField,
+    pub comparison: Comparison,
+    pub value: f64,
+    /// `RRGGBB`, the way spreadsheets take it.
+    pub color: String,
+}
+
+/// Balances of one account in one period, every row of the table is kept in one of them.
+#[derive(Debug, Clone, Copy)]
+pub struct Sheet {
+    pub account_id: i64,
+    pub period_id: i64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Period {
+    pub id: i64,
+    pub year: i64,
+    pub month: i64,
+    pub closed: bool,
+}
+
+/// Balance of one counterparty in one period, added up over the accounts of the view.
+#[derive(Debug, Clone)]
+pub struct PeriodBalance {
+    pub period_id: i64,
+    pub remainder_begin_month: f64,
+    pub debit_turnover: f64,
+    pub credit_turnover: f64,
+    pub remainder_end_month: f64,
+}
+
+/// Units of the reporting currency per unit of `currency` at the end of the month.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExchangeRate {
+    pub currency: String,
+    pub year: i64,
+    pub month: i64,
+    pub rate: f64,
+}
+
+/// Our own details, printed on documents sent to counterparties.
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Organization {
+    pub name: String,
+    pub tax_id: String,
+    pub legal_address: String,
+    pub signatory: String,
+}
+
+/// Which balances the table shows: the ones kept on a single account or every account added up
+/// per counterparty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Deserialize, serde::Serialize)]
+pub enum AccountView {
+    Account(i64),
+    #[default]
+    Consolidated,
+}
+
+/// One field set the same on every selected counterparty at once.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BulkChange {
+    /// Both turnovers of the sheet become zero, so the closing balance is the opening one.
+    ZeroTurnovers,
+    Group(Option<i64>),
+    Note(String),
+}
+
+/// A file kept with a company. Only the contents of images are fetched, to preview them.
+#[derive(Debug, Clone)]
+pub struct Attachment {
+    pub id: i64,
+    pub file_name: String,
+    pub size: i64,
+    pub created_at: String,
+    pub preview: Option<Arc<[u8]>>,
+}
+
+/// A company found by the full-text search, with the part of the text that matched.
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub id: i64,
+    pub name: String,
+    pub snippet: String,
+}
+
+/// What merging a duplicate into another counterparty did, the way it is logged.
+#[derive(Debug, Clone)]
+pub struct CompanyMerge {
+    pub kept_name: String,
+    pub merged_name: String,
+    pub balances_moved: u64,
+    pub balances_combined: u64,
 }
 
 #[derive(Debug)]
@@ -1,7 +1,23 @@
+use std::collections::HashMap;
+
 use sqlx::SqlitePool;
 
 use super::{
-    model::{Company, EditedCompany, NewCompany},
+    dataset::{
+        AccountRecord, AttachmentRecord, BalanceRecord, CompanyRecord, CompanyTagRecord, Dataset,
+        DatasetCounts, DatasetError, DatasetMode, ExchangeRateRecord, GroupRecord,
+        HighlightRuleRecord, MergeLogRecord, PeriodLogRecord, PeriodRecord, TagRecord,
+        DATASET_FORMAT, DATASET_VERSION,
+    },
+    duplicates::{same_currency, MergeError},
+    groups::{parent_cycles, GroupError},
+    import::{ImportError, Imported},
+    model::{
+        Account, AccountKind, AccountView, Attachment, BulkChange, Company, CompanyDetails,
+        CompanyMerge, Comparison, EditedCompany, ExchangeRate, Group, HighlightRule, NewCompany,
+        Period, PeriodBalance, RuleField, SearchHit, Sheet, Tag,
+    },
+    search::fts_query,
     TotalRow,
 };
 
@@ -19,6 +35,61 @@ pub enum Operation {
     Total {
         total: TotalRow,
     },
+    FetchGroups {
+        all_groups: Result<Vec<Group>, sqlx::Error>,
+    },
+    GroupsChanged,
+    FetchAccounts {
+        all_accounts: Result<Vec<Account>, sqlx::Error>,
+    },
+    AccountsChanged,
+    FetchPeriods {
+        all_periods: Result<Vec<Period>, sqlx::Error>,
+    },
+    PeriodsChanged,
+    PeriodTotals {
+        totals: Result<Vec<(i64, TotalRow)>, sqlx::Error>,
+    },
+    BalanceHistory {
+        history: Result<Vec<(i64, PeriodBalance)>, sqlx::Error>,
+    },
+    FetchRates {
+        all_rates: Result<Vec<ExchangeRate>, sqlx::Error>,
+    },
+    RatesChanged,
+    FetchRules {
+        all_rules: Result<Vec<HighlightRule>, sqlx::Error>,
+    },
+    RulesChanged,
+    Merged {
+        merge: CompanyMerge,
+    },
+    FetchTags {
+        all_tags: Result<Vec<Tag>, sqlx::Error>,
+        company_tags: Result<Vec<(i64, i64)>, sqlx::Error>,
+    },
+    FetchAttachments {
+        company_id: i64,
+        attachments: Result<Vec<Attachment>, sqlx::Error>,
+    },
+    AttachmentsChanged {
+        company_id: i64,
+    },
+    Imported {
+        file_name: String,
+        imported: Result<Imported, ImportError>,
+    },
+    DatasetImported {
+        file_name: String,
+        counts: Result<DatasetCounts, DatasetError>,
+    },
+    Search {
+        query: String,
+        hits: Result<Vec<SearchHit>, sqlx::Error>,
+    },
+    Failed {
+        error: String,
+    },
 }
 
 enum RemainderType {
@@ -26,8 +97,11 @@ enum RemainderType {
     Credit,
 }
 
+/// Adds the balance to the sheet. A counterparty that is already known by name but has no
+/// balance on this sheet yet is reused, so its details stay shared between the accounts.
 pub async fn add_company(
     db: SqlitePool,
+    sheet: Sheet,
     NewCompany {
         name,
         remainder_begin_month,
@@ -46,26 +120,125 @@ pub async fn add_company(
         RemainderType::Credit => remainder_begin_month + debit_turnover,
     };
 
-    let result = sqlx::query_as!(Company, r#"INSERT INTO company (name, remainder_begin_month, debit_turnover, credit_turnover, remainder_end_month)
-                    VALUES (?, ?, ?, ?, ?)
-                    RETURNING id, name, remainder_begin_month, debit_turnover, credit_turnover, remainder_end_month"#,
-                    name, remainder_begin_month, debit_turnover, credit_turnover, remainder)
-        .fetch_one(&db)
-        .await?;
+    let mut tx = db.begin().await?;
+
+    let existing = sqlx::query_scalar!(
+        r#"SELECT id FROM company
+        WHERE name = ? COLLATE NOCASE
+        AND id NOT IN (SELECT company_id FROM balance WHERE account_id = ? AND period_id = ?)"#,
+        name,
+        sheet.account_id,
+        sheet.period_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
 
-    Ok(result)
+    let id = match existing {
+        Some(id) => id,
+        None => {
+            sqlx::query_scalar!("INSERT INTO company (name) VALUES (?) RETURNING id", name)
+                .fetch_one(&mut *tx)
+                .await?
+        }
+    };
+
+    sqlx::query!(
+        r#"INSERT INTO balance (company_id, account_id, period_id, remainder_begin_month, debit_turnover, credit_turnover, remainder_end_month)
+        VALUES (?, ?, ?, ?, ?, ?, ?)"#,
+        id,
+        sheet.account_id,
+        sheet.period_id,
+        remainder_begin_month,
+        debit_turnover,
+        credit_turnover,
+        remainder
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    get_company(db, sheet, id).await
 }
 
-pub async fn get_all_companies(db: SqlitePool) -> Result<Vec<Company>, sqlx::Error> {
-    let result = sqlx::query_as!(Company, "SELECT * FROM company")
+/// Without a period the latest one is shown.
+pub async fn get_all_companies(
+    db: SqlitePool,
+    view: AccountView,
+    period_id: Option<i64>,
+) -> Result<Vec<Company>, sqlx::Error> {
+    let period_id = match period_id {
+        Some(id) => id,
+        None => latest_period_id(&db).await?,
+    };
+
+    match view {
+        AccountView::Account(account_id) => sqlx::query_as!(
+            Company,
+            r#"SELECT c.id, c.name, b.remainder_begin_month, b.debit_turnover, b.credit_turnover,
+                b.remainder_end_month, c.tax_id, c.legal_address, c.contact_person, c.phone,
+                c.email, c.iban, c.bic, c.contract_number, c.note, c.group_id, c.currency,
+                c.debit_limit, c.credit_limit
+                FROM company c
+                JOIN balance b ON b.company_id = c.id
+                WHERE b.account_id = ? AND b.period_id = ?
+                ORDER BY c.id"#,
+            account_id,
+            period_id
+        )
         .fetch_all(&db)
-        .await?;
+        .await,
+        // counterparties without any balance are listed too, so their details aren't lost
+        AccountView::Consolidated => {
+            sqlx::query_as!(
+                Company,
+                r#"SELECT c.id, c.name,
+                COALESCE(SUM(b.remainder_begin_month), 0) AS "remainder_begin_month!: f64",
+                COALESCE(SUM(b.debit_turnover), 0) AS "debit_turnover!: f64",
+                COALESCE(SUM(b.credit_turnover), 0) AS "credit_turnover!: f64",
+                COALESCE(SUM(b.remainder_end_month), 0) AS "remainder_end_month!: f64",
+                c.tax_id, c.legal_address, c.contact_person, c.phone,
+                c.email, c.iban, c.bic, c.contract_number, c.note, c.group_id, c.currency,
+                c.debit_limit, c.credit_limit
+                FROM company c
+                LEFT JOIN balance b ON b.company_id = c.id AND b.period_id = ?
+                GROUP BY c.id
+                ORDER BY c.id"#,
+                period_id
+            )
+            .fetch_all(&db)
+            .await
+        }
+    }
+}
+
+async fn latest_period_id(db: &SqlitePool) -> Result<i64, sqlx::Error> {
+    sqlx::query_scalar!("SELECT id FROM period ORDER BY year DESC, month DESC LIMIT 1")
+        .fetch_one(db)
+        .await
+}
 
-    Ok(result)
+async fn get_company(db: SqlitePool, sheet: Sheet, id: i64) -> Result<Company, sqlx::Error> {
+    sqlx::query_as!(
+        Company,
+        r#"SELECT c.id, c.name, b.remainder_begin_month, b.debit_turnover, b.credit_turnover,
+        b.remainder_end_month, c.tax_id, c.legal_address, c.contact_person, c.phone,
+        c.email, c.iban, c.bic, c.contract_number, c.note, c.group_id, c.currency,
+        c.debit_limit, c.credit_limit
+        FROM company c
+        JOIN balance b ON b.company_id = c.id
+        WHERE b.account_id = ? AND b.period_id = ? AND c.id = ?"#,
+        sheet.account_id,
+        sheet.period_id,
+        id
+    )
+    .fetch_one(&db)
+    .await
 }
 
 pub async fn edit_company(
     db: SqlitePool,
+    sheet: Sheet,
     EditedCompany {
         id,
         name,
@@ -83,33 +256,1241 @@ pub async fn edit_company(
         RemainderType::Debit => remainder_begin_month - credit_turnover,
         RemainderType::Credit => remainder_begin_month + debit_turnover,
     };
+
+    let mut tx = db.begin().await?;
+
+    sqlx::query!("UPDATE company SET name = ? WHERE id = ?", name, id)
+        .execute(&mut *tx)
+        .await?;
+
     sqlx::query!(
-        r#"UPDATE company
-        SET name = ?,
-        remainder_begin_month = ?,
+        r#"UPDATE balance
+        SET remainder_begin_month = ?,
         debit_turnover = ?,
         credit_turnover = ?,
         remainder_end_month = ?
-        WHERE id = ?"#,
-        name,
+        WHERE company_id = ? AND account_id = ? AND period_id = ?"#,
         remainder_begin_month,
         debit_turnover,
         credit_turnover,
         remainder,
+        id,
+        sheet.account_id,
+        sheet.period_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    get_company(db, sheet, id).await
+}
+
+pub async fn edit_company_details(
+    db: SqlitePool,
+    id: i64,
+    CompanyDetails {
+        tax_id,
+        legal_address,
+        contact_person,
+        phone,
+        email,
+        iban,
+        bic,
+        contract_number,
+        note,
+        group_id,
+        currency,
+        debit_limit,
+        credit_limit,
+    }: CompanyDetails,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        r#"UPDATE company
+        SET tax_id = ?,
+        legal_address = ?,
+        contact_person = ?,
+        phone = ?,
+        email = ?,
+        iban = ?,
+        bic = ?,
+        contract_number = ?,
+        note = ?,
+        group_id = ?,
+        currency = ?,
+        debit_limit = ?,
+        credit_limit = ?
+        WHERE id = ?"#,
+        tax_id,
+        legal_address,
+        contact_person,
+        phone,
+        email,
+        iban,
+        bic,
+        contract_number,
+        note,
+        group_id,
+        currency,
+        debit_limit,
+        credit_limit,
         id
     )
     .execute(&db)
     .await?;
 
-    sqlx::query_as!(Company, "SELECT * FROM company WHERE id = ?", id)
+    Ok(())
+}
+
+/// Applies the change to every company in one transaction, so either all of them change or
+/// none does.
+pub async fn bulk_edit(
+    db: SqlitePool,
+    sheet: Sheet,
+    ids: Vec<i64>,
+    change: BulkChange,
+) -> Result<(), sqlx::Error> {
+    let mut tx = db.begin().await?;
+
+    for id in ids {
+        match &change {
+            BulkChange::ZeroTurnovers => {
+                sqlx::query!(
+                    r#"UPDATE balance
+                    SET debit_turnover = 0,
+                    credit_turnover = 0,
+                    remainder_end_month = remainder_begin_month
+                    WHERE company_id = ? AND account_id = ? AND period_id = ?"#,
+                    id,
+                    sheet.account_id,
+                    sheet.period_id
+                )
+                .execute(&mut *tx)
+                .await?;
+            }
+            BulkChange::Group(group_id) => {
+                sqlx::query!("UPDATE company SET group_id = ? WHERE id = ?", group_id, id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            BulkChange::Note(note) => {
+                sqlx::query!("UPDATE company SET note = ? WHERE id = ?", note, id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+    }
+
+    tx.commit().await
+}
+
+/// Removes the balance from the sheet, the counterparty itself goes away with its last
+/// balance.
+pub async fn delete_company(db: SqlitePool, sheet: Sheet, id: i64) -> Result<(), sqlx::Error> {
+    let mut tx = db.begin().await?;
+
+    sqlx::query!(
+        "DELETE FROM balance WHERE company_id = ? AND account_id = ? AND period_id = ?",
+        id,
+        sheet.account_id,
+        sheet.period_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "DELETE FROM company WHERE id = ? AND id NOT IN (SELECT company_id FROM balance)",
+        id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await
+}
+
+/// Moves everything of the duplicate over to the company that is kept: balances on a sheet both
+/// have are added up, the rest change hands, details the company lacks, tags and attachments are
+/// taken from the duplicate. The duplicate is deleted, its merge history handed over and the
+/// merge logged. Balances in different currencies can't be added up and a balance in a closed
+/// period can't change, so such a merge fails as a whole.
+pub async fn merge_companies(
+    db: SqlitePool,
+    keep_id: i64,
+    merged_id: i64,
+) -> Result<CompanyMerge, MergeError> {
+    let mut tx = db.begin().await?;
+
+    let kept = sqlx::query!("SELECT name, currency FROM company WHERE id = ?", keep_id)
+        .fetch_one(&mut *tx)
+        .await?;
+    let merged = sqlx::query!(
+        "SELECT name, tax_id, currency FROM company WHERE id = ?",
+        merged_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+    if !same_currency(&kept.currency, &merged.currency) {
+        return Err(MergeError::Currencies {
+            kept: kept.currency,
+            merged: merged.currency,
+        });
+    }
+    let kept_name = kept.name;
+
+    let balances_combined = sqlx::query!(
+        r#"UPDATE balance AS kept
+        SET remainder_begin_month = kept.remainder_begin_month + merged.remainder_begin_month,
+        debit_turnover = kept.debit_turnover + merged.debit_turnover,
+        credit_turnover = kept.credit_turnover + merged.credit_turnover,
+        remainder_end_month = kept.remainder_end_month + merged.remainder_end_month
+        FROM balance AS merged
+        WHERE kept.company_id = ? AND merged.company_id = ?
+        AND merged.account_id = kept.account_id AND merged.period_id = kept.period_id"#,
+        keep_id,
+        merged_id
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    sqlx::query!(
+        r#"DELETE FROM balance
+        WHERE company_id = ?
+        AND EXISTS (
+            SELECT 1 FROM balance AS kept
+            WHERE kept.company_id = ?
+            AND kept.account_id = balance.account_id AND kept.period_id = balance.period_id
+        )"#,
+        merged_id,
+        keep_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let balances_moved = sqlx::query!(
+        "UPDATE balance SET company_id = ? WHERE company_id = ?",
+        keep_id,
+        merged_id
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    sqlx::query!(
+        r#"UPDATE company
+        SET tax_id = iif(company.tax_id = '', merged.tax_id, company.tax_id),
+        legal_address = iif(company.legal_address = '', merged.legal_address, company.legal_address),
+        contact_person = iif(company.contact_person = '', merged.contact_person, company.contact_person),
+        phone = iif(company.phone = '', merged.phone, company.phone),
+        email = iif(company.email = '', merged.email, company.email),
+        iban = iif(company.iban = '', merged.iban, company.iban),
+        bic = iif(company.bic = '', merged.bic, company.bic),
+        contract_number = iif(company.contract_number = '', merged.contract_number, company.contract_number),
+        note = iif(company.note = '', merged.note, company.note),
+        group_id = coalesce(company.group_id, merged.group_id),
+        currency = iif(company.currency = '', merged.currency, company.currency),
+        debit_limit = coalesce(company.debit_limit, merged.debit_limit),
+        credit_limit = coalesce(company.credit_limit, merged.credit_limit)
+        FROM company AS merged
+        WHERE company.id = ? AND merged.id = ?"#,
+        keep_id,
+        merged_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"INSERT OR IGNORE INTO company_tag (company_id, tag_id)
+        SELECT ?, tag_id FROM company_tag WHERE company_id = ?"#,
+        keep_id,
+        merged_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE attachment SET company_id = ? WHERE company_id = ?",
+        keep_id,
+        merged_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE company_merge_log SET company_id = ? WHERE company_id = ?",
+        keep_id,
+        merged_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!("DELETE FROM company WHERE id = ?", merged_id)
+        .execute(&mut *tx)
+        .await?;
+
+    let (moved, combined) = (balances_moved as i64, balances_combined as i64);
+    sqlx::query!(
+        r#"INSERT INTO company_merge_log
+        (company_id, merged_id, merged_name, merged_tax_id, balances_moved, balances_combined)
+        VALUES (?, ?, ?, ?, ?, ?)"#,
+        keep_id,
+        merged_id,
+        merged.name,
+        merged.tax_id,
+        moved,
+        combined
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(CompanyMerge {
+        kept_name,
+        merged_name: merged.name,
+        balances_moved,
+        balances_combined,
+    })
+}
+
+/// Companies matching the query best first, found among every company whatever the sheet. All of
+/// them, the table is filtered by them too.
+pub async fn search_companies(db: SqlitePool, query: &str) -> Result<Vec<SearchHit>, sqlx::Error> {
+    let Some(query) = fts_query(query) else {
+        return Ok(Vec::new());
+    };
+
+    sqlx::query_as!(
+        SearchHit,
+        r#"SELECT rowid AS "id!",
+        name AS "name!: String",
+        snippet(company_search, -1, '', '', '…', 8) AS "snippet!: String"
+        FROM company_search
+        WHERE company_search MATCH ?
+        ORDER BY rank"#,
+        query
+    )
+    .fetch_all(&db)
+    .await
+}
+
+pub async fn get_all_groups(db: SqlitePool) -> Result<Vec<Group>, sqlx::Error> {
+    sqlx::query_as!(Group, "SELECT * FROM company_group ORDER BY name")
+        .fetch_all(&db)
+        .await
+}
+
+pub async fn add_group(
+    db: SqlitePool,
+    name: String,
+    parent_id: Option<i64>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "INSERT INTO company_group (name, parent_id) VALUES (?, ?)",
+        name,
+        parent_id
+    )
+    .execute(&db)
+    .await?;
+
+    Ok(())
+}
+
+/// Fails when the new parent is the group itself or one of its subgroups.
+pub async fn edit_group(
+    db: SqlitePool,
+    Group {
+        id,
+        name,
+        parent_id,
+    }: Group,
+) -> Result<(), GroupError> {
+    let mut tx = db.begin().await?;
+
+    let mut parents: HashMap<_, _> = sqlx::query!("SELECT id, parent_id FROM company_group")
+        .fetch_all(&mut *tx)
+        .await?
+        .into_iter()
+        .map(|group| (group.id, group.parent_id))
+        .collect();
+    parents.insert(id, parent_id);
+    if parent_cycles(&parents).contains(&id) {
+        return Err(GroupError::Cycle(name));
+    }
+
+    sqlx::query!(
+        "UPDATE company_group SET name = ?, parent_id = ? WHERE id = ?",
+        name,
+        parent_id,
+        id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Subgroups are deleted with the group, their companies become ungrouped.
+pub async fn delete_group(db: SqlitePool, id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query!("DELETE FROM company_group WHERE id = ?", id)
+        .execute(&db)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn get_all_tags(db: SqlitePool) -> Result<Vec<Tag>, sqlx::Error> {
+    sqlx::query_as!(Tag, "SELECT id, name FROM tag ORDER BY name")
+        .fetch_all(&db)
+        .await
+}
+
+/// Every tag of every company as `(company_id, tag_id)`.
+pub async fn get_company_tags(db: SqlitePool) -> Result<Vec<(i64, i64)>, sqlx::Error> {
+    let rows = sqlx::query!("SELECT company_id, tag_id FROM company_tag")
+        .fetch_all(&db)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.company_id, row.tag_id))
+        .collect())
+}
+
+/// Replaces the tags of the company, tags not known yet are created.
+pub async fn set_company_tags(
+    db: SqlitePool,
+    company_id: i64,
+    names: Vec<String>,
+) -> Result<(), sqlx::Error> {
+    let mut tx = db.begin().await?;
+
+    sqlx::query!("DELETE FROM company_tag WHERE company_id = ?", company_id)
+        .execute(&mut *tx)
+        .await?;
+
+    for name in names {
+        sqlx::query!(
+            "INSERT INTO tag (name) VALUES (?) ON CONFLICT (name) DO NOTHING",
+            name
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"INSERT OR IGNORE INTO company_tag (company_id, tag_id)
+            SELECT ?, id FROM tag WHERE name = ?"#,
+            company_id,
+            name
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await
+}
+
+pub async fn delete_tag(db: SqlitePool, id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query!("DELETE FROM tag WHERE id = ?", id)
+        .execute(&db)
+        .await?;
+
+    Ok(())
+}
+
+/// Attachments of the company, newest first.
+pub async fn get_attachments(
+    db: SqlitePool,
+    company_id: i64,
+) -> Result<Vec<Attachment>, sqlx::Error> {
+    let rows = sqlx::query!(
+        r#"SELECT id, file_name, length(data) AS "size!: i64", created_at,
+        CASE WHEN lower(file_name) GLOB '*.png'
+            OR lower(file_name) GLOB '*.jpg'
+            OR lower(file_name) GLOB '*.jpeg'
+        THEN data END AS "preview?: Vec<u8>"
+        FROM attachment
+        WHERE company_id = ?
+        ORDER BY created_at DESC, id DESC"#,
+        company_id
+    )
+    .fetch_all(&db)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| Attachment {
+            id: row.id,
+            file_name: row.file_name,
+            size: row.size,
+            created_at: row.created_at,
+            preview: row.preview.map(Into::into),
+        })
+        .collect())
+}
+
+/// Files as `(file_name, contents)`.
+pub async fn add_attachments(
+    db: SqlitePool,
+    company_id: i64,
+    files: Vec<(String, Vec<u8>)>,
+) -> Result<(), sqlx::Error> {
+    let mut tx = db.begin().await?;
+
+    for (file_name, data) in files {
+        sqlx::query!(
+            "INSERT INTO attachment (company_id, file_name, data) VALUES (?, ?, ?)",
+            company_id,
+            file_name,
+            data
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await
+}
+
+pub async fn get_attachment_data(db: SqlitePool, id: i64) -> Result<Vec<u8>, sqlx::Error> {
+    sqlx::query_scalar!("SELECT data FROM attachment WHERE id = ?", id)
         .fetch_one(&db)
         .await
 }
 
-pub async fn delete_company(db: SqlitePool, id: i64) -> Result<(), sqlx::Error> {
-    let result = sqlx::query!("DELETE FROM company WHERE id = ?", id)
+pub async fn delete_attachment(db: SqlitePool, id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query!("DELETE FROM attachment WHERE id = ?", id)
         .execute(&db)
         .await?;
 
     Ok(())
 }
+
+pub async fn get_all_accounts(db: SqlitePool) -> Result<Vec<Account>, sqlx::Error> {
+    sqlx::query_as!(
+        Account,
+        r#"SELECT id, code, name, kind AS "kind: AccountKind" FROM account ORDER BY code"#
+    )
+    .fetch_all(&db)
+    .await
+}
+
+pub async fn add_account(
+    db: SqlitePool,
+    code: String,
+    name: String,
+    kind: AccountKind,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "INSERT INTO account (code, name, kind) VALUES (?, ?, ?)",
+        code,
+        name,
+        kind
+    )
+    .execute(&db)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn edit_account(
+    db: SqlitePool,
+    Account {
+        id,
+        code,
+        name,
+        kind,
+    }: Account,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "UPDATE account SET code = ?, name = ?, kind = ? WHERE id = ?",
+        code,
+        name,
+        kind,
+        id
+    )
+    .execute(&db)
+    .await?;
+
+    Ok(())
+}
+
+/// Balances on the account are deleted with it, the counterparties stay. Fails when the account
+/// has balances in a closed period.
+pub async fn delete_account(db: SqlitePool, id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query!("DELETE FROM account WHERE id = ?", id)
+        .execute(&db)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn get_all_periods(db: SqlitePool) -> Result<Vec<Period>, sqlx::Error> {
+    sqlx::query_as!(
+        Period,
+        r#"SELECT id, year, month, closed AS "closed: bool" FROM period ORDER BY year, month"#
+    )
+    .fetch_all(&db)
+    .await
+}
+
+/// Opens the month after the latest period. Closing balances of the latest period become the
+/// opening balances of the new one.
+pub async fn add_next_period(db: SqlitePool) -> Result<(), sqlx::Error> {
+    let mut tx = db.begin().await?;
+
+    let latest =
+        sqlx::query!("SELECT id, year, month FROM period ORDER BY year DESC, month DESC LIMIT 1")
+            .fetch_one(&mut *tx)
+            .await?;
+
+    let (year, month) = match latest.month {
+        12 => (latest.year + 1, 1),
+        month => (latest.year, month + 1),
+    };
+
+    let period_id = sqlx::query_scalar!(
+        "INSERT INTO period (year, month) VALUES (?, ?) RETURNING id",
+        year,
+        month
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"INSERT INTO balance (company_id, account_id, period_id, remainder_begin_month, debit_turnover, credit_turnover, remainder_end_month)
+        SELECT company_id, account_id, ?, remainder_end_month, 0, 0, remainder_end_month
+        FROM balance
+        WHERE period_id = ?"#,
+        period_id,
+        latest.id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await
+}
+
+/// Closes or reopens the period and records it in the period log.
+pub async fn set_period_closed(db: SqlitePool, id: i64, closed: bool) -> Result<(), sqlx::Error> {
+    let action = if closed { "close" } else { "reopen" };
+    let mut tx = db.begin().await?;
+
+    sqlx::query!("UPDATE period SET closed = ? WHERE id = ?", closed, id)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query!(
+        "INSERT INTO period_log (period_id, action) VALUES (?, ?)",
+        id,
+        action
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await
+}
+
+/// Totals of every period in the reporting currency, for the whole view the same way the table
+/// adds them up: balances in the reporting currency, or with none set, are taken as they are.
+pub async fn get_period_totals(
+    db: SqlitePool,
+    view: AccountView,
+    reporting_currency: &str,
+) -> Result<Vec<(i64, TotalRow)>, sqlx::Error> {
+    let account_id = match view {
+        AccountView::Account(id) => Some(id),
+        AccountView::Consolidated => None,
+    };
+
+    let balances = sqlx::query!(
+        r#"SELECT b.period_id,
+        SUM(b.remainder_begin_month) AS "remainder_begin_month!: f64",
+        SUM(b.debit_turnover) AS "debit_turnover!: f64",
+        SUM(b.credit_turnover) AS "credit_turnover!: f64",
+        SUM(b.remainder_end_month) AS "remainder_end_month!: f64",
+        CASE WHEN c.currency IN ('', ?) THEN 1.0
+        ELSE COALESCE((SELECT r.rate FROM exchange_rate r
+            WHERE r.currency = c.currency AND r.year * 12 + r.month <= p.year * 12 + p.month
+            ORDER BY r.year DESC, r.month DESC LIMIT 1), 1.0) END AS "rate!: f64"
+        FROM balance b
+        JOIN period p ON p.id = b.period_id
+        JOIN company c ON c.id = b.company_id
+        WHERE ? IS NULL OR b.account_id = ?
+        GROUP BY b.period_id, b.company_id
+        ORDER BY p.year, p.month"#,
+        reporting_currency,
+        account_id,
+        account_id
+    )
+    .fetch_all(&db)
+    .await?;
+
+    let mut totals: Vec<(i64, Vec<Company>)> = Vec::new();
+    for balance in balances {
+        let company = Company {
+            remainder_begin_month: balance.remainder_begin_month * balance.rate,
+            debit_turnover: balance.debit_turnover * balance.rate,
+            credit_turnover: balance.credit_turnover * balance.rate,
+            remainder_end_month: balance.remainder_end_month * balance.rate,
+            ..Default::default()
+        };
+
+        match totals.last_mut() {
+            Some((period_id, companies)) if *period_id == balance.period_id => {
+                companies.push(company)
+            }
+            _ => totals.push((balance.period_id, vec![company])),
+        }
+    }
+
+    Ok(totals
+        .into_iter()
+        .map(|(period_id, companies)| (period_id, companies.iter().collect()))
+        .collect())
+}
+
+/// Balances of one counterparty in every period, oldest first.
+pub async fn get_company_history(
+    db: SqlitePool,
+    company_id: i64,
+    view: AccountView,
+) -> Result<Vec<PeriodBalance>, sqlx::Error> {
+    let account_id = match view {
+        AccountView::Account(id) => Some(id),
+        AccountView::Consolidated => None,
+    };
+
+    sqlx::query_as!(
+        PeriodBalance,
+        r#"SELECT b.period_id,
+        SUM(b.remainder_begin_month) AS "remainder_begin_month!: f64",
+        SUM(b.debit_turnover) AS "debit_turnover!: f64",
+        SUM(b.credit_turnover) AS "credit_turnover!: f64",
+        SUM(b.remainder_end_month) AS "remainder_end_month!: f64"
+        FROM balance b
+        JOIN period p ON p.id = b.period_id
+        WHERE b.company_id = ? AND (? IS NULL OR b.account_id = ?)
+        GROUP BY b.period_id
+        ORDER BY p.year, p.month"#,
+        company_id,
+        account_id,
+        account_id
+    )
+    .fetch_all(&db)
+    .await
+}
+
+/// Balances of every company in every period, ordered by company and then by period.
+pub async fn get_balance_history(
+    db: SqlitePool,
+    view: AccountView,
+) -> Result<Vec<(i64, PeriodBalance)>, sqlx::Error> {
+    let account_id = match view {
+        AccountView::Account(id) => Some(id),
+        AccountView::Consolidated => None,
+    };
+
+    let records = sqlx::query!(
+        r#"SELECT b.company_id, b.period_id,
+        SUM(b.remainder_begin_month) AS "remainder_begin_month!: f64",
+        SUM(b.debit_turnover) AS "debit_turnover!: f64",
+        SUM(b.credit_turnover) AS "credit_turnover!: f64",
+        SUM(b.remainder_end_month) AS "remainder_end_month!: f64"
+        FROM balance b
+        JOIN period p ON p.id = b.period_id
+        WHERE ? IS NULL OR b.account_id = ?
+        GROUP BY b.company_id, b.period_id
+        ORDER BY b.company_id, p.year, p.month"#,
+        account_id,
+        account_id
+    )
+    .fetch_all(&db)
+    .await?;
+
+    Ok(records
+        .into_iter()
+        .map(|record| {
+            (
+                record.company_id,
+                PeriodBalance {
+                    period_id: record.period_id,
+                    remainder_begin_month: record.remainder_begin_month,
+                    debit_turnover: record.debit_turnover,
+                    credit_turnover: record.credit_turnover,
+                    remainder_end_month: record.remainder_end_month,
+                },
+            )
+        })
+        .collect())
+}
+
+pub async fn get_all_rates(db: SqlitePool) -> Result<Vec<ExchangeRate>, sqlx::Error> {
+    sqlx::query_as!(
+        ExchangeRate,
+        "SELECT currency, year, month, rate FROM exchange_rate ORDER BY currency, year, month"
+    )
+    .fetch_all(&db)
+    .await
+}
+
+/// Adds the rates or replaces the ones already known for the same currency and month, all or
+/// nothing.
+pub async fn save_rates(db: SqlitePool, rates: Vec<ExchangeRate>) -> Result<(), sqlx::Error> {
+    let mut tx = db.begin().await?;
+
+    for ExchangeRate {
+        currency,
+        year,
+        month,
+        rate,
+    } in rates
+    {
+        sqlx::query!(
+            r#"INSERT INTO exchange_rate (currency, year, month, rate) VALUES (?, ?, ?, ?)
+            ON CONFLICT (currency, year, month) DO UPDATE SET rate = excluded.rate"#,
+            currency,
+            year,
+            month,
+            rate
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await
+}
+
+pub async fn delete_rate(
+    db: SqlitePool,
+    currency: String,
+    year: i64,
+    month: i64,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "DELETE FROM exchange_rate WHERE currency = ? AND year = ? AND month = ?",
+        currency,
+        year,
+        month
+    )
+    .execute(&db)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn get_all_rules(db: SqlitePool) -> Result<Vec<HighlightRule>, sqlx::Error> {
+    sqlx::query_as!(
+        HighlightRule,
+        r#"SELECT id, field AS "field: RuleField", comparison AS "comparison: Comparison", value,
+        color
+        FROM highlight_rule
+        ORDER BY id"#
+    )
+    .fetch_all(&db)
+    .await
+}
+
+pub async fn add_rule(
+    db: SqlitePool,
+    field: RuleField,
+    comparison: Comparison,
+    value: f64,
+    color: String,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "INSERT INTO highlight_rule (field, comparison, value, color) VALUES (?, ?, ?, ?)",
+        field,
+        comparison,
+        value,
+        color
+    )
+    .execute(&db)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn edit_rule(
+    db: SqlitePool,
+    HighlightRule {
+        id,
+        field,
+        comparison,
+        value,
+        color,
+    }: HighlightRule,
+) -> Result<(), sqlx::Error> {
+    sqlx::query!(
+        "UPDATE highlight_rule SET field = ?, comparison = ?, value = ?, color = ? WHERE id = ?",
+        field,
+        comparison,
+        value,
+        color,
+        id
+    )
+    .execute(&db)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn delete_rule(db: SqlitePool, id: i64) -> Result<(), sqlx::Error> {
+    sqlx::query!("DELETE FROM highlight_rule WHERE id = ?", id)
+        .execute(&db)
+        .await?;
+
+    Ok(())
+}
+
+/// Every table as records, in the order of their ids.
+pub async fn export_dataset(db: SqlitePool) -> Result<Dataset, sqlx::Error> {
+    let mut tx = db.begin().await?;
+
+    let exported_at =
+        sqlx::query_scalar!(r#"SELECT datetime('now', 'localtime') AS "now!: String""#)
+            .fetch_one(&mut *tx)
+            .await?;
+    let groups = sqlx::query_as!(
+        GroupRecord,
+        "SELECT id, name, parent_id FROM company_group ORDER BY id"
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    let accounts = sqlx::query_as!(
+        AccountRecord,
+        "SELECT id, code, name, kind FROM account ORDER BY id"
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    let periods = sqlx::query_as!(
+        PeriodRecord,
+        r#"SELECT id, year, month, closed AS "closed: bool" FROM period ORDER BY id"#
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    let companies = sqlx::query_as!(
+        CompanyRecord,
+        "SELECT id, name, tax_id, legal_address, contact_person, phone, email, iban, bic,
+        contract_number, note, group_id, currency, debit_limit, credit_limit
+        FROM company ORDER BY id"
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    let balances = sqlx::query_as!(
+        BalanceRecord,
+        "SELECT company_id, account_id, period_id, remainder_begin_month, debit_turnover,
+        credit_turnover, remainder_end_month
+        FROM balance ORDER BY period_id, account_id, company_id"
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    let exchange_rates = sqlx::query_as!(
+        ExchangeRateRecord,
+        "SELECT currency, year, month, rate FROM exchange_rate ORDER BY currency, year, month"
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    let highlight_rules = sqlx::query_as!(
+        HighlightRuleRecord,
+        "SELECT id, field, comparison, value, color FROM highlight_rule ORDER BY id"
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    let tags = sqlx::query_as!(TagRecord, "SELECT id, name FROM tag ORDER BY id")
+        .fetch_all(&mut *tx)
+        .await?;
+    let company_tags = sqlx::query_as!(
+        CompanyTagRecord,
+        "SELECT company_id, tag_id FROM company_tag ORDER BY company_id, tag_id"
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    let attachments = sqlx::query_as!(
+        AttachmentRecord,
+        "SELECT id, company_id, file_name, created_at, data FROM attachment ORDER BY id"
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    let period_log = sqlx::query_as!(
+        PeriodLogRecord,
+        "SELECT id, period_id, action, created_at FROM period_log ORDER BY id"
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+    let merge_log = sqlx::query_as!(
+        MergeLogRecord,
+        "SELECT id, company_id, merged_id, merged_name, merged_tax_id, balances_moved,
+        balances_combined, created_at
+        FROM company_merge_log ORDER BY id"
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Dataset {
+        format: DATASET_FORMAT.to_string(),
+        version: DATASET_VERSION,
+        exported_at,
+        groups,
+        accounts,
+        periods,
+        companies,
+        balances,
+        exchange_rates,
+        highlight_rules,
+        tags,
+        company_tags,
+        attachments,
+        period_log,
+        merge_log,
+    })
+}
+
+/// Replaces the database with the dataset or merges the dataset into it, in one transaction
+/// either way. A merge with any conflict changes nothing. Returns what was added.
+pub async fn import_dataset(
+    db: SqlitePool,
+    dataset: Dataset,
+    mode: DatasetMode,
+) -> Result<DatasetCounts, DatasetError> {
+    let parents = dataset
+        .groups
+        .iter()
+        .map(|group| (group.id, group.parent_id))
+        .collect();
+    let cyclic = parent_cycles(&parents);
+    if !cyclic.is_empty() {
+        let names = dataset
+            .groups
+            .iter()
+            .filter(|group| cyclic.contains(&group.id))
+            .map(|group| group.name.clone())
+            .collect();
+        return Err(DatasetError::GroupCycles(names));
+    }
+
+    let dataset = match mode {
+        DatasetMode::Replace => dataset,
+        DatasetMode::Merge => export_dataset(db.clone())
+            .await?
+            .merge_new(&dataset)
+            .map_err(DatasetError::Conflicts)?,
+    };
+
+    let mut tx = db.begin().await?;
+
+    if mode == DatasetMode::Replace {
+        // a closed period is only reopened through its own dialog, where it gets logged
+        let closed = sqlx::query_as!(
+            Period,
+            r#"SELECT id, year, month, closed AS "closed: bool" FROM period
+            WHERE closed ORDER BY year, month"#
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        if !closed.is_empty() {
+            return Err(DatasetError::ClosedPeriods(
+                closed.iter().map(Period::label).collect(),
+            ));
+        }
+
+        sqlx::query!(
+            "DELETE FROM company_tag;
+            DELETE FROM attachment;
+            DELETE FROM company_merge_log;
+            DELETE FROM period_log;
+            DELETE FROM balance;
+            DELETE FROM company;
+            DELETE FROM company_group;
+            DELETE FROM account;
+            DELETE FROM period;
+            DELETE FROM exchange_rate;
+            DELETE FROM highlight_rule;
+            DELETE FROM tag;"
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    // parents are set once every group is in, a group may come before its parent
+    for group in &dataset.groups {
+        sqlx::query!(
+            "INSERT INTO company_group (id, name) VALUES (?, ?)",
+            group.id,
+            group.name
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+    for group in &dataset.groups {
+        sqlx::query!(
+            "UPDATE company_group SET parent_id = ? WHERE id = ?",
+            group.parent_id,
+            group.id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    for account in &dataset.accounts {
+        sqlx::query!(
+            "INSERT INTO account (id, code, name, kind) VALUES (?, ?, ?, ?)",
+            account.id,
+            account.code,
+            account.name,
+            account.kind
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    // periods are closed after their balances are in
+    for period in &dataset.periods {
+        sqlx::query!(
+            "INSERT INTO period (id, year, month, closed) VALUES (?, ?, ?, FALSE)",
+            period.id,
+            period.year,
+            period.month
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    for company in &dataset.companies {
+        sqlx::query!(
+            "INSERT INTO company (id, name, tax_id, legal_address, contact_person, phone, email,
+            iban, bic, contract_number, note, group_id, currency, debit_limit, credit_limit)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            company.id,
+            company.name,
+            company.tax_id,
+            company.legal_address,
+            company.contact_person,
+            company.phone,
+            company.email,
+            company.iban,
+            company.bic,
+            company.contract_number,
+            company.note,
+            company.group_id,
+            company.currency,
+            company.debit_limit,
+            company.credit_limit
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    for balance in &dataset.balances {
+        sqlx::query!(
+            "INSERT INTO balance (company_id, account_id, period_id, remainder_begin_month,
+            debit_turnover, credit_turnover, remainder_end_month)
+            VALUES (?, ?, ?, ?, ?, ?, ?)",
+            balance.company_id,
+            balance.account_id,
+            balance.period_id,
+            balance.remainder_begin_month,
+            balance.debit_turnover,
+            balance.credit_turnover,
+            balance.remainder_end_month
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    for rate in &dataset.exchange_rates {
+        sqlx::query!(
+            "INSERT INTO exchange_rate (currency, year, month, rate) VALUES (?, ?, ?, ?)",
+            rate.currency,
+            rate.year,
+            rate.month,
+            rate.rate
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    for rule in &dataset.highlight_rules {
+        sqlx::query!(
+            "INSERT INTO highlight_rule (id, field, comparison, value, color)
+            VALUES (?, ?, ?, ?, ?)",
+            rule.id,
+            rule.field,
+            rule.comparison,
+            rule.value,
+            rule.color
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    for tag in &dataset.tags {
+        sqlx::query!("INSERT INTO tag (id, name) VALUES (?, ?)", tag.id, tag.name)
+            .execute(&mut *tx)
+            .await?;
+    }
+    for link in &dataset.company_tags {
+        sqlx::query!(
+            "INSERT INTO company_tag (company_id, tag_id) VALUES (?, ?)",
+            link.company_id,
+            link.tag_id
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    for attachment in &dataset.attachments {
+        sqlx::query!(
+            "INSERT INTO attachment (id, company_id, file_name, data, created_at)
+            VALUES (?, ?, ?, ?, ?)",
+            attachment.id,
+            attachment.company_id,
+            attachment.file_name,
+            attachment.data,
+            attachment.created_at
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    for log in &dataset.period_log {
+        sqlx::query!(
+            "INSERT INTO period_log (id, period_id, action, created_at) VALUES (?, ?, ?, ?)",
+            log.id,
+            log.period_id,
+            log.action,
+            log.created_at
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+    for log in &dataset.merge_log {
+        sqlx::query!(
+            "INSERT INTO company_merge_log (id, company_id, merged_id, merged_name, merged_tax_id,
+            balances_moved, balances_combined, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            log.id,
+            log.company_id,
+            log.merged_id,
+            log.merged_name,
+            log.merged_tax_id,
+            log.balances_moved,
+            log.balances_combined,
+            log.created_at
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    for period in dataset.periods.iter().filter(|period| period.closed) {
+        sqlx::query!("UPDATE period SET closed = TRUE WHERE id = ?", period.id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(dataset.counts())
+}
@@ -0,0 +1,49 @@
+use egui::Color32;
+
+use super::{
+    i18n::{Language, Message},
+    model::Organization,
+    validation::validate_tax_id,
+};
+
+/// Our details for the documents we send. They are kept with the rest of the settings.
+pub fn organization_window(
+    ctx: &egui::Context,
+    open: &mut bool,
+    organization: &mut Organization,
+    language: Language,
+) {
+    egui::Window::new(language.tr(Message::OurOrganization))
+        .open(open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            egui::Grid::new("organization_grid")
+                .num_columns(2)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label(language.tr(Message::Name));
+                    ui.text_edit_singleline(&mut organization.name);
+                    ui.end_row();
+
+                    ui.label(language.tr(Message::TaxId));
+                    ui.vertical(|ui| {
+                        ui.text_edit_singleline(&mut organization.tax_id);
+                        if !organization.tax_id.is_empty() {
+                            match validate_tax_id(&organization.tax_id) {
+                                Ok(kind) => ui.weak(language.tr(kind.title())),
+                                Err(error) => ui.colored_label(Color32::RED, language.tr(error)),
+                            };
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label(language.tr(Message::LegalAddress));
+                    ui.text_edit_singleline(&mut organization.legal_address);
+                    ui.end_row();
+
+                    ui.label(language.tr(Message::Signatory));
+                    ui.text_edit_singleline(&mut organization.signatory);
+                    ui.end_row();
+                });
+        });
+}
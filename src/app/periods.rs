@@ -0,0 +1,212 @@
+use super::{
+    i18n::{Language, Message},
+    model::Period,
+};
+
+impl Period {
+    pub fn label(&self) -> String {
+        format!("{:02}.{}", self.month, self.year)
+    }
+
+    pub fn first_day(&self) -> String {
+        format!("01.{:02}.{}", self.month, self.year)
+    }
+
+    pub fn last_day(&self) -> String {
+        format!("{}.{:02}.{}", self.days(), self.month, self.year)
+    }
+
+    fn days(&self) -> i64 {
+        let leap = self.year % 4 == 0 && (self.year % 100 != 0 || self.year % 400 == 0);
+        match self.month {
+            2 if leap => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
+    /// Number of the first day of the period, counted in days so that periods can be subtracted.
+    pub fn first_day_number(&self) -> i64 {
+        day_number(self.year, self.month, 1)
+    }
+
+    pub fn last_day_number(&self) -> i64 {
+        day_number(self.year, self.month, self.days())
+    }
+}
+
+/// Days since 1970-01-01 in the proleptic Gregorian calendar.
+fn day_number(year: i64, month: i64, day: i64) -> i64 {
+    // years start in March so that the leap day is the last day of a year
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// `None` stands for the latest period, so a new month is picked up without choosing it.
+pub fn period_selector(
+    ui: &mut egui::Ui,
+    selected: &mut Option<i64>,
+    periods: &[Period],
+    language: Language,
+) {
+    let label = |period: &Period| {
+        if period.closed {
+            format!("🔒 {}", period.label())
+        } else {
+            period.label()
+        }
+    };
+
+    let current = match selected {
+        Some(id) => periods.iter().find(|period| period.id == *id),
+        None => periods.last(),
+    };
+
+    ui.label(language.tr(Message::Period));
+    egui::ComboBox::from_id_source("period_selector")
+        .selected_text(current.map(label).unwrap_or_default())
+        .show_ui(ui, |ui| {
+            for period in periods.iter().rev() {
+                ui.selectable_value(selected, Some(period.id), label(period));
+            }
+        });
+}
+
+pub fn period_combo(
+    ui: &mut egui::Ui,
+    id_source: &str,
+    selected: &mut Option<i64>,
+    periods: &[Period],
+) {
+    let text = selected
+        .and_then(|id| periods.iter().find(|period| period.id == id))
+        .map(Period::label)
+        .unwrap_or_default();
+
+    egui::ComboBox::from_id_source(id_source)
+        .selected_text(text)
+        .show_ui(ui, |ui| {
+            for period in periods.iter().rev() {
+                ui.selectable_value(selected, Some(period.id), period.label());
+            }
+        });
+}
+
+/// Picks the periods of a workbook with a sheet for every month.
+#[derive(Default)]
+pub struct PeriodRangeDialog {
+    pub open: bool,
+    from: Option<i64>,
+    to: Option<i64>,
+}
+
+impl PeriodRangeDialog {
+    /// The range starts at the beginning of the year of the period shown.
+    pub fn open(&mut self, periods: &[Period], current: Option<&Period>) {
+        self.open = true;
+        self.to = current.map(|period| period.id);
+        self.from = current
+            .and_then(|current| periods.iter().find(|period| period.year == current.year))
+            .map(|period| period.id);
+    }
+
+    /// Returns the periods from the first to the last one picked, oldest first.
+    pub fn dialog_ui(
+        &mut self,
+        ctx: &egui::Context,
+        periods: &[Period],
+        language: Language,
+    ) -> Option<Vec<Period>> {
+        let mut picked = None;
+        let mut open = self.open;
+
+        egui::Window::new(language.tr(Message::PeriodsWorkbook))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("period_range_grid")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(language.tr(Message::PeriodFrom));
+                        period_combo(ui, "period_range_from", &mut self.from, periods);
+                        ui.end_row();
+
+                        ui.label(language.tr(Message::PeriodTo));
+                        period_combo(ui, "period_range_to", &mut self.to, periods);
+                        ui.end_row();
+                    });
+
+                let position = |id: Option<i64>| {
+                    id.and_then(|id| periods.iter().position(|period| period.id == id))
+                };
+                let range = match (position(self.from), position(self.to)) {
+                    (Some(from), Some(to)) if from <= to => Some(&periods[from..=to]),
+                    _ => None,
+                };
+
+                ui.separator();
+                let text = language.trf(
+                    Message::ExportPeriods,
+                    &[("count", &range.map_or(0, <[Period]>::len))],
+                );
+                if ui
+                    .add_enabled(range.is_some(), egui::Button::new(text))
+                    .clicked()
+                {
+                    picked = range.map(<[Period]>::to_vec);
+                }
+            });
+
+        self.open = open && picked.is_none();
+        picked
+    }
+}
+
+/// Asks before a closed period is opened for changes again.
+#[derive(Default)]
+pub struct ReopenDialog {
+    period: Option<Period>,
+}
+
+impl ReopenDialog {
+    pub fn open(&mut self, period: Period) {
+        self.period = Some(period);
+    }
+
+    /// Returns the id of the period once reopening is confirmed.
+    pub fn dialog_ui(&mut self, ctx: &egui::Context, language: Language) -> Option<i64> {
+        let period = self.period.as_ref()?;
+        let mut confirmed = None;
+        let mut close = false;
+
+        egui::Window::new(language.tr(Message::ReopenPeriod))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(language.trf(Message::ReopenConfirm, &[("period", &period.label())]));
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(language.tr(Message::ReopenPeriod)).clicked() {
+                        confirmed = Some(period.id);
+                        close = true;
+                    }
+                    if ui.button(language.tr(Message::Cancel)).clicked() {
+                        close = true;
+                    }
+                });
+            });
+
+        if close {
+            self.period = None;
+        }
+        confirmed
+    }
+}
@@ -0,0 +1,100 @@
+use super::{
+    i18n::{Language, Message},
+    model::{Company, Period},
+    periods::period_combo,
+};
+
+pub enum ReconciliationFormat {
+    Pdf,
+    Excel,
+}
+
+pub struct ReconciliationRequest {
+    pub company_id: i64,
+    pub from: i64,
+    pub to: i64,
+    pub format: ReconciliationFormat,
+}
+
+/// Picks the range of periods and the format of a reconciliation statement.
+#[derive(Default)]
+pub struct ReconciliationDialog {
+    company: Option<(i64, String)>,
+    from: Option<i64>,
+    to: Option<i64>,
+}
+
+impl ReconciliationDialog {
+    pub fn open(&mut self, company: &Company, period: Option<&Period>) {
+        self.company = Some((company.id, company.name.clone()));
+        self.from = period.map(|period| period.id);
+        self.to = self.from;
+    }
+
+    pub fn dialog_ui(
+        &mut self,
+        ctx: &egui::Context,
+        periods: &[Period],
+        language: Language,
+    ) -> Option<ReconciliationRequest> {
+        let (company_id, name) = self.company.clone()?;
+        let mut request = None;
+        let mut open = true;
+
+        egui::Window::new(language.tr(Message::ReconciliationAct))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.strong(&name);
+                ui.separator();
+
+                egui::Grid::new("reconciliation_grid")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label(language.tr(Message::PeriodFrom));
+                        period_combo(ui, "reconciliation_from", &mut self.from, periods);
+                        ui.end_row();
+
+                        ui.label(language.tr(Message::PeriodTo));
+                        period_combo(ui, "reconciliation_to", &mut self.to, periods);
+                        ui.end_row();
+                    });
+
+                let position = |id: Option<i64>| {
+                    id.and_then(|id| periods.iter().position(|period| period.id == id))
+                };
+                let range = match (position(self.from), position(self.to)) {
+                    (Some(from), Some(to)) if from <= to => {
+                        Some((periods[from].id, periods[to].id))
+                    }
+                    _ => None,
+                };
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    for (format, title) in [
+                        (ReconciliationFormat::Pdf, Message::Pdf),
+                        (ReconciliationFormat::Excel, Message::Excel),
+                    ] {
+                        let button =
+                            ui.add_enabled(range.is_some(), egui::Button::new(language.tr(title)));
+
+                        if let (true, Some((from, to))) = (button.clicked(), range) {
+                            request = Some(ReconciliationRequest {
+                                company_id,
+                                from,
+                                to,
+                                format,
+                            });
+                        }
+                    }
+                });
+            });
+
+        if !open || request.is_some() {
+            self.company = None;
+        }
+        request
+    }
+}
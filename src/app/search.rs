@@ -0,0 +1,165 @@
+use super::model::SearchHit;
+
+const CYRILLIC: [(char, &str); 33] = [
+    ('а', "a"),
+    ('б', "b"),
+    ('в', "v"),
+    ('г', "g"),
+    ('д', "d"),
+    ('е', "e"),
+    ('ё', "e"),
+    ('ж', "zh"),
+    ('з', "z"),
+    ('и', "i"),
+    ('й', "y"),
+    ('к', "k"),
+    ('л', "l"),
+    ('м', "m"),
+    ('н', "n"),
+    ('о', "o"),
+    ('п', "p"),
+    ('р', "r"),
+    ('с', "s"),
+    ('т', "t"),
+    ('у', "u"),
+    ('ф', "f"),
+    ('х', "kh"),
+    ('ц', "ts"),
+    ('ч', "ch"),
+    ('ш', "sh"),
+    ('щ', "shch"),
+    ('ъ', ""),
+    ('ы', "y"),
+    ('ь', ""),
+    ('э', "e"),
+    ('ю', "yu"),
+    ('я', "ya"),
+];
+
+/// Latin letters back to Cyrillic, longer spellings first so `sh` isn't read as `с` and `х`.
+const LATIN: [(&str, char); 32] = [
+    ("shch", 'щ'),
+    ("zh", 'ж'),
+    ("kh", 'х'),
+    ("ts", 'ц'),
+    ("ch", 'ч'),
+    ("sh", 'ш'),
+    ("yu", 'ю'),
+    ("ya", 'я'),
+    ("a", 'а'),
+    ("b", 'б'),
+    ("c", 'к'),
+    ("d", 'д'),
+    ("e", 'е'),
+    ("f", 'ф'),
+    ("g", 'г'),
+    ("h", 'х'),
+    ("i", 'и'),
+    ("j", 'ж'),
+    ("k", 'к'),
+    ("l", 'л'),
+    ("m", 'м'),
+    ("n", 'н'),
+    ("o", 'о'),
+    ("p", 'п'),
+    ("q", 'к'),
+    ("r", 'р'),
+    ("s", 'с'),
+    ("t", 'т'),
+    ("u", 'у'),
+    ("v", 'в'),
+    ("w", 'в'),
+    ("y", 'й'),
+];
+
+fn to_latin(word: &str) -> String {
+    word.chars()
+        .map(
+            |c| match CYRILLIC.iter().find(|(cyrillic, _)| *cyrillic == c) {
+                Some((_, latin)) => latin.to_string(),
+                None => c.to_string(),
+            },
+        )
+        .collect()
+}
+
+fn to_cyrillic(word: &str) -> String {
+    let mut cyrillic = String::new();
+    let mut rest = word;
+
+    while let Some(c) = rest.chars().next() {
+        match LATIN.iter().find(|(latin, _)| rest.starts_with(latin)) {
+            Some((latin, letter)) => {
+                cyrillic.push(*letter);
+                rest = &rest[latin.len()..];
+            }
+            None => {
+                cyrillic.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+
+    cyrillic
+}
+
+/// FTS5 query matching every word of the input as a prefix, spelled as typed or transliterated
+/// either way, so "romashka" finds "Ромашка". `None` when there is nothing to look for.
+pub fn fts_query(input: &str) -> Option<String> {
+    let words: Vec<String> = input
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect();
+    if words.is_empty() {
+        return None;
+    }
+
+    let mut spellings = vec![words.clone()];
+    for transliterate in [to_latin, to_cyrillic] {
+        let spelling: Vec<_> = words.iter().map(|word| transliterate(word)).collect();
+        if !spellings.contains(&spelling) {
+            spellings.push(spelling);
+        }
+    }
+
+    let query = spellings
+        .iter()
+        .map(|spelling| {
+            let words: Vec<_> = spelling.iter().map(|word| format!("\"{word}\"*")).collect();
+            format!("({})", words.join(" "))
+        })
+        .collect::<Vec<_>>()
+        .join(" OR ");
+
+    Some(query)
+}
+
+/// Results of the search under the filter field, best first. Returns the id of the company
+/// picked.
+pub fn search_popup(ui: &mut egui::Ui, field: &egui::Response, hits: &[SearchHit]) -> Option<i64> {
+    let popup_id = ui.make_persistent_id("company_search_popup");
+    if field.has_focus() && !hits.is_empty() {
+        ui.memory_mut(|memory| memory.open_popup(popup_id));
+    }
+
+    let mut picked = None;
+    egui::popup_below_widget(ui, popup_id, field, |ui| {
+        ui.set_min_width(field.rect.width().max(250.0));
+        for hit in hits {
+            let response = ui.selectable_label(false, &hit.name);
+            if hit.snippet != hit.name {
+                ui.weak(&hit.snippet);
+            }
+            if response.clicked() {
+                picked = Some(hit.id);
+            }
+        }
+    });
+
+    if picked.is_some() {
+        ui.memory_mut(|memory| memory.close_popup());
+    }
+    picked
+}
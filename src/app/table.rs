@@ -1,28 +1,117 @@
-use egui::Ui;
+use egui::{Color32, Ui};
 use egui_extras::{Column, TableBuilder, TableRow};
 
-use super::{model::Company, EditedCompanyRow, Row};
+use std::collections::HashSet;
+
+use super::{
+    currency::Currencies,
+    expression::{evaluate, is_expression},
+    format::NumberFormat,
+    groups::{group_items, GroupedItem},
+    highlight::matching_rule,
+    i18n::{Language, Message},
+    limits::OVER_LIMIT_COLOR,
+    model::{Company, Group, HighlightRule, SearchHit, Tag},
+    tags::{has_tags, tag_chip, tags_of, CompanyTags},
+    EditedCompanyRow, NewCompanyRow, Row, TotalRow,
+};
 
 pub struct CompanyTable<'a> {
     rows: &'a mut Vec<Row>,
     selected_rows: &'a mut std::collections::HashSet<usize>,
+    number_format: &'a NumberFormat,
+    language: Language,
+    scroll_to_row: Option<usize>,
+    filter: &'a str,
+    search_hits: &'a [SearchHit],
+    groups: Option<(&'a [Group], &'a mut HashSet<Option<i64>>)>,
+    currencies: Option<&'a Currencies>,
+    over_limit_only: bool,
+    rules: &'a [HighlightRule],
+    tags: Option<(&'a [Tag], &'a CompanyTags, &'a HashSet<i64>)>,
 }
 
 impl<'a> CompanyTable<'a> {
     pub fn new(
         rows: &'a mut Vec<Row>,
         selected_rows: &'a mut std::collections::HashSet<usize>,
+        number_format: &'a NumberFormat,
+        language: Language,
     ) -> CompanyTable<'a> {
         Self {
             rows,
             selected_rows,
+            number_format,
+            language,
+            scroll_to_row: None,
+            filter: "",
+            search_hits: &[],
+            groups: None,
+            currencies: None,
+            over_limit_only: false,
+            rules: &[],
+            tags: None,
         }
     }
 
-    pub fn table_ui(&mut self, ui: &mut egui::Ui) {
+    /// Shows companies under collapsible group headers with subtotals.
+    pub fn groups(mut self, groups: &'a [Group], collapsed: &'a mut HashSet<Option<i64>>) -> Self {
+        self.groups = Some((groups, collapsed));
+        self
+    }
+
+    /// Shows foreign currency balances in the reporting currency too and adds up subtotals in it.
+    pub fn currencies(mut self, currencies: &'a Currencies) -> Self {
+        self.currencies = Some(currencies);
+        self
+    }
+
+    /// Shows the companies that match the query as typed or that the full-text search found
+    /// for it, so a transliterated name or a word form finds them in the table as well.
+    pub fn filter(mut self, query: &'a str, search_hits: &'a [SearchHit]) -> Self {
+        self.filter = query;
+        self.search_hits = search_hits;
+        self
+    }
+
+    /// Colors every company row with the first rule it matches.
+    pub fn rules(mut self, rules: &'a [HighlightRule]) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Shows the tags of every company next to its name and hides the companies that lack any
+    /// of the tags in `filter`.
+    pub fn tags(
+        mut self,
+        tags: &'a [Tag],
+        company_tags: &'a CompanyTags,
+        filter: &'a HashSet<i64>,
+    ) -> Self {
+        self.tags = Some((tags, company_tags, filter));
+        self
+    }
+
+    /// Hides the companies whose closing balance is within their limits.
+    pub fn over_limit_only(mut self, over_limit_only: bool) -> Self {
+        self.over_limit_only = over_limit_only;
+        self
+    }
+
+    pub fn scroll_to_row(mut self, row: Option<usize>) -> Self {
+        self.scroll_to_row = row;
+        self
+    }
+
+    /// Returns the id of the company whose row was double clicked.
+    pub fn table_ui(&mut self, ui: &mut egui::Ui) -> Option<i64> {
         let available_height = ui.available_height();
+        let language = self.language;
+        let mut double_clicked = None;
+
+        let visible = self.visible_rows();
 
-        let builder = TableBuilder::new(ui)
+        let mut builder = TableBuilder::new(ui)
             .striped(true)
             .resizable(true)
             .sense(egui::Sense::click())
@@ -33,126 +122,215 @@ impl<'a> CompanyTable<'a> {
             .min_scrolled_height(0.0)
             .max_scroll_height(available_height);
 
+        let scroll_to_row = self.scroll_to_row.and_then(|row| {
+            visible
+                .iter()
+                .position(|item| matches!(item, GroupedItem::Item(i) if *i == row))
+        });
+
+        if let Some(row) = scroll_to_row {
+            builder = builder.scroll_to_row(row, Some(egui::Align::Center));
+        }
+
         builder
             .header(20.0, |mut header| {
                 header.col(|ui| {
                     ui.vertical_centered(|ui| {
-                        ui.strong("Код");
+                        ui.strong(language.tr(Message::Id));
                         ui.separator();
                     });
                 });
 
                 header.col(|ui| {
                     ui.vertical_centered(|ui| {
-                        ui.strong("Наименование");
+                        ui.strong(language.tr(Message::Name));
                         ui.separator();
                     });
                 });
-                let cols = ["Дебет", "Кредит"];
-                header.col(|ui| multi_header(ui, "Остаток на начало месяца", Some(cols)));
-                header.col(|ui| multi_header(ui, "Оборот за месяц", Some(cols)));
-                header.col(|ui| multi_header(ui, "Остаток на конец", Some(cols)));
+                let cols = [language.tr(Message::Debit), language.tr(Message::Credit)];
+                header.col(|ui| {
+                    multi_header(ui, language.tr(Message::RemainderBeginMonth), Some(cols))
+                });
+                header.col(|ui| multi_header(ui, language.tr(Message::Turnover), Some(cols)));
+                header.col(|ui| {
+                    multi_header(ui, language.tr(Message::RemainderEndMonth), Some(cols))
+                });
             })
             .body(|body| {
-                let row_height = 18.0;
-                body.rows(row_height, self.rows.len(), |mut row| {
-                    let index = row.index();
+                let row_height = if self.is_editing() {
+                    // room for the result of an expression under the field
+                    40.0
+                } else if self.has_foreign() {
+                    32.0
+                } else {
+                    18.0
+                };
+                body.rows(row_height, visible.len(), |mut row| {
+                    let index = match &visible[row.index()] {
+                        GroupedItem::Item(index) => *index,
+                        GroupedItem::Header {
+                            id,
+                            name,
+                            depth,
+                            collapsed,
+                        } => {
+                            row_group_header(&mut row, name, *depth, *collapsed);
+                            if row.response().clicked() {
+                                self.toggle_group(*id);
+                            }
+                            return;
+                        }
+                        GroupedItem::Subtotal { name, depth, total } => {
+                            let label = language.trf(Message::SubtotalFor, &[("group", name)]);
+                            row_total(&mut row, &label, *depth, total, self.number_format);
+                            return;
+                        }
+                    };
+
                     match &mut self.rows[index] {
                         Row::Constant(company) => {
                             row.set_selected(self.selected_rows.contains(&index));
-                            row_constant(&mut row, company);
+                            let rate = self
+                                .currencies
+                                .filter(|currencies| currencies.is_foreign(&company.currency))
+                                .map(|currencies| currencies.rate(&company.currency));
+                            // breaching a limit is an alert, it goes over any rule
+                            let highlight = if company.is_over_limit() {
+                                Some(OVER_LIMIT_COLOR)
+                            } else {
+                                matching_rule(self.rules, company).map(HighlightRule::fill)
+                            };
+                            let tags = match self.tags {
+                                Some((tags, company_tags, _)) => {
+                                    tags_of(tags, company_tags, company.id)
+                                }
+                                None => Vec::new(),
+                            };
+                            row_constant(
+                                &mut row,
+                                company,
+                                &tags,
+                                rate,
+                                highlight,
+                                self.number_format,
+                            );
+                            if row.response().double_clicked() {
+                                double_clicked = Some(company.id);
+                            }
                             self.toggle_selection(index, &row.response());
                         }
                         Row::BeingEdited(edit_company) => {
-                            row_editable(&mut row, edit_company);
+                            row_editable(&mut row, edit_company, self.number_format, language);
                         }
                         Row::New(new_company) => {
-                            row.col(|ui| {
-                                ui.label("");
-                            });
-
-                            row.col(|ui| {
-                                ui.text_edit_singleline(&mut new_company.name);
-                            });
-
-                            row.col(|ui| {
-                                ui.columns(3, |columns| {
-                                    if columns[0]
-                                        .text_edit_singleline(
-                                            &mut new_company.remainder_begin_month_pos,
-                                        )
-                                        .changed()
-                                    {
-                                        new_company.remainder_begin_month_neg.clear();
-                                    }
-
-                                    columns[1].add(egui::Separator::default().vertical());
-                                    if columns[2]
-                                        .text_edit_singleline(
-                                            &mut new_company.remainder_begin_month_neg,
-                                        )
-                                        .changed()
-                                    {
-                                        new_company.remainder_begin_month_pos.clear();
-                                    }
-                                });
-                            });
-
-                            row.col(|ui| {
-                                ui.columns(3, |columns| {
-                                    columns[0]
-                                        .text_edit_singleline(&mut new_company.debit_turnover);
-                                    columns[1].add(egui::Separator::default().vertical());
-                                    columns[2]
-                                        .text_edit_singleline(&mut new_company.credit_turnover);
-                                });
-                            });
+                            row_addable(&mut row, new_company, self.number_format, language);
                         }
                         Row::Total(total) => {
-                            row.col(|ui| {
-                                ui.label("");
-                            });
-
-                            row.col(|ui| {
-                                ui.label("ИТОГО");
-                            });
-
-                            row.col(|ui| {
-                                ui.columns(2, |columns| {
-                                    columns[0].vertical_centered(|ui| {
-                                        ui.label(format!("{}", total.remainder_begin_month_pos));
-                                    });
-                                    columns[1].vertical_centered(|ui| {
-                                        ui.label(format!("{}", total.remainder_begin_month_neg));
-                                    });
-                                });
-                            });
-
-                            row.col(|ui| {
-                                ui.columns(2, |columns| {
-                                    columns[0].vertical_centered(|ui| {
-                                        ui.label(format!("{}", total.debit_turnover))
-                                    });
-                                    columns[1].vertical_centered(|ui| {
-                                        ui.label(format!("{}", total.credit_turnover))
-                                    });
-                                });
-                            });
-
-                            row.col(|ui| {
-                                ui.columns(2, |columns| {
-                                    columns[0].vertical_centered(|ui| {
-                                        ui.label(format!("{}", total.remainder_end_month_pos));
-                                    });
-                                    columns[1].vertical_centered(|ui| {
-                                        ui.label(format!("{}", total.remainder_end_month_neg));
-                                    });
-                                });
-                            });
+                            row_total(
+                                &mut row,
+                                language.tr(Message::Total),
+                                0,
+                                total,
+                                self.number_format,
+                            );
                         }
                     }
                 });
             });
+
+        double_clicked
+    }
+
+    /// Rows in display order. Rows being added or edited and the total are never hidden by the
+    /// filter, with groups they go after all the groups.
+    fn visible_rows(&self) -> Vec<GroupedItem<usize>> {
+        let hits: HashSet<i64> = self.search_hits.iter().map(|hit| hit.id).collect();
+        let shown = |row: &Row| match row {
+            Row::Constant(company) => {
+                (self.filter.is_empty()
+                    || hits.contains(&company.id)
+                    || company.matches(self.filter))
+                    && (!self.over_limit_only || company.is_over_limit())
+                    && self.tags.is_none_or(|(_, company_tags, filter)| {
+                        has_tags(company_tags, company.id, filter)
+                    })
+            }
+            _ => true,
+        };
+
+        let Some((groups, collapsed)) = &self.groups else {
+            return self
+                .rows
+                .iter()
+                .enumerate()
+                .filter(|(_, row)| shown(row))
+                .map(|(i, _)| GroupedItem::Item(i))
+                .collect();
+        };
+
+        let companies: Vec<_> = self
+            .rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| shown(row))
+            .filter_map(|(i, row)| match row {
+                Row::Constant(company) => Some((i, company)),
+                _ => None,
+            })
+            .collect();
+
+        let mut visible = match self.currencies {
+            Some(currencies) => {
+                let converted: Vec<_> = companies
+                    .iter()
+                    .map(|(i, company)| (*i, currencies.convert(company)))
+                    .collect();
+                let converted: Vec<_> =
+                    converted.iter().map(|(i, company)| (*i, company)).collect();
+                group_items(&converted, groups, collapsed, self.language)
+            }
+            None => group_items(&companies, groups, collapsed, self.language),
+        };
+
+        let (totals, others): (Vec<_>, Vec<_>) = self
+            .rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| !matches!(row, Row::Constant(_)))
+            .partition(|(_, row)| matches!(row, Row::Total(_)));
+
+        visible.extend(
+            others
+                .into_iter()
+                .chain(totals)
+                .map(|(i, _)| GroupedItem::Item(i)),
+        );
+
+        visible
+    }
+
+    fn has_foreign(&self) -> bool {
+        let Some(currencies) = self.currencies else {
+            return false;
+        };
+
+        self.rows.iter().any(
+            |row| matches!(row, Row::Constant(company) if currencies.is_foreign(&company.currency)),
+        )
+    }
+
+    fn is_editing(&self) -> bool {
+        self.rows
+            .iter()
+            .any(|row| matches!(row, Row::BeingEdited(_) | Row::New(_)))
+    }
+
+    fn toggle_group(&mut self, id: Option<i64>) {
+        if let Some((_, collapsed)) = &mut self.groups {
+            if !collapsed.remove(&id) {
+                collapsed.insert(id);
+            }
+        }
     }
 
     fn toggle_selection(&mut self, row_index: usize, row_response: &egui::Response) {
@@ -167,88 +345,247 @@ impl<'a> CompanyTable<'a> {
     }
 }
 
-fn row_constant(row: &mut TableRow, company: &Company) {
+/// `rate` is set for a balance in a foreign currency, which is then also shown converted.
+fn row_constant(
+    row: &mut TableRow,
+    company: &Company,
+    tags: &[&Tag],
+    rate: Option<f64>,
+    highlight: Option<Color32>,
+    number_format: &NumberFormat,
+) {
+    let fill = |ui: &mut Ui| {
+        if let Some(color) = highlight {
+            ui.painter().rect_filled(ui.max_rect(), 0.0, color);
+        }
+    };
+
+    let amount = |ui: &mut Ui, value: f64| {
+        ui.label(number_format.format(value));
+        if let Some(rate) = rate {
+            ui.weak(number_format.format(value * rate));
+        }
+    };
+
     row.col(|ui| {
+        fill(ui);
         ui.label(format!("{}", company.id));
     });
     row.col(|ui| {
-        ui.label(&company.name);
+        fill(ui);
+        ui.horizontal(|ui| {
+            ui.label(&company.name);
+            for tag in tags {
+                tag_chip(ui, tag);
+            }
+        });
+        if rate.is_some() {
+            ui.weak(&company.currency);
+        }
     });
     row.col(|ui| {
+        fill(ui);
         ui.columns(2, |columns| {
-            let mut remainder = company.remainder_begin_month;
+            let remainder = company.remainder_begin_month;
             if remainder >= 0. {
-                columns[0].vertical_centered(|ui| {
-                    ui.label(format!("{remainder}"));
-                });
+                columns[0].vertical_centered(|ui| amount(ui, remainder));
             }
-            // columns[1].add(egui::Separator::default().vertical());
             if remainder < 0. {
-                remainder *= -1.;
-                columns[1].vertical_centered(|ui| {
-                    ui.label(format!("{remainder}"));
-                });
+                columns[1].vertical_centered(|ui| amount(ui, -remainder));
             }
         });
     });
     row.col(|ui| {
+        fill(ui);
         ui.columns(2, |columns| {
-            columns[0].vertical_centered(|ui| ui.label(format!("{}", company.debit_turnover)));
-            columns[1].vertical_centered(|ui| ui.label(format!("{}", company.credit_turnover)));
+            columns[0].vertical_centered(|ui| amount(ui, company.debit_turnover));
+            columns[1].vertical_centered(|ui| amount(ui, company.credit_turnover));
         });
     });
     row.col(|ui| {
+        fill(ui);
         ui.columns(2, |columns| {
-            let mut remainder = company.remainder_end_month;
+            let remainder = company.remainder_end_month;
             if remainder >= 0. {
-                columns[0].vertical_centered(|ui| ui.label(format!("{remainder}")));
+                columns[0].vertical_centered(|ui| amount(ui, remainder));
             }
             if remainder < 0. {
-                remainder *= -1.;
-                columns[1].vertical_centered(|ui| ui.label(format!("{remainder}")));
+                columns[1].vertical_centered(|ui| amount(ui, -remainder));
             }
         })
     });
 }
 
-fn row_editable(row: &mut TableRow, edit_company: &mut EditedCompanyRow) {
+fn row_group_header(row: &mut TableRow, name: &str, depth: usize, collapsed: bool) {
     row.col(|ui| {
-        ui.label(format!("{}", edit_company.id));
+        ui.label(if collapsed { "▶" } else { "▼" });
+    });
+    row.col(|ui| {
+        ui.add_space(depth as f32 * 12.0);
+        ui.strong(name);
+    });
+}
+
+fn row_total(
+    row: &mut TableRow,
+    label: &str,
+    depth: usize,
+    total: &TotalRow,
+    number_format: &NumberFormat,
+) {
+    row.col(|ui| {
+        ui.label("");
     });
 
     row.col(|ui| {
-        ui.text_edit_singleline(&mut edit_company.name);
+        ui.add_space(depth as f32 * 12.0);
+        ui.label(label);
+    });
+
+    row.col(|ui| {
+        ui.columns(2, |columns| {
+            columns[0].vertical_centered(|ui| {
+                ui.label(number_format.format(total.remainder_begin_month_pos));
+            });
+            columns[1].vertical_centered(|ui| {
+                ui.label(number_format.format(total.remainder_begin_month_neg));
+            });
+        });
+    });
+
+    row.col(|ui| {
+        ui.columns(2, |columns| {
+            columns[0].vertical_centered(|ui| ui.label(number_format.format(total.debit_turnover)));
+            columns[1]
+                .vertical_centered(|ui| ui.label(number_format.format(total.credit_turnover)));
+        });
     });
 
+    row.col(|ui| {
+        ui.columns(2, |columns| {
+            columns[0].vertical_centered(|ui| {
+                ui.label(number_format.format(total.remainder_end_month_pos));
+            });
+            columns[1].vertical_centered(|ui| {
+                ui.label(number_format.format(total.remainder_end_month_neg));
+            });
+        });
+    });
+}
+
+/// A text field for an amount that takes an expression too, with its result or the error
+/// shown under it.
+fn amount_edit(
+    ui: &mut Ui,
+    value: &mut String,
+    number_format: &NumberFormat,
+    language: Language,
+) -> egui::Response {
+    let response = ui.text_edit_singleline(value);
+
+    match evaluate(value, number_format) {
+        Ok(result) if is_expression(value) => {
+            ui.weak(format!("= {}", number_format.format(result)));
+        }
+        Ok(_) => {}
+        Err(error) => {
+            let message = error.message(language);
+            ui.add(
+                egui::Label::new(
+                    egui::RichText::new(&message)
+                        .small()
+                        .color(ui.visuals().error_fg_color),
+                )
+                .truncate(true),
+            )
+            .on_hover_text(message);
+        }
+    }
+
+    response
+}
+
+fn amount_cells(
+    row: &mut TableRow,
+    [pos, neg, debit, credit]: [&mut String; 4],
+    number_format: &NumberFormat,
+    language: Language,
+) {
     row.col(|ui| {
         ui.columns(3, |columns| {
-            if columns[0]
-                .text_edit_singleline(&mut edit_company.remainder_begin_month_pos)
-                .changed()
-            {
-                edit_company.remainder_begin_month_neg.clear();
+            if amount_edit(&mut columns[0], pos, number_format, language).changed() {
+                neg.clear();
             }
 
             columns[1].add(egui::Separator::default().vertical());
-            if columns[2]
-                .text_edit_singleline(&mut edit_company.remainder_begin_month_neg)
-                .changed()
-            {
-                edit_company.remainder_begin_month_pos.clear();
+            if amount_edit(&mut columns[2], neg, number_format, language).changed() {
+                pos.clear();
             }
         });
     });
 
     row.col(|ui| {
         ui.columns(3, |columns| {
-            columns[0].text_edit_singleline(&mut edit_company.debit_turnover);
+            amount_edit(&mut columns[0], debit, number_format, language);
             columns[1].add(egui::Separator::default().vertical());
-            columns[2].text_edit_singleline(&mut edit_company.credit_turnover);
+            amount_edit(&mut columns[2], credit, number_format, language);
         });
     });
 }
 
-fn row_addable() {}
+fn row_editable(
+    row: &mut TableRow,
+    edit_company: &mut EditedCompanyRow,
+    number_format: &NumberFormat,
+    language: Language,
+) {
+    row.col(|ui| {
+        ui.label(format!("{}", edit_company.id));
+    });
+
+    row.col(|ui| {
+        ui.text_edit_singleline(&mut edit_company.name);
+    });
+
+    amount_cells(
+        row,
+        [
+            &mut edit_company.remainder_begin_month_pos,
+            &mut edit_company.remainder_begin_month_neg,
+            &mut edit_company.debit_turnover,
+            &mut edit_company.credit_turnover,
+        ],
+        number_format,
+        language,
+    );
+}
+
+fn row_addable(
+    row: &mut TableRow,
+    new_company: &mut NewCompanyRow,
+    number_format: &NumberFormat,
+    language: Language,
+) {
+    row.col(|ui| {
+        ui.label("");
+    });
+
+    row.col(|ui| {
+        ui.text_edit_singleline(&mut new_company.name);
+    });
+
+    amount_cells(
+        row,
+        [
+            &mut new_company.remainder_begin_month_pos,
+            &mut new_company.remainder_begin_month_neg,
+            &mut new_company.debit_turnover,
+            &mut new_company.credit_turnover,
+        ],
+        number_format,
+        language,
+    );
+}
 
 fn multi_header(ui: &mut Ui, title: &str, cols: Option<[&str; 2]>) {
     ui.vertical_centered(|ui| {
@@ -0,0 +1,199 @@
+use std::collections::{HashMap, HashSet};
+
+use egui::Color32;
+
+use super::{
+    format::NumberFormat,
+    i18n::{Language, Message},
+    model::{Company, Tag},
+    TotalRow,
+};
+
+/// Ids of the tags of every company that has any, by company id.
+pub type CompanyTags = HashMap<i64, Vec<i64>>;
+
+pub fn company_tags(pairs: Vec<(i64, i64)>) -> CompanyTags {
+    let mut company_tags = CompanyTags::new();
+    for (company_id, tag_id) in pairs {
+        company_tags.entry(company_id).or_default().push(tag_id);
+    }
+    company_tags
+}
+
+/// Tags of the company in alphabetical order.
+pub fn tags_of<'a>(tags: &'a [Tag], company_tags: &CompanyTags, id: i64) -> Vec<&'a Tag> {
+    let ids = company_tags.get(&id).map(Vec::as_slice).unwrap_or_default();
+    tags.iter().filter(|tag| ids.contains(&tag.id)).collect()
+}
+
+/// Whether the company has every one of the tags.
+pub fn has_tags(company_tags: &CompanyTags, id: i64, filter: &HashSet<i64>) -> bool {
+    let ids = company_tags.get(&id).map(Vec::as_slice).unwrap_or_default();
+    filter.iter().all(|tag_id| ids.contains(tag_id))
+}
+
+/// A hue of its own for every tag, spread around the color wheel by the golden ratio.
+fn tag_color(id: i64) -> Color32 {
+    let hue = (id as f32 * 0.618_034).fract();
+    egui::ecolor::Hsva::new(hue, 0.45, 0.75, 0.6).into()
+}
+
+pub fn tag_chip(ui: &mut egui::Ui, tag: &Tag) -> egui::Response {
+    egui::Frame::none()
+        .fill(tag_color(tag.id))
+        .rounding(8.0)
+        .inner_margin(egui::Margin::symmetric(6.0, 0.0))
+        .show(ui, |ui| {
+            ui.label(
+                egui::RichText::new(&tag.name)
+                    .small()
+                    .color(ui.visuals().strong_text_color()),
+            )
+        })
+        .response
+}
+
+/// Checkboxes over every tag, a company is shown when it has all the checked ones.
+pub fn tag_filter_menu(
+    ui: &mut egui::Ui,
+    tags: &[Tag],
+    filter: &mut HashSet<i64>,
+    language: Language,
+) {
+    let title = match filter.len() {
+        0 => language.tr(Message::Tags).to_string(),
+        count => format!("{} ({count})", language.tr(Message::Tags)),
+    };
+
+    ui.menu_button(title, |ui| {
+        for tag in tags {
+            let mut checked = filter.contains(&tag.id);
+            if ui.checkbox(&mut checked, &tag.name).changed() {
+                if checked {
+                    filter.insert(tag.id);
+                } else {
+                    filter.remove(&tag.id);
+                }
+            }
+        }
+        ui.separator();
+        if ui.button(language.tr(Message::AnyTags)).clicked() {
+            filter.clear();
+            ui.close_menu();
+        }
+    });
+}
+
+pub struct TagTotal {
+    /// `None` for the companies without tags.
+    pub id: Option<i64>,
+    pub name: String,
+    pub count: usize,
+    pub total: TotalRow,
+}
+
+/// Subtotals of every tag and of the companies without tags. A company with several tags is
+/// counted under each of them.
+pub fn tag_totals(
+    companies: &[Company],
+    tags: &[Tag],
+    company_tags: &CompanyTags,
+    language: Language,
+) -> Vec<TagTotal> {
+    let tag_ids = |company: &Company| company_tags.get(&company.id).cloned().unwrap_or_default();
+    let total = |id: Option<i64>, name: &str| {
+        let members: Vec<_> = companies
+            .iter()
+            .filter(|company| match id {
+                Some(id) => tag_ids(company).contains(&id),
+                None => tag_ids(company).is_empty(),
+            })
+            .collect();
+
+        TagTotal {
+            id,
+            name: name.to_string(),
+            count: members.len(),
+            total: members.into_iter().collect(),
+        }
+    };
+
+    tags.iter()
+        .map(|tag| total(Some(tag.id), &tag.name))
+        .chain([total(None, language.tr(Message::NoTags))])
+        .collect()
+}
+
+#[derive(Default)]
+pub struct TagsWindow {
+    pub open: bool,
+}
+
+impl TagsWindow {
+    /// Returns the id of the tag to delete.
+    pub fn window_ui(
+        &mut self,
+        ctx: &egui::Context,
+        totals: &[TagTotal],
+        number_format: &NumberFormat,
+        language: Language,
+    ) -> Option<i64> {
+        let mut delete = None;
+
+        egui::Window::new(language.tr(Message::TagSubtotals))
+            .open(&mut self.open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.weak(language.tr(Message::TagSubtotalsHint));
+                egui::ScrollArea::vertical()
+                    .max_height(400.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("tag_totals_grid")
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.strong(language.tr(Message::Tags));
+                                ui.strong(language.tr(Message::Companies));
+                                for (title, side) in [
+                                    (Message::RemainderBeginMonth, Message::Debit),
+                                    (Message::RemainderBeginMonth, Message::Credit),
+                                    (Message::Turnover, Message::Debit),
+                                    (Message::Turnover, Message::Credit),
+                                    (Message::RemainderEndMonth, Message::Debit),
+                                    (Message::RemainderEndMonth, Message::Credit),
+                                ] {
+                                    ui.strong(format!(
+                                        "{}, {}",
+                                        language.tr(title),
+                                        language.tr(side).to_lowercase()
+                                    ));
+                                }
+                                ui.end_row();
+
+                                for row in totals {
+                                    ui.label(&row.name);
+                                    ui.label(row.count.to_string());
+                                    let total = &row.total;
+                                    for value in [
+                                        total.remainder_begin_month_pos,
+                                        total.remainder_begin_month_neg,
+                                        total.debit_turnover,
+                                        total.credit_turnover,
+                                        total.remainder_end_month_pos,
+                                        total.remainder_end_month_neg,
+                                    ] {
+                                        ui.label(number_format.format(value));
+                                    }
+                                    if let Some(id) = row.id {
+                                        if ui.button(language.tr(Message::Delete)).clicked() {
+                                            delete = Some(id);
+                                        }
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            });
+
+        delete
+    }
+}
@@ -0,0 +1,125 @@
+use super::i18n::Message;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TaxIdKind {
+    /// Russian ИНН of an organization, 10 digits.
+    InnLegal,
+    /// Russian ИНН of an individual, 12 digits.
+    InnIndividual,
+    /// Azerbaijani VÖEN, 10 digits ending with 1 for organizations and 2 for individuals.
+    Voen,
+}
+
+impl TaxIdKind {
+    pub fn title(self) -> Message {
+        match self {
+            TaxIdKind::InnLegal | TaxIdKind::InnIndividual => Message::Inn,
+            TaxIdKind::Voen => Message::Voen,
+        }
+    }
+}
+
+pub fn validate_tax_id(tax_id: &str) -> Result<TaxIdKind, Message> {
+    let digits: Vec<u32> = tax_id.chars().filter_map(|c| c.to_digit(10)).collect();
+
+    if digits.len() != tax_id.chars().count() {
+        return Err(Message::TaxIdNotDigits);
+    }
+
+    let check = |weights: &[u32]| {
+        let sum: u32 = weights.iter().zip(&digits).map(|(w, d)| w * d).sum();
+        sum % 11 % 10
+    };
+
+    match digits.len() {
+        10 if check(&[2, 4, 10, 3, 5, 9, 4, 6, 8]) == digits[9] => Ok(TaxIdKind::InnLegal),
+        // the tax service doesn't publish a check digit algorithm for VÖEN, only the layout
+        10 if matches!(digits[9], 1 | 2) => Ok(TaxIdKind::Voen),
+        12 if check(&[7, 2, 4, 10, 3, 5, 9, 4, 6, 8]) == digits[10]
+            && check(&[3, 7, 2, 4, 10, 3, 5, 9, 4, 6, 8]) == digits[11] =>
+        {
+            Ok(TaxIdKind::InnIndividual)
+        }
+        10 | 12 => Err(Message::TaxIdChecksum),
+        _ => Err(Message::TaxIdLength),
+    }
+}
+
+pub fn validate_iban(iban: &str) -> Result<(), Message> {
+    let iban: String = iban.chars().filter(|c| !c.is_whitespace()).collect();
+
+    if !(15..=34).contains(&iban.len()) || !iban.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(Message::IbanFormat);
+    }
+
+    let (country, rest) = iban.split_at(4);
+    if !country[..2].chars().all(|c| c.is_ascii_uppercase())
+        || !country[2..].chars().all(|c| c.is_ascii_digit())
+    {
+        return Err(Message::IbanFormat);
+    }
+
+    // ISO 13616: move the country code and check digits to the end, replace letters with
+    // numbers starting from A = 10 and take the remainder of division by 97
+    let remainder = rest
+        .chars()
+        .chain(country.chars())
+        .filter_map(|c| c.to_digit(36))
+        .fold(0, |acc, value| {
+            if value < 10 {
+                (acc * 10 + value) % 97
+            } else {
+                (acc * 100 + value) % 97
+            }
+        });
+
+    if remainder == 1 {
+        Ok(())
+    } else {
+        Err(Message::IbanChecksum)
+    }
+}
+
+pub fn validate_bic(bic: &str) -> Result<(), Message> {
+    let valid = bic.is_ascii()
+        && matches!(bic.len(), 8 | 11)
+        && bic[..6].chars().all(|c| c.is_ascii_uppercase())
+        && bic[6..]
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+
+    valid.then_some(()).ok_or(Message::BicFormat)
+}
+
+/// ISO 4217 code such as USD.
+pub fn validate_currency(currency: &str) -> Result<(), Message> {
+    let valid = currency.len() == 3 && currency.chars().all(|c| c.is_ascii_uppercase());
+
+    valid.then_some(()).ok_or(Message::CurrencyFormat)
+}
+
+pub fn validate_email(email: &str) -> Result<(), Message> {
+    let valid = match email.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.split('.').filter(|part| !part.is_empty()).count() >= 2
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+        }
+        None => false,
+    };
+
+    valid.then_some(()).ok_or(Message::EmailFormat)
+}
+
+pub fn validate_phone(phone: &str) -> Result<(), Message> {
+    let allowed = phone
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, '+' | '-' | '(' | ')' | ' '));
+    let digits = phone.chars().filter(char::is_ascii_digit).count();
+
+    (allowed && digits >= 5)
+        .then_some(())
+        .ok_or(Message::PhoneFormat)
+}
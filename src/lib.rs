@@ -13,6 +13,22 @@ pub enum AppError{
     EframeError{error: eframe::Error}
 }
 
+/// Runs a command given on the command line against the same database as the window.
+pub fn run_command(args: &[String]) -> Result<(), AppError> {
+    let rt = Builder::new_current_thread().enable_all().build().unwrap();
+
+    let db = rt
+        .block_on(database::get_pooled_connection("test.db"))
+        .map_err(|err| AppError::StdError { error: Box::new(err) })?;
+
+    rt.block_on(async {
+        sqlx::migrate!().run(&db).await
+    }).expect("Couldn't migrate");
+
+    rt.block_on(app::cli::run(db, args))
+        .map_err(|error| AppError::StdError { error })
+}
+
 pub fn run(options: NativeOptions) -> Result<(), AppError> {
     let rt = Builder::new_current_thread().enable_all().build().unwrap();
 
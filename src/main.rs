@@ -5,6 +5,19 @@ use company_calc::AppError;
 
 fn main() -> Result<(), AppError> {
     env_logger::init(); // Log to stderr (if you run with `RUST_LOG=debug`).
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        attach_parent_console();
+
+        // printed as it is, the usage and import conflicts are written for a person to read
+        if let Err(AppError::StdError { error }) = company_calc::run_command(&args) {
+            eprintln!("{error}");
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default().with_inner_size([800.0, 600.0]),
         ..Default::default()
@@ -13,3 +26,23 @@ fn main() -> Result<(), AppError> {
     company_calc::run(options)
 }
 
+
+/// A release build on Windows has no console of its own, so the command line output goes to the
+/// one it was started from. Started from Explorer there is none and nothing is printed.
+#[cfg(windows)]
+fn attach_parent_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+
+    // fails when there is no parent console or the process already has one, as in debug builds
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(windows))]
+fn attach_parent_console() {}